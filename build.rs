@@ -1,6 +1,31 @@
 fn main() {
     use png::Decoder;
-    use std::{env, fs::File, io::Write};
+    use std::{
+        env,
+        fs::{read_to_string, File},
+        io::Write,
+        path::Path,
+        process::Command,
+    };
+
+    // Listing anything here stops Cargo from rerunning this on every change to the package, so
+    // everything read below has to be listed.
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=extra/logo/ablavema256.png");
+    println!("cargo:rerun-if-changed=Cargo.lock");
+    // The commit hash changes along with the checked out branch, or the branch it points to.
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    if let Some(head_ref) = read_to_string(".git/HEAD")
+        .ok()
+        .and_then(|head| Some(head.strip_prefix("ref: ")?.trim().to_string()))
+    {
+        // Refs that haven't changed in a while may only be in the packed refs.
+        if Path::new(".git").join(&head_ref).exists() {
+            println!("cargo:rerun-if-changed=.git/{}", head_ref);
+        } else {
+            println!("cargo:rerun-if-changed=.git/packed-refs");
+        }
+    }
 
     let ablavema32_file = File::open("extra/logo/ablavema256.png").unwrap();
     let decoder = Decoder::new(ablavema32_file);
@@ -19,8 +44,50 @@ fn main() {
     println!("cargo:rustc-env=ICED_ICON_WIDTH={}", info.width);
     println!("cargo:rustc-env=ICED_ICON_HEIGHT={}", info.height);
 
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| String::from("unknown"));
+    println!("cargo:rustc-env=ABLAVEMA_GIT_HASH={}", git_hash);
+    println!(
+        "cargo:rustc-env=ABLAVEMA_BUILD_DATE={}",
+        chrono::Utc::now().format("%B %d, %Y")
+    );
+
+    // One "name version" line per third-party crate, shown in the About tab. They're read from
+    // the lock file, where only the crates that come from elsewhere have a source.
+    let mut credits: Vec<String> = read_to_string("Cargo.lock")
+        .unwrap_or_default()
+        .split("[[package]]")
+        .filter(|package| package.contains("\nsource = "))
+        .filter_map(|package| {
+            let field = |key: &str| {
+                package.lines().find_map(|line| {
+                    Some(
+                        line.strip_prefix(key)?
+                            .strip_prefix(" = ")?
+                            .trim_matches('"'),
+                    )
+                })
+            };
+            Some(format!("{} {}", field("name")?, field("version")?))
+        })
+        .collect();
+    credits.sort();
+    let credits = credits.join("\n");
+    let credits_path = format!("{}/{}", env::var("OUT_DIR").unwrap(), "credits");
+    File::create(&credits_path)
+        .unwrap()
+        .write_all(credits.as_bytes())
+        .unwrap();
+    println!("cargo:rustc-env=ABLAVEMA_CREDITS_PATH={}", credits_path);
+
     #[cfg(target_os = "windows")]
     {
+        println!("cargo:rerun-if-changed=extra/windows/ablavema.ico");
         let mut resource = winres::WindowsResource::new();
         resource.set_icon("extra/windows/ablavema.ico");
         resource.set("FileDescription", "Ablavema");
@@ -1,23 +1,168 @@
+pub mod schema;
+
+use self::schema::InstalledPackage;
+#[cfg(feature = "gui")]
+use crate::gui::monitor::Monitor;
 use crate::{
-    gui::extra::GuiFlags,
+    gui::install::{DownloadSlots, Install, Progress},
     helpers::is_time_to_update,
-    releases::Releases,
+    package::{Build, Package, PackageState, PackageStatus, ARCHIVE_EXTENSIONS},
+    releases::{
+        daily_latest::DailyLatest, experimental_latest::ExperimentalLatest, installed::Installed,
+        lts::Lts, patch_latest::PatchLatest, stable_latest::StableLatest, ReleaseType, Releases,
+    },
     self_updater::SelfUpdater,
-    settings::{get_setting, CAN_CONNECT, LAUNCH_GUI},
+    settings::{get_setting, SettingsService, CAN_CONNECT, LAUNCH_GUI},
 };
-use clap::{command, Arg};
+use clap::{command, Arg, Command};
+#[cfg(feature = "gui")]
 use device_query::{DeviceQuery, DeviceState};
-use std::sync::atomic::Ordering;
+use futures::StreamExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use self_update::update::Release;
+use std::{collections::HashSet, process::exit, sync::atomic::Ordering, time::SystemTime};
+
+/// What the command line leaves for the launcher's window to do.
+#[derive(Debug)]
+#[cfg_attr(not(feature = "gui"), allow(dead_code))]
+pub struct GuiFlags {
+    pub releases: Releases,
+    pub file_paths: Vec<String>,
+    pub self_releases: Option<Vec<Release>>,
+    /// Updates to install in the background as soon as the GUI starts.
+    pub auto_install: Vec<Package>,
+    /// Whether to start with the quick switch instead of the whole launcher.
+    pub quick_switch: bool,
+    /// Whether to start with only the installed packages in a small window.
+    pub compact: bool,
+    /// The monitors there were at launch, asked for right before the window is made.
+    #[cfg(feature = "gui")]
+    pub monitors: Vec<Monitor>,
+}
 
 pub async fn run_cli() -> GuiFlags {
     let args = command!()
         .arg(
             Arg::new("path")
                 .value_name("PATH")
-                .help("Path to .blend file"),
+                .help("Path to .blend file")
+                .multiple_values(true),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .help("Open a small window with only the installed packages"),
+        )
+        .arg(
+            Arg::new("list-installed")
+                .long("list-installed")
+                .help("Print the installed packages and exit"),
+        )
+        .arg(
+            Arg::new("format")
+                .long("format")
+                .value_name("FORMAT")
+                .help("Output format of --list-installed")
+                .possible_values(["json"])
+                .default_value("json"),
+        )
+        .subcommand(
+            Command::new("install")
+                .about("Install a package without opening the GUI")
+                .arg(
+                    Arg::new("package")
+                        .value_name("PACKAGE_OR_URL")
+                        .help("Name of a fetched package or a direct download URL")
+                        .required(true),
+                ),
+        )
+        .subcommand(
+            Command::new("remove")
+                .about("Remove an installed package without opening the GUI")
+                .arg(
+                    Arg::new("package")
+                        .value_name("PACKAGE")
+                        .help("Exact name of an installed package")
+                        .required(true),
+                )
+                .arg(
+                    Arg::new("all")
+                        .long("all")
+                        .help("Remove every package with the name, if there's more than one"),
+                ),
+        )
+        .subcommand(
+            Command::new("update-all")
+                .about(
+                    "Check for updates and install them without opening the GUI. Checks every \
+                    build type unless some are picked. Exits with 0 when everything was up to \
+                    date, 1 when updates were installed and 2 on errors",
+                )
+                .arg(
+                    Arg::new("daily")
+                        .long("daily")
+                        .help("Update the daily builds"),
+                )
+                .arg(
+                    Arg::new("branched")
+                        .long("branched")
+                        .help("Update the experimental and patch builds"),
+                )
+                .arg(
+                    Arg::new("stable")
+                        .long("stable")
+                        .help("Update the stable release"),
+                )
+                .arg(
+                    Arg::new("lts")
+                        .long("lts")
+                        .help("Update the long-term support releases"),
+                ),
         )
         .get_matches();
 
+    if let Some(install_args) = args.subcommand_matches("install") {
+        let mut releases = Releases::init().await;
+        exit(
+            install_package(
+                &mut releases,
+                install_args.value_of("package").unwrap(),
+                &DownloadSlots::new(get_setting().max_concurrent_downloads),
+            )
+            .await,
+        );
+    }
+
+    if let Some(update_args) = args.subcommand_matches("update-all") {
+        let build_types = UpdateBuildTypes {
+            daily: update_args.is_present("daily"),
+            branched: update_args.is_present("branched"),
+            stable: update_args.is_present("stable"),
+            lts: update_args.is_present("lts"),
+        };
+        exit(update_all(build_types.or_all()).await);
+    }
+
+    if let Some(remove_args) = args.subcommand_matches("remove") {
+        exit(remove_package(
+            remove_args.value_of("package").unwrap(),
+            remove_args.is_present("all"),
+        ));
+    }
+
+    if args.is_present("list-installed") {
+        let mut installed = Installed::default();
+        installed.fetch();
+        let packages: Vec<InstalledPackage> =
+            installed.iter().map(InstalledPackage::from).collect();
+
+        match args.value_of("format") {
+            Some("json") => println!("{}", serde_json::to_string_pretty(&packages).unwrap()),
+            _ => unreachable!("unsupported format"),
+        }
+        exit(0);
+    }
+
     let mut releases = Releases::init().await;
     let mut self_releases = None;
 
@@ -48,7 +193,10 @@ pub async fn run_cli() -> GuiFlags {
         && is_time_to_update()
         && CAN_CONNECT.load(Ordering::Relaxed)
     {
-        self_releases = SelfUpdater::fetch();
+        self_releases = SelfUpdater::fetch().unwrap_or_else(|e| {
+            log::error!("{}", e);
+            None
+        });
 
         if let Some(updates) = SelfUpdater::count_new(&self_releases) {
             println!(
@@ -60,20 +208,387 @@ pub async fn run_cli() -> GuiFlags {
         }
     }
 
-    if get_setting().bypass_launcher && !LAUNCH_GUI.load(Ordering::Relaxed) {
-        let device_state = DeviceState::new();
-        let keys = device_state.get_keys();
+    // Holding the modifier key down while bypassing the launcher asks for the quick switch,
+    // unless there's a reason to show the whole launcher anyway.
+    #[allow(unused_mut)]
+    let mut quick_switch = false;
+    let compact = args.is_present("compact");
+    if compact {
+        LAUNCH_GUI.store(true, Ordering::Relaxed);
+    } else if get_setting().bypass_launcher && !LAUNCH_GUI.load(Ordering::Relaxed) {
+        #[cfg(feature = "gui")]
+        {
+            let device_state = DeviceState::new();
+            let keys = device_state.get_keys();
 
-        if keys.contains(&get_setting().modifier_key.get_keycode()) {
-            LAUNCH_GUI.store(true, Ordering::Relaxed);
+            if keys.contains(&get_setting().modifier_key.get_keycode()) {
+                LAUNCH_GUI.store(true, Ordering::Relaxed);
+                quick_switch = true;
+            }
         }
     } else {
         LAUNCH_GUI.store(true, Ordering::Relaxed);
     }
 
+    let mut auto_install = Vec::new();
+    if get_setting().check_updates_at_launch
+        && get_setting().auto_install_updates
+        && CAN_CONNECT.load(Ordering::Relaxed)
+    {
+        releases.sync();
+        auto_install = releases
+            .build_vec()
+            .into_iter()
+            .filter(|package| {
+                package.status == PackageStatus::Update
+                    && !matches!(package.state, PackageState::Installed)
+                    && get_setting().auto_installs(&package.build)
+            })
+            .collect();
+    }
+
+    // Without the GUI there's nothing to install them in the background, so they're installed
+    // right away for the default package to switch to the update before it's opened.
+    if (!LAUNCH_GUI.load(Ordering::Relaxed) || cfg!(not(feature = "gui")))
+        && get_setting().default_package.is_some()
+    {
+        let download_slots = DownloadSlots::new(get_setting().max_concurrent_downloads);
+        for package in auto_install.drain(..) {
+            install_package(&mut releases, &package.name, &download_slots).await;
+        }
+    }
+
     GuiFlags {
         releases,
-        file_path: args.value_of("path").map(|file_path| file_path.to_string()),
+        file_paths: args
+            .values_of("path")
+            .map(|file_paths| file_paths.map(|file_path| file_path.to_string()).collect())
+            .unwrap_or_default(),
         self_releases,
+        auto_install,
+        quick_switch,
+        compact,
+        #[cfg(feature = "gui")]
+        monitors: Vec::new(),
+    }
+}
+
+/// Installs a package by its name or download URL, returning the exit code.
+async fn install_package(
+    releases: &mut Releases,
+    target: &str,
+    download_slots: &DownloadSlots,
+) -> i32 {
+    if !CAN_CONNECT.load(Ordering::Relaxed) {
+        eprintln!("Failed to connect to server, can't install {}.", target);
+        return 1;
+    }
+
+    let package = match releases
+        .build_vec()
+        .into_iter()
+        .find(|package| package.name == target || package.url == target)
+    {
+        Some(package) => package,
+        None if target.starts_with("https://") || target.starts_with("http://") => {
+            // The version is guessed from archive names like blender-3.2.0-linux-x64.tar.xz.
+            let file_name = target.rsplit('/').next().unwrap_or_default();
+            let version = ARCHIVE_EXTENSIONS
+                .iter()
+                .find_map(|extension| file_name.strip_suffix(extension))
+                .unwrap_or(file_name)
+                .split('-')
+                .find(|part| part.starts_with(|c: char| c.is_ascii_digit()))
+                .unwrap_or("0.0.0")
+                .to_string();
+            match Package::from_url(target, "", &version) {
+                Ok(package) => package,
+                Err(e) => {
+                    eprintln!("Can't install {}: {}.", target, e);
+                    return 1;
+                }
+            }
+        }
+        None => {
+            eprintln!(
+                "No package named {} was found. Check for updates or fetch the releases first.",
+                target
+            );
+            return 1;
+        }
+    };
+
+    if let PackageState::Installed = package.state {
+        println!("{} is already installed.", package.name);
+        return 0;
+    }
+
+    let progress_bar = ProgressBar::new(100);
+    progress_bar.set_style(
+        ProgressStyle::default_bar()
+            .template("{prefix:>11} [{bar:40}] {pos:>3}% {msg}")
+            .progress_chars("=> "),
+    );
+    progress_bar.set_prefix("Queued");
+
+    let mut installation = Install::standalone(package.clone(), download_slots.clone());
+
+    while let Some((_, progress)) = installation.next().await {
+        match progress {
+            Progress::Started(_) => progress_bar.set_prefix("Downloading"),
+            Progress::DownloadProgress {
+                progress,
+                bytes_per_sec,
+                eta_secs,
+                chunk_size,
+                ..
+            } => {
+                SettingsService::update_unsaved(|settings| {
+                    settings.download_stats.total_bytes_downloaded += chunk_size
+                });
+                progress_bar.set_position(progress as u64);
+                progress_bar.set_message(format!(
+                    "{:.1} MB/s{}",
+                    bytes_per_sec / 1024.0 / 1024.0,
+                    match eta_secs {
+                        Some(eta) => format!(" - ETA {} s", eta),
+                        None => String::new(),
+                    }
+                ));
+            }
+            Progress::FinishedDownloading => {
+                progress_bar.set_prefix("Verifying");
+                progress_bar.set_message("");
+            }
+            Progress::ChecksumVerified | Progress::ChecksumUnavailable => {
+                if let Progress::ChecksumUnavailable = progress {
+                    progress_bar.println("No checksum was published for this package.");
+                }
+                progress_bar.set_prefix("Extracting");
+                progress_bar.set_position(0);
+            }
+            Progress::ExtractionProgress(progress) => progress_bar.set_position(progress as u64),
+            Progress::FinishedExtracting => (),
+            Progress::FinishedInstalling => {
+                progress_bar.finish_and_clear();
+                break;
+            }
+            Progress::Cancelled => {
+                progress_bar.abandon();
+                eprintln!("Installation of {} was cancelled.", package.name);
+                return 1;
+            }
+            Progress::ChecksumFailed(message) | Progress::Errored(message) => {
+                progress_bar.abandon();
+                eprintln!("Failed to install {}: {}.", package.name, message);
+                return 1;
+            }
+        }
+    }
+
+    releases.installed.fetch();
+    releases.installed.update_default();
+    releases
+        .installed
+        .remove_old_packages(&HashSet::from([package.name.clone()]));
+    releases.installed.fetch();
+    SettingsService::update(|settings| settings.download_stats.total_packages_installed += 1);
+    println!("Installed {}.", package.name);
+    0
+}
+
+/// The build types picked for `update-all`.
+struct UpdateBuildTypes {
+    daily: bool,
+    branched: bool,
+    stable: bool,
+    lts: bool,
+}
+
+impl UpdateBuildTypes {
+    /// Picking none of them means all of them.
+    fn or_all(self) -> Self {
+        if self.daily || self.branched || self.stable || self.lts {
+            self
+        } else {
+            UpdateBuildTypes {
+                daily: true,
+                branched: true,
+                stable: true,
+                lts: true,
+            }
+        }
+    }
+
+    fn includes(&self, build: &Build) -> bool {
+        match build {
+            Build::DailyLatest(_) => self.daily,
+            Build::ExperimentalLatest(_) | Build::PatchLatest(_) => self.branched,
+            Build::StableLatest => self.stable,
+            Build::Lts(_) => self.lts,
+            Build::DailyArchive(_)
+            | Build::ExperimentalArchive(_)
+            | Build::PatchArchive(_)
+            | Build::StableArchive => false,
+        }
+    }
+}
+
+/// Checks the picked build types for updates to the installed packages and installs them,
+/// returning the exit code: 0 when there was nothing to update, 1 when updates were installed
+/// and 2 when something went wrong.
+async fn update_all(build_types: UpdateBuildTypes) -> i32 {
+    let mut releases = Releases::init().await;
+    if !CAN_CONNECT.load(Ordering::Relaxed) {
+        eprintln!("Failed to connect to server, can't check for updates.");
+        return 2;
+    }
+
+    let (mut daily_latest, mut experimental_latest, mut patch_latest, mut stable_latest, mut lts) =
+        releases.take();
+    if build_types.daily {
+        println!("Checking daily builds...");
+        daily_latest = DailyLatest::check_updates(daily_latest).await.1;
+    }
+    if build_types.branched {
+        println!("Checking experimental and patch builds...");
+        experimental_latest = ExperimentalLatest::check_updates(experimental_latest)
+            .await
+            .1;
+        patch_latest = PatchLatest::check_updates(patch_latest).await.1;
+    }
+    if build_types.stable {
+        println!("Checking stable release...");
+        stable_latest = StableLatest::check_updates(stable_latest).await.1;
+    }
+    if build_types.lts {
+        println!("Checking long-term support releases...");
+        lts = Lts::check_updates(lts).await.1;
+    }
+    releases.add_new_packages((
+        false,
+        daily_latest,
+        experimental_latest,
+        patch_latest,
+        stable_latest,
+        lts,
+    ));
+    SettingsService::update(|settings| settings.last_update_time = SystemTime::now());
+    releases.sync();
+
+    let updates: Vec<Package> = releases
+        .build_vec()
+        .into_iter()
+        .filter(|package| {
+            package.status == PackageStatus::Update
+                && !matches!(package.state, PackageState::Installed)
+                && build_types.includes(&package.build)
+        })
+        .collect();
+
+    if updates.is_empty() {
+        println!("Everything is up to date.");
+        return 0;
+    }
+
+    let mut updated = Vec::new();
+    let mut failed = Vec::new();
+    let download_slots = DownloadSlots::new(get_setting().max_concurrent_downloads);
+    for package in updates {
+        if install_package(&mut releases, &package.name, &download_slots).await == 0 {
+            updated.push(package.name);
+        } else {
+            failed.push(package.name);
+        }
+    }
+
+    if !updated.is_empty() {
+        println!(
+            "Updated {} package{}:",
+            updated.len(),
+            if updated.len() > 1 { "s" } else { "" }
+        );
+        for name in &updated {
+            println!("  {}", name);
+        }
+    }
+    if failed.is_empty() {
+        1
+    } else {
+        eprintln!(
+            "Failed to update {} package{}:",
+            failed.len(),
+            if failed.len() > 1 { "s" } else { "" }
+        );
+        for name in &failed {
+            eprintln!("  {}", name);
+        }
+        2
+    }
+}
+
+/// Removes the installed package with exactly the given name, returning the exit code. When
+/// nothing has that name, the packages containing it are listed and nothing is removed.
+fn remove_package(target: &str, all: bool) -> i32 {
+    let mut installed = Installed::default();
+    installed.fetch();
+
+    let packages: Vec<Package> = installed
+        .iter()
+        .filter(|package| package.name == target)
+        .cloned()
+        .collect();
+
+    if packages.is_empty() {
+        let candidates: Vec<&Package> = installed
+            .iter()
+            .filter(|package| package.name.contains(target))
+            .collect();
+        if candidates.is_empty() {
+            eprintln!("No installed package is named {}.", target);
+        } else {
+            eprintln!("No installed package is named {}. Did you mean:", target);
+            for package in candidates {
+                eprintln!("  {}", package.name);
+            }
+        }
+        return 1;
+    }
+
+    if packages.len() > 1 && !all {
+        eprintln!("Multiple installed packages are named {}:", target);
+        for package in &packages {
+            eprintln!("  {}", package.name);
+        }
+        eprintln!("Pass --all to remove all of them.");
+        return 1;
+    }
+
+    let mut skipped = false;
+    for package in &packages {
+        // Local builds aren't Ablavema's to delete, they can only be forgotten from the GUI.
+        if package.local_path.is_some() {
+            eprintln!("Not removing {}, since it's a local build.", package.name);
+            skipped = true;
+            continue;
+        }
+        package.remove();
+        let was_default = SettingsService::update(|settings| {
+            settings.download_stats.total_packages_removed += 1;
+            let was_default = settings.default_package.as_ref() == Some(package);
+            if was_default {
+                settings.default_package = None;
+            }
+            was_default
+        });
+        if was_default {
+            println!("{} was the default package, unset it.", package.name);
+        }
+    }
+
+    if skipped {
+        1
+    } else {
+        0
     }
 }
@@ -0,0 +1,39 @@
+//! Shapes of the machine-readable output printed by the command line interface.
+//!
+//! Fields are only ever added to these types, so anything parsing the output can rely on the
+//! existing ones staying as they are.
+
+use crate::{package::Package, settings::get_setting};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// An entry of the array printed by `--list-installed`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct InstalledPackage {
+    /// Directory name of the package, also used to refer to it elsewhere.
+    pub name: String,
+    /// Blender version, such as "3.2.0".
+    pub version: String,
+    /// Where the package comes from, such as "Daily (latest): Alpha".
+    pub build: String,
+    /// Date the package was built on, in UTC.
+    pub date: NaiveDateTime,
+    /// Directory the package is installed in.
+    pub path: PathBuf,
+}
+
+impl From<&Package> for InstalledPackage {
+    fn from(package: &Package) -> Self {
+        Self {
+            name: package.name.clone(),
+            version: package.version.to_string(),
+            build: package.build.to_string(),
+            date: package.date,
+            path: match &package.local_path {
+                Some(local_path) => local_path.clone(),
+                None => get_setting().packages_dir.join(&package.name),
+            },
+        }
+    }
+}
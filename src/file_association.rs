@@ -0,0 +1,248 @@
+//! Making .blend files open with Ablavema, and through it with the default package.
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use std::{env::current_exe, path::PathBuf};
+
+#[cfg(target_os = "linux")]
+use directories_next::BaseDirs;
+#[cfg(target_os = "linux")]
+use std::{
+    fs::{create_dir_all, read_to_string, remove_file, write},
+    process::Command,
+};
+
+#[cfg(target_os = "windows")]
+use std::process::Command;
+
+#[cfg(target_os = "linux")]
+const DESKTOP_FILE: &str = "ablavema.desktop";
+#[cfg(target_os = "linux")]
+const MIME_TYPE: &str = "application/x-blender";
+
+#[cfg(target_os = "windows")]
+const EXTENSION_KEY: &str = r"HKCU\Software\Classes\.blend";
+#[cfg(target_os = "windows")]
+const PROG_ID: &str = "Ablavema.blend";
+#[cfg(target_os = "windows")]
+const PROG_ID_KEY: &str = r"HKCU\Software\Classes\Ablavema.blend";
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn executable() -> Result<PathBuf, String> {
+    current_exe().map_err(|e| format!("couldn't find Ablavema's executable: {}", e))
+}
+
+#[cfg(target_os = "linux")]
+fn desktop_file_path() -> Result<PathBuf, String> {
+    let base_dirs = BaseDirs::new().ok_or("couldn't find the home directory")?;
+    Ok(base_dirs.data_dir().join("applications").join(DESKTOP_FILE))
+}
+
+/// The desktop entry shipped for packaging, pointed at this executable since it's likely not
+/// on the `PATH` when registered from the launcher.
+#[cfg(target_os = "linux")]
+fn desktop_entry(executable: &std::path::Path) -> String {
+    include_str!("../extra/linux/Ablavema.desktop")
+        .lines()
+        .map(|line| {
+            if line.starts_with("Exec=") {
+                format!("Exec=\"{}\" %f", executable.display())
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+        + "\n"
+}
+
+#[cfg(target_os = "linux")]
+pub fn is_registered() -> bool {
+    desktop_file_path()
+        .map(|path| path.exists())
+        .unwrap_or_default()
+}
+
+/// Writes the desktop file for the current user and makes it the default for .blend files.
+#[cfg(target_os = "linux")]
+pub fn register() -> Result<(), String> {
+    let path = desktop_file_path()?;
+    create_dir_all(path.parent().unwrap()).map_err(|e| format!("{}: {}", path.display(), e))?;
+    write(&path, desktop_entry(&executable()?))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let status = Command::new("xdg-mime")
+        .args(["default", DESKTOP_FILE, MIME_TYPE])
+        .status()
+        .map_err(|e| format!("couldn't run xdg-mime: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("xdg-mime failed with {}", status))
+    }
+}
+
+/// Removes the desktop file and takes it out of the user's `mimeapps.list`, which `xdg-mime`
+/// has no way of doing.
+#[cfg(target_os = "linux")]
+pub fn unregister() -> Result<(), String> {
+    let path = desktop_file_path()?;
+    if path.exists() {
+        remove_file(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    }
+
+    let base_dirs = BaseDirs::new().ok_or("couldn't find the home directory")?;
+    let mimeapps_path = base_dirs.config_dir().join("mimeapps.list");
+    let mimeapps = match read_to_string(&mimeapps_path) {
+        Ok(mimeapps) => mimeapps,
+        Err(_) => return Ok(()),
+    };
+    write(&mimeapps_path, without_desktop_file(&mimeapps))
+        .map_err(|e| format!("{}: {}", mimeapps_path.display(), e))
+}
+
+/// Takes the desktop file out of the associations for .blend files, dropping the line
+/// altogether when nothing else is left on it.
+#[cfg(target_os = "linux")]
+fn without_desktop_file(mimeapps: &str) -> String {
+    let prefix = format!("{}=", MIME_TYPE);
+    mimeapps
+        .lines()
+        .filter_map(|line| match line.strip_prefix(&prefix) {
+            Some(desktop_files) => {
+                let desktop_files: Vec<&str> = desktop_files
+                    .split(';')
+                    .filter(|desktop_file| {
+                        !desktop_file.is_empty() && *desktop_file != DESKTOP_FILE
+                    })
+                    .collect();
+                (!desktop_files.is_empty())
+                    .then(|| format!("{}{};", prefix, desktop_files.join(";")))
+            }
+            None => Some(line.to_string()),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+        + "\n"
+}
+
+#[cfg(target_os = "windows")]
+fn reg(args: &[&str]) -> Result<(), String> {
+    let status = Command::new("reg")
+        .args(args)
+        .status()
+        .map_err(|e| format!("couldn't run reg: {}", e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("reg failed with {}", status))
+    }
+}
+
+#[cfg(target_os = "windows")]
+pub fn is_registered() -> bool {
+    Command::new("reg")
+        .args(["query", PROG_ID_KEY])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or_default()
+}
+
+/// Registers a program ID under the current user's classes and points .blend files to it.
+#[cfg(target_os = "windows")]
+pub fn register() -> Result<(), String> {
+    let command = format!("\"{}\" \"%1\"", executable()?.display());
+    reg(&[
+        "add",
+        &format!(r"{}\shell\open\command", PROG_ID_KEY),
+        "/ve",
+        "/d",
+        &command,
+        "/f",
+    ])?;
+    reg(&["add", EXTENSION_KEY, "/ve", "/d", PROG_ID, "/f"])
+}
+
+#[cfg(target_os = "windows")]
+pub fn unregister() -> Result<(), String> {
+    reg(&["delete", PROG_ID_KEY, "/f"])?;
+    // Only the default value is ours, other programs may have registered under the key too.
+    reg(&["delete", EXTENSION_KEY, "/ve", "/f"])
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn is_registered() -> bool {
+    false
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn register() -> Result<(), String> {
+    Err(String::from("not supported on this platform"))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+pub fn unregister() -> Result<(), String> {
+    Err(String::from("not supported on this platform"))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn desktop_entry_runs_the_executable() {
+        let entry = desktop_entry(Path::new("/opt/ablavema dir/ablavema"));
+        assert!(entry.starts_with("[Desktop Entry]\n"));
+        assert!(entry.ends_with('\n'));
+        assert!(entry.contains("\nExec=\"/opt/ablavema dir/ablavema\" %f\n"));
+        assert!(!entry.contains("Exec=ablavema"));
+        assert!(entry.contains("\nMimeType=application/x-blender;\n"));
+    }
+
+    #[test]
+    fn without_desktop_file_keeps_other_associations() {
+        let mimeapps = "\
+[Default Applications]
+application/x-blender=blender.desktop;ablavema.desktop;
+text/plain=gedit.desktop;
+";
+        assert_eq!(
+            without_desktop_file(mimeapps),
+            "\
+[Default Applications]
+application/x-blender=blender.desktop;
+text/plain=gedit.desktop;
+"
+        );
+    }
+
+    #[test]
+    fn without_desktop_file_removes_lone_association() {
+        let mimeapps = "\
+[Default Applications]
+application/x-blender=ablavema.desktop;
+text/plain=gedit.desktop;
+
+[Added Associations]
+application/x-blender=ablavema.desktop
+";
+        assert_eq!(
+            without_desktop_file(mimeapps),
+            "\
+[Default Applications]
+text/plain=gedit.desktop;
+
+[Added Associations]
+"
+        );
+    }
+
+    #[test]
+    fn without_desktop_file_leaves_similar_names() {
+        let mimeapps = "\
+[Default Applications]
+application/x-blender=ablavema.desktop.old;
+application/x-blender-text=ablavema.desktop;
+";
+        assert_eq!(without_desktop_file(mimeapps), mimeapps);
+    }
+}
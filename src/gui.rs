@@ -1,46 +1,105 @@
+//! The launcher's window. Only the settings it stores and the installing are built without the
+//! `gui` feature, since the command line needs them too.
+#[cfg(feature = "gui")]
+mod changelog;
+#[cfg(feature = "gui")]
+pub mod compact;
+#[cfg(feature = "gui")]
+mod compare;
+#[cfg(feature = "gui")]
 mod controls;
+#[cfg(feature = "gui")]
 pub mod extra;
 pub mod filters;
-mod install;
+pub mod install;
+#[cfg(feature = "gui")]
 mod message;
+#[cfg(feature = "gui")]
+mod modal;
+#[cfg(feature = "gui")]
+pub mod monitor;
+#[cfg(feature = "gui")]
 mod package;
+#[cfg(feature = "gui")]
+mod quick_switch;
+#[cfg(feature = "gui")]
+mod scroll;
 pub mod sort_by;
 pub mod style;
 pub mod tabs;
+#[cfg(feature = "gui")]
+mod wizard;
+#[cfg(feature = "gui")]
 use self::{
     controls::Controls,
-    extra::{DiskSpace, GlobalTokio, GuiFlags, GuiState},
-    install::Install,
+    extra::{DiskSpace, GlobalTokio, GuiState, Notification},
+    install::{DownloadSlots, Install},
     message::GuiMessage,
     tabs::recent_files::RecentFile,
+    wizard::WizardState,
 };
+#[cfg(all(target_os = "linux", feature = "dbus"))]
+use crate::helpers::ipc;
+#[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+use crate::helpers::tray::{self, SystemTray};
+#[cfg(feature = "gui")]
 use crate::{
-    gui::tabs::Tab,
-    helpers::check_connection,
-    package::Package,
+    cli::GuiFlags,
+    gui::{filters::Filters, tabs::Tab},
+    helpers::{check_connection, http_client},
+    package::{BuildHealth, IntegrityStatus, Package, PackageState, PackageStatus},
     releases::{
-        daily_archive::DailyArchive, daily_latest::DailyLatest,
+        buildbot::fetch_build_health, daily_archive::DailyArchive, daily_latest::DailyLatest,
         experimental_archive::ExperimentalArchive, experimental_latest::ExperimentalLatest,
-        lts::Lts, patch_archive::PatchArchive, patch_latest::PatchLatest,
+        launcher::LauncherUpdate, lts::Lts, patch_archive::PatchArchive, patch_latest::PatchLatest,
         stable_archive::StableArchive, stable_latest::StableLatest, ReleaseType, Releases,
     },
     self_updater::SelfUpdater,
-    settings::{get_setting, save_settings, set_setting, CAN_CONNECT},
+    settings::{
+        get_setting, KeyCombo, SettingsService, CAN_CONNECT, INSTALLING, LOADING_STAGE, PORTABLE,
+        RATE_LIMITED_SECS,
+    },
 };
+#[cfg(feature = "gui")]
 use fs2::available_space;
+#[cfg(feature = "gui")]
 use fs_extra::dir;
+#[cfg(feature = "gui")]
 use iced::{
     alignment::Horizontal,
     pure::{
         widget::{Button, Column, Container, Row, Text},
         Application, Element,
     },
-    Command, Length, Space, Subscription,
+    time, Alignment, Command, Length, Space, Subscription,
+};
+#[cfg(feature = "gui")]
+use iced_native::{
+    event::Status,
+    keyboard::{self, KeyCode},
+    subscription::events_with,
+    window, Event,
 };
+#[cfg(feature = "gui")]
 use self_update::update::Release;
-use std::sync::atomic::Ordering;
-use tokio::task::spawn_blocking;
+#[cfg(feature = "gui")]
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{remove_dir_all, rename},
+    path::PathBuf,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+#[cfg(feature = "gui")]
+use tokio::{sync::watch, task::spawn_blocking, time::sleep};
+#[cfg(feature = "gui")]
+use tokio_util::sync::CancellationToken;
+
+/// How long the shown packages have to stay the same before asking for their build health.
+#[cfg(feature = "gui")]
+const BUILD_HEALTH_DELAY: Duration = Duration::from_millis(750);
 
+#[cfg(feature = "gui")]
 macro_rules! build_fetching {
     ($name:ident, $release:ident) => {
         async fn $name(packages: $release) -> (bool, $release) {
@@ -55,22 +114,243 @@ macro_rules! build_fetching {
     };
 }
 
+#[cfg(feature = "gui")]
 #[derive(Debug)]
 pub struct Gui {
     releases: Releases,
     packages: Vec<Package>,
-    installing: Vec<Package>,
-    file_path: Option<String>,
+    installing: Vec<(Package, CancellationToken)>,
+    download_slots: DownloadSlots,
+    file_paths: Vec<String>,
     recent_files: Vec<RecentFile>,
     state: GuiState,
     controls: Controls,
     self_releases: Option<Vec<Release>>,
+    notifications: Vec<Notification>,
+    /// Names of the packages shown regardless of the filters, until the launcher is closed.
+    pinned: HashSet<String>,
+    /// The step of the first run setup being shown, covering everything else.
+    wizard: Option<WizardState>,
+    /// Shows the quick switch over everything but the setup, until a package is picked or it's
+    /// dismissed.
+    quick_switch_mode: bool,
+    /// Shows only the installed packages, for the small window opened with `--compact`, until
+    /// it's left for the whole launcher.
+    compact_mode: bool,
+    /// Set while the first fetch of a launcher with nothing fetched yet is running.
+    is_loading: bool,
+    spinner_frame: usize,
+    /// Last known state of the connection, kept up to date by polling.
+    connection_ok: bool,
+    /// Whether the downloads in progress are held, sent to them through `pause_downloads`.
+    downloads_paused: bool,
+    pause_downloads: watch::Sender<bool>,
+    /// Position in the shown packages of the one picked with the arrow keys.
+    focused_package_index: Option<usize>,
+    /// Names of the packages ticked for the batch actions while in multi-select mode.
+    selected_packages: HashSet<String>,
+    /// When fetching is allowed again after the server asked to slow down.
+    retry_after: Option<Instant>,
+    /// Packages asked to be installed through the D-Bus service.
+    #[cfg(all(target_os = "linux", feature = "dbus"))]
+    install_requests: ipc::InstallRequests,
+    /// The packages as of the last sync, shared with the D-Bus service.
+    #[cfg(all(target_os = "linux", feature = "dbus"))]
+    synced_packages: ipc::SyncedPackages,
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    tray: SystemTray,
+    /// Whether the window was closed to the tray.
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    hidden_to_tray: bool,
+    /// Set when quitting from the tray, since closing the window only hides it.
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    should_exit: bool,
 }
 
+#[cfg(feature = "gui")]
 impl Gui {
     pub fn sync(&mut self) {
         self.releases.sync();
+        self.state.external_scan_wanted = true;
+        self.refresh_packages();
+    }
+
+    /// Lists the packages again from the releases, keeping what was found about them this
+    /// session.
+    fn refresh_packages(&mut self) {
         self.packages = self.releases.build_vec();
+        for package in &mut self.packages {
+            package.integrity = self.state.integrity.get(&package.name).cloned();
+            package.test_launch = self.state.test_launches.get(&package.name).copied();
+            package.build_health = self.state.build_health.get(&package.name).copied();
+            package.is_new_since_last_session =
+                self.state.new_since_last_session.contains(&package.name);
+        }
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        ipc::publish(&self.synced_packages, &self.packages);
+        let installed = &self.releases.installed;
+        self.state
+            .package_sizes
+            .retain(|name, _| installed.iter().any(|package| package.name == *name));
+
+        #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+        self.tray
+            .set_update_badge(self.releases.count_updates().all.is_some());
+
+        let retry_after_secs = RATE_LIMITED_SECS.swap(0, Ordering::Relaxed);
+        if retry_after_secs > 0 {
+            self.retry_after = Some(Instant::now() + Duration::from_secs(retry_after_secs));
+        }
+    }
+
+    /// Syncs after fetching, marking the packages that weren't listed before as new since the
+    /// last session. Nothing is marked when nothing was listed, like on the first launch.
+    pub fn sync_fetched(&mut self) {
+        let known: HashSet<String> = self
+            .packages
+            .iter()
+            .map(|package| package.name.clone())
+            .collect();
+        self.sync();
+        if known.is_empty() {
+            return;
+        }
+        for package in &mut self.packages {
+            if !known.contains(&package.name)
+                && package.local_path.is_none()
+                && package.status != PackageStatus::External
+            {
+                package.is_new_since_last_session = true;
+                self.state
+                    .new_since_last_session
+                    .insert(package.name.clone());
+            }
+        }
+    }
+
+    /// Looks for packages installed by hand off the GUI thread, since it runs their Blender, once
+    /// a sync asked for it and the last look is done.
+    fn scan_external(&mut self) -> Command<GuiMessage> {
+        if !self.state.external_scan_wanted
+            || self.state.scanning_external
+            || INSTALLING.load(Ordering::Relaxed)
+        {
+            return Command::none();
+        }
+        self.state.external_scan_wanted = false;
+        self.state.scanning_external = true;
+
+        let installed = self.releases.installed.clone();
+        let known = self.releases.external.clone();
+        Command::perform(
+            async move {
+                spawn_blocking(move || installed.scan_unknown(&known))
+                    .await
+                    .unwrap()
+            },
+            GuiMessage::ExternalScanned,
+        )
+    }
+
+    /// Measures the installed packages missing from the size cache, but only when sorting by
+    /// size since walking every package directory can take a while.
+    fn measure_missing_sizes(
+        packages: &[Package],
+        package_sizes: &HashMap<String, u64>,
+    ) -> Command<GuiMessage> {
+        if !get_setting().sort_by.is_by_size() {
+            return Command::none();
+        }
+
+        let missing: Vec<Package> = packages
+            .iter()
+            .filter(|package| {
+                matches!(package.state, PackageState::Installed)
+                    && !package_sizes.contains_key(&package.name)
+            })
+            .cloned()
+            .collect();
+
+        if missing.is_empty() {
+            Command::none()
+        } else {
+            Command::perform(Gui::measure_sizes(missing), GuiMessage::SizesMeasured)
+        }
+    }
+
+    async fn measure_sizes(packages: Vec<Package>) -> HashMap<String, u64> {
+        spawn_blocking(move || {
+            packages
+                .into_iter()
+                .filter_map(|package| Some((package.name.clone(), package.disk_usage()?)))
+                .collect()
+        })
+        .await
+        .unwrap()
+    }
+
+    /// What decides which packages get their build health asked for: the search, the filters,
+    /// the tab and the buildbot's URL.
+    fn build_health_inputs(&self) -> (String, Filters, Tab, String) {
+        let settings = get_setting();
+        (
+            self.controls.search.clone(),
+            settings.filters.clone(),
+            settings.tab,
+            settings.buildbot_api_url.clone(),
+        )
+    }
+
+    /// Waits for the shown packages to settle before asking the buildbot about the ones missing
+    /// their build health, so typing a search or going through the filters doesn't send a
+    /// request for every step along the way.
+    fn debounce_build_health(&mut self) -> Command<GuiMessage> {
+        let wanted: Vec<String> = if get_setting().buildbot_api_url.trim().is_empty()
+            || get_setting().tab != Tab::Packages
+        {
+            Vec::new()
+        } else {
+            let filters = &get_setting().filters;
+            self.packages
+                .iter()
+                .filter(|package| {
+                    !package.commit.is_empty()
+                        && !self.state.build_health.contains_key(&package.name)
+                        && !self.state.build_health_pending.contains(&package.name)
+                        && filters.matches(package, &self.controls.search, &self.pinned)
+                })
+                .map(|package| package.name.clone())
+                .collect()
+        };
+
+        if wanted == self.state.build_health_wanted {
+            return Command::none();
+        }
+        self.state.build_health_wanted = wanted;
+        self.state.build_health_generation += 1;
+
+        if self.state.build_health_wanted.is_empty() {
+            Command::none()
+        } else {
+            let generation = self.state.build_health_generation;
+            Command::perform(
+                async move {
+                    sleep(BUILD_HEALTH_DELAY).await;
+                    generation
+                },
+                GuiMessage::CheckBuildHealth,
+            )
+        }
+    }
+
+    async fn fetch_build_health(packages: Vec<Package>) -> HashMap<String, BuildHealth> {
+        let api_url = get_setting().buildbot_api_url.trim().to_string();
+        let mut health = HashMap::new();
+        for package in packages {
+            let package_health = fetch_build_health(&api_url, &package.commit).await;
+            health.insert(package.name, package_health);
+        }
+        health
     }
 
     /// A tuple is returned where:
@@ -78,18 +358,21 @@ impl Gui {
     async fn check_availability(
         for_install: bool,
         package: Package,
-    ) -> Option<(bool, bool, Package)> {
-        match reqwest::get(&package.url).await {
+    ) -> Result<(bool, bool, Package), String> {
+        match http_client().get(&package.url).send().await {
             Ok(response) => {
                 if response.status().is_client_error() {
-                    Some((false, for_install, package))
+                    Ok((false, for_install, package))
                 } else {
-                    Some((true, for_install, package))
+                    Ok((true, for_install, package))
                 }
             }
-            Err(_) => {
+            Err(e) => {
                 CAN_CONNECT.store(false, Ordering::Relaxed);
-                None
+                Err(format!(
+                    "Couldn't check if '{}' is available: {}",
+                    package.name, e
+                ))
             }
         }
     }
@@ -102,6 +385,11 @@ impl Gui {
         string
     }
 
+    async fn debounce(instant: Instant) -> Instant {
+        sleep(Duration::from_millis(500)).await;
+        instant
+    }
+
     async fn check_for_updates(
         packages: (
             DailyLatest,
@@ -155,18 +443,28 @@ impl Gui {
         check_connection().await;
 
         if CAN_CONNECT.load(Ordering::Relaxed) {
+            let stage = |stage| LOADING_STAGE.store(stage, Ordering::Relaxed);
+            stage(0);
             let daily_latest = DailyLatest::check_updates(daily_latest).await.1;
+            stage(1);
             let daily_archive = DailyArchive::check_updates(daily_archive).await.1;
+            stage(2);
             let experimental_latest = ExperimentalLatest::check_updates(experimental_latest)
                 .await
                 .1;
+            stage(3);
             let experimental_archive = ExperimentalArchive::check_updates(experimental_archive)
                 .await
                 .1;
+            stage(4);
             let patch_latest = PatchLatest::check_updates(patch_latest).await.1;
+            stage(5);
             let patch_archive = PatchArchive::check_updates(patch_archive).await.1;
+            stage(6);
             let stable_latest = StableLatest::check_updates(stable_latest).await.1;
+            stage(7);
             let stable_archive = StableArchive::check_updates(stable_archive).await.1;
+            stage(8);
             let lts = Lts::check_updates(lts).await.1;
 
             (
@@ -219,14 +517,113 @@ impl Gui {
         check_connection().await;
     }
 
-    async fn fetch_self_releases() -> Option<Vec<Release>> {
-        spawn_blocking(SelfUpdater::fetch).await.unwrap()
+    async fn poll_connection() -> bool {
+        check_connection().await;
+        CAN_CONNECT.load(Ordering::Relaxed)
+    }
+
+    async fn fetch_self_releases() -> Result<Option<Vec<Release>>, String> {
+        spawn_blocking(SelfUpdater::fetch)
+            .await
+            .map_err(|e| e.to_string())?
     }
 
-    async fn change_self_version(releases: Vec<Release>, version: String) {
+    async fn change_self_version(releases: Vec<Release>, version: String) -> Result<(), String> {
         spawn_blocking(|| SelfUpdater::change(releases, version))
             .await
-            .unwrap();
+            .map_err(|e| e.to_string())?
+    }
+
+    /// Moves an installed package into another packages location. Renaming is tried first,
+    /// falling back to copying it over for moves across devices, in which case a failure
+    /// leaves the package untouched in its old location.
+    async fn move_package(package_dir: PathBuf, packages_dir: PathBuf) -> Result<(), String> {
+        spawn_blocking(move || {
+            let destination = packages_dir.join(package_dir.file_name().unwrap());
+
+            // Never merge into or clean up a package that's already there.
+            if destination.exists() {
+                return Err(format!("{} already exists", destination.display()));
+            }
+
+            if rename(&package_dir, &destination).is_ok() {
+                return Ok(());
+            }
+
+            if let Err(e) = dir::copy(&package_dir, &packages_dir, &dir::CopyOptions::new()) {
+                // Only what this copy created is removed, since the destination didn't exist.
+                let _ = remove_dir_all(&destination);
+                return Err(format!("{}: {}", package_dir.display(), e));
+            }
+            remove_dir_all(&package_dir).map_err(|e| format!("{}: {}", package_dir.display(), e))
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Makes sure the URL of a custom package can be downloaded and isn't a web page, reading
+    /// only the headers of the response.
+    async fn check_custom_package(package: Package) -> Result<Package, String> {
+        let response = http_client()
+            .get(&package.url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("couldn't reach {}: {}", package.url, e))?;
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .unwrap_or_default();
+        if content_type.starts_with("text/") {
+            return Err(format!(
+                "{} is a {} page rather than an archive",
+                package.url, content_type
+            ));
+        }
+        Ok(package)
+    }
+
+    async fn local_build(dir: PathBuf) -> Result<Package, String> {
+        spawn_blocking(move || Package::from_local_build(dir))
+            .await
+            .map_err(|e| e.to_string())?
+    }
+
+    async fn test_launch(package: Package) -> (Package, bool, String) {
+        spawn_blocking(move || match package.test_launch() {
+            Ok(output) => (package, true, output),
+            Err(e) => (package, false, e),
+        })
+        .await
+        .unwrap()
+    }
+
+    async fn verify_integrity(package: Package) -> (Package, IntegrityStatus) {
+        spawn_blocking(move || {
+            let status = package.check_integrity();
+            (package, status)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Starts moving the next package waiting to be migrated, or wraps up if there are none.
+    fn migrate_next_package(&mut self) -> Command<GuiMessage> {
+        match self.state.migrating_packages.pop() {
+            Some(package_dir) => Command::perform(
+                Gui::move_package(package_dir, get_setting().packages_dir.clone()),
+                GuiMessage::PackageMigrated,
+            ),
+            None => {
+                if self.state.move_packages_error.is_none() {
+                    self.state.previous_packages_dir = None;
+                }
+                self.state.migration_total = 0;
+                self.sync();
+                Command::perform(Gui::calculate_disk_space(), GuiMessage::CalculateDiskSpace)
+            }
+        }
     }
 
     async fn calculate_disk_space() -> DiskSpace {
@@ -251,6 +648,7 @@ impl Gui {
     }
 }
 
+#[cfg(feature = "gui")]
 impl Application for Gui {
     type Executor = GlobalTokio;
     type Message = GuiMessage;
@@ -261,15 +659,32 @@ impl Application for Gui {
 
         let packages = releases.build_vec();
 
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        let (install_requests, synced_packages) = ipc::start();
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        ipc::publish(&synced_packages, &packages);
+
         let default_package = get_setting().default_package.clone();
         if let Some(package) = default_package {
             if !releases.installed.contains(&package) {
-                set_setting().default_package = None;
-                save_settings();
+                SettingsService::update(|settings| settings.default_package = None);
             }
         }
 
+        // With nothing installed there's nothing to switch to.
+        let quick_switch_mode = flags.quick_switch && !releases.installed.is_empty();
+
+        // Without any packages there's nothing to show until everything is fetched once.
+        let is_loading = packages.is_empty() && CAN_CONNECT.load(Ordering::Relaxed);
+
         let mut state = GuiState::new();
+        state.monitors = flags.monitors;
+        let measure_sizes = Gui::measure_missing_sizes(&packages, &state.package_sizes);
+        state.background_installs = flags
+            .auto_install
+            .iter()
+            .map(|package| package.name.clone())
+            .collect();
 
         let self_releases = flags.self_releases;
 
@@ -284,42 +699,225 @@ impl Application for Gui {
             Gui {
                 releases,
                 packages,
-                file_path: flags.file_path,
+                file_paths: flags.file_paths,
                 recent_files: get_setting().recent_files.to_vec(),
                 installing: Vec::default(),
+                download_slots: DownloadSlots::new(get_setting().max_concurrent_downloads),
                 state,
-                controls: Controls::default(),
+                controls: Controls::new(),
                 self_releases,
+                notifications: Vec::new(),
+                pinned: HashSet::new(),
+                wizard: get_setting().first_run.then_some(WizardState::Welcome),
+                quick_switch_mode,
+                compact_mode: flags.compact,
+                is_loading,
+                spinner_frame: 0,
+                connection_ok: CAN_CONNECT.load(Ordering::Relaxed),
+                downloads_paused: false,
+                pause_downloads: watch::channel(false).0,
+                focused_package_index: None,
+                selected_packages: HashSet::new(),
+                retry_after: None,
+                #[cfg(all(target_os = "linux", feature = "dbus"))]
+                install_requests,
+                #[cfg(all(target_os = "linux", feature = "dbus"))]
+                synced_packages,
+                #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+                tray: SystemTray::start(),
+                #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+                hidden_to_tray: false,
+                #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+                should_exit: false,
             },
-            Command::perform(Gui::calculate_disk_space(), GuiMessage::CalculateDiskSpace),
+            Command::batch(
+                [
+                    if is_loading {
+                        Command::perform(async {}, |_| GuiMessage::FetchAll)
+                    } else {
+                        Command::none()
+                    },
+                    Command::perform(Gui::calculate_disk_space(), GuiMessage::CalculateDiskSpace),
+                    measure_sizes,
+                    if CAN_CONNECT.load(Ordering::Relaxed) {
+                        Command::perform(
+                            LauncherUpdate::check(),
+                            GuiMessage::LauncherUpdateAvailable,
+                        )
+                    } else {
+                        Command::none()
+                    },
+                ]
+                .into_iter()
+                .chain(flags.auto_install.into_iter().map(|package| {
+                    Command::perform(
+                        Gui::check_availability(true, package),
+                        GuiMessage::CheckAvailability,
+                    )
+                })),
+            ),
         )
     }
 
     fn title(&self) -> String {
+        let name = if PORTABLE.load(Ordering::Relaxed) {
+            "Ablavema (Portable)"
+        } else {
+            "Ablavema"
+        };
         match self.releases.count_updates().all {
             Some(count) => format!(
-                "Ablavema - {} update{} available!",
+                "{} - {} update{} available!",
+                name,
                 count,
                 if count > 1 { "s" } else { "" }
             ),
-            None => String::from("Ablavema"),
+            None => String::from(name),
         }
     }
 
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    fn mode(&self) -> iced::window::Mode {
+        if self.hidden_to_tray {
+            iced::window::Mode::Hidden
+        } else {
+            iced::window::Mode::Windowed
+        }
+    }
+
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    fn should_exit(&self) -> bool {
+        self.should_exit
+    }
+
     fn update(&mut self, message: GuiMessage) -> Command<GuiMessage> {
-        self.update_message(message)
+        let shown_by = self.build_health_inputs();
+        let command = self.update_message(message);
+        let command = Command::batch([command, self.scan_external()]);
+        if self.build_health_inputs() == shown_by {
+            command
+        } else {
+            Command::batch([command, self.debounce_build_health()])
+        }
     }
 
     fn subscription(&self) -> Subscription<GuiMessage> {
-        Subscription::batch(
+        let subscription = Subscription::batch(
             self.installing
                 .iter()
-                .map(|package| Install::package(package.to_owned())),
-        )
+                .map(|(package, cancellation_token)| {
+                    Install::package(
+                        package.to_owned(),
+                        cancellation_token.to_owned(),
+                        self.download_slots.clone(),
+                        self.pause_downloads.subscribe(),
+                    )
+                })
+                .chain(std::iter::once(events_with(|event, status| match event {
+                    Event::Window(window::Event::Resized { width, height }) => {
+                        Some(GuiMessage::WindowResized(width, height))
+                    }
+                    Event::Window(window::Event::Moved { x, y }) => {
+                        Some(GuiMessage::WindowMoved(x, y))
+                    }
+                    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+                    Event::Window(window::Event::CloseRequested) => {
+                        Some(GuiMessage::CloseRequested)
+                    }
+                    Event::Window(window::Event::FileHovered(_)) => Some(GuiMessage::FileHovered),
+                    Event::Window(window::Event::FileDropped(path)) => {
+                        Some(GuiMessage::FileDropped(path))
+                    }
+                    Event::Window(window::Event::FilesHoveredLeft) => {
+                        Some(GuiMessage::FilesHoveredLeft)
+                    }
+                    Event::Keyboard(keyboard::Event::KeyPressed {
+                        key_code,
+                        modifiers,
+                    }) if !matches!(
+                        key_code,
+                        KeyCode::LControl
+                            | KeyCode::RControl
+                            | KeyCode::LAlt
+                            | KeyCode::RAlt
+                            | KeyCode::LShift
+                            | KeyCode::RShift
+                            | KeyCode::LWin
+                            | KeyCode::RWin
+                    ) =>
+                    {
+                        Some(GuiMessage::KeyPressed(
+                            KeyCombo {
+                                ctrl: modifiers.control(),
+                                alt: modifiers.alt(),
+                                shift: modifiers.shift(),
+                                key: format!("{:?}", key_code),
+                            },
+                            status == Status::Captured,
+                        ))
+                    }
+                    _ => None,
+                })))
+                .chain(std::iter::once(
+                    time::every(Duration::from_secs(60)).map(|_| GuiMessage::ScheduledCheckTick),
+                ))
+                .chain(std::iter::once(
+                    time::every(Duration::from_secs(30)).map(|_| GuiMessage::PollConnection),
+                ))
+                .chain(self.retry_after.is_some().then(|| {
+                    time::every(Duration::from_secs(1)).map(|_| GuiMessage::RateLimitTick)
+                }))
+                // Also keeps the progress of fetching everything up to date.
+                .chain(
+                    (self.is_loading || self.controls.fetch_progress.is_some()).then(|| {
+                        time::every(Duration::from_millis(250)).map(|_| GuiMessage::SpinnerTick)
+                    }),
+                )
+                .chain(if self.notifications.is_empty() {
+                    None
+                } else {
+                    Some(
+                        time::every(Duration::from_secs(1)).map(|_| GuiMessage::PruneNotifications),
+                    )
+                }),
+        );
+
+        #[cfg(all(target_os = "linux", feature = "dbus"))]
+        let subscription = Subscription::batch([
+            subscription,
+            ipc::subscription(&self.install_requests).map(GuiMessage::InstallRequested),
+        ]);
+
+        #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+        let subscription = Subscription::batch([
+            subscription,
+            tray::subscription(&self.tray).map(GuiMessage::TrayEvent),
+        ]);
+
+        subscription
     }
 
     fn view(&self) -> Element<'_, GuiMessage> {
-        let file_exists = self.file_path.is_some();
+        if let Some(wizard) = self.wizard {
+            return wizard::wizard_view(wizard);
+        }
+
+        if self.quick_switch_mode {
+            return quick_switch::quick_switch_view(&quick_switch::quick_switch_packages(
+                &self.releases.installed,
+            ));
+        }
+
+        if self.compact_mode {
+            return match &self.state.confirm_modal {
+                Some(modal) => modal::confirm_modal_view(modal),
+                None => compact::compact_view(
+                    &compact::compact_packages(&self.packages, &self.state.package_sizes),
+                    self.focused_package_index,
+                ),
+            };
+        }
+
         let current_tab = get_setting().tab;
         let update_count = self.releases.count_updates();
 
@@ -357,26 +955,129 @@ impl Application for Gui {
                 } else {
                     Container::new(Space::with_width(Length::Units(0)))
                 })
+                .push(tab_button("Logs", Tab::Logs))
                 .push(tab_button("About", Tab::About)),
         )
         .width(Length::Fill)
         .center_x()
         .style(get_setting().theme.tab_container());
 
-        let body = match current_tab {
-            Tab::RecentFiles => Tab::recent_files_body(self.file_path.clone(), &self.recent_files),
-            Tab::Packages => Tab::packages_body(
-                &self.packages,
-                self.file_path.clone(),
-                update_count,
-                file_exists,
-                &self.controls,
-            ),
-            Tab::Settings => Tab::settings_body(&self.releases, self.state.disk_space),
-            Tab::SelfUpdater => Tab::self_updater_body(&self.state, &self.self_releases),
-            Tab::About => Tab::about_body(),
+        let body = match (
+            &self.state.confirm_modal,
+            &self.state.comparing,
+            &self.state.changelog,
+        ) {
+            (Some(modal), _, _) => modal::confirm_modal_view(modal),
+            (None, Some((first, second)), _) => compare::comparison_view(first, second),
+            (None, None, Some((old, new))) => {
+                changelog::changelog_view(old, new, &self.state.changelog_commits)
+            }
+            (None, None, None) => match current_tab {
+                Tab::RecentFiles => Tab::recent_files_body(&self.file_paths, &self.recent_files),
+                Tab::Packages if self.is_loading => Tab::loading_body(self.spinner_frame),
+                Tab::Packages => Tab::packages_body(
+                    &self.packages,
+                    &self.file_paths,
+                    &self.recent_files,
+                    update_count,
+                    &self.controls,
+                    &self.pinned,
+                    &self.state,
+                    self.downloads_paused,
+                    self.focused_package_index,
+                    &self.selected_packages,
+                    self.retry_after.is_some(),
+                    self.installing
+                        .iter()
+                        .filter_map(|(installing, _)| {
+                            self.packages.iter().find(|package| *package == installing)
+                        })
+                        .collect(),
+                ),
+                Tab::Settings => Tab::settings_body(&self.releases, &self.state),
+                Tab::SelfUpdater => Tab::self_updater_body(&self.state, &self.self_releases),
+                Tab::Logs => Tab::logs_body(),
+                Tab::About => Tab::about_body(&self.state),
+            },
+        };
+
+        let error_banner = match &self.state.error {
+            Some(error) => Container::new(
+                Row::new()
+                    .padding(1)
+                    .align_items(Alignment::Center)
+                    .push(Space::with_width(Length::Units(9)))
+                    .push(Text::new(error).width(Length::Fill))
+                    .push(
+                        Button::new(Text::new("[X]"))
+                            .on_press(GuiMessage::DismissError)
+                            .style(get_setting().theme.tab_button()),
+                    )
+                    .push(Space::with_width(Length::Units(9))),
+            )
+            .width(Length::Fill)
+            .style(get_setting().theme.status_container()),
+            None => Container::new(Space::with_height(Length::Units(0))),
+        };
+
+        let connection_banner = if self.connection_ok {
+            Container::new(Space::with_height(Length::Units(0)))
+        } else {
+            Container::new(
+                Text::new("No internet connection — updates unavailable")
+                    .width(Length::Fill)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .padding(3)
+            .width(Length::Fill)
+            .style(get_setting().theme.status_container())
         };
 
-        Column::new().push(tabs).push(body).into()
+        let rate_limit_banner = match self.retry_after {
+            Some(retry_after) => Container::new(
+                Text::new(format!(
+                    "Rate limited — retry in {} s",
+                    // Rounded up, so it doesn't read 0 s while still waiting.
+                    retry_after
+                        .saturating_duration_since(Instant::now())
+                        .as_millis()
+                        .div_ceil(1000)
+                ))
+                .width(Length::Fill)
+                .horizontal_alignment(Horizontal::Center),
+            )
+            .padding(3)
+            .width(Length::Fill)
+            .style(get_setting().theme.status_container()),
+            None => Container::new(Space::with_height(Length::Units(0))),
+        };
+
+        let notifications = self.notifications.iter().fold(
+            Column::new()
+                .spacing(5)
+                .padding(10)
+                .align_items(Alignment::End),
+            |column, notification| {
+                column.push(
+                    Container::new(Text::new(&notification.message))
+                        .padding(10)
+                        .max_width(400)
+                        .style(get_setting().theme.info_container()),
+                )
+            },
+        );
+
+        Column::new()
+            .push(tabs)
+            .push(connection_banner)
+            .push(rate_limit_banner)
+            .push(error_banner)
+            .push(body)
+            .push(
+                Container::new(notifications)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Right),
+            )
+            .into()
     }
 }
@@ -0,0 +1,87 @@
+use super::message::GuiMessage;
+use crate::{
+    package::{Change, Package},
+    settings::{get_setting, TEXT_SIZE},
+};
+use iced::{
+    alignment::Horizontal,
+    pure::{
+        widget::{Button, Column, Container, Row, Scrollable, Text},
+        Element,
+    },
+    Alignment, Length,
+};
+
+/// The commits that went in between an installed package and its update.
+pub fn changelog_view<'a>(
+    old: &'a Package,
+    new: &'a Package,
+    commits: &'a Option<Result<Vec<Change>, String>>,
+) -> Element<'a, GuiMessage> {
+    let message = |text: String| -> Element<'_, GuiMessage> {
+        Container::new(Text::new(text).size(TEXT_SIZE * 2))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    };
+
+    let body = match commits {
+        None => message(String::from("Fetching commits...")),
+        Some(Err(e)) => message(format!("Couldn't fetch the commits: {}.", e)),
+        Some(Ok(commits)) if commits.is_empty() => message(String::from("No commits in between")),
+        Some(Ok(commits)) => Scrollable::new(
+            commits
+                .iter()
+                .fold(Column::new().spacing(5), |column, commit| {
+                    column.push(
+                        Row::new()
+                            .spacing(10)
+                            .align_items(Alignment::Center)
+                            .push(Text::new(&commit.text).width(Length::Fill))
+                            .push(
+                                Button::new(Text::new("[>]"))
+                                    .on_press(GuiMessage::OpenBrowser(commit.url.clone()))
+                                    .style(get_setting().theme),
+                            ),
+                    )
+                })
+                .padding(10),
+        )
+        .height(Length::Fill)
+        .into(),
+    };
+
+    Container::new(
+        Column::new()
+            .padding(20)
+            .spacing(20)
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .push(
+                        Column::new()
+                            .width(Length::Fill)
+                            .push(
+                                Text::new("Changelog")
+                                    .size(TEXT_SIZE * 2)
+                                    .color(get_setting().theme.highlight_text()),
+                            )
+                            .push(Text::new(format!("{} -> {}", old.name, new.name))),
+                    )
+                    .push(
+                        Button::new(
+                            Text::new("[X] Close").horizontal_alignment(Horizontal::Center),
+                        )
+                        .on_press(GuiMessage::CloseChangelog)
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(body),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(get_setting().theme.normal_container())
+    .into()
+}
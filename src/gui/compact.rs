@@ -0,0 +1,96 @@
+use super::{message::GuiMessage, package::PackageMessage};
+use crate::{
+    package::{Package, PackageState},
+    settings::{get_setting, TEXT_SIZE},
+};
+use iced::{
+    pure::{
+        widget::{Button, Column, Container, Row, Scrollable, Text},
+        Element,
+    },
+    Alignment, Length,
+};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+/// The size of the window when starting in compact mode.
+pub const COMPACT_WINDOW_SIZE: (u32, u32) = (400, 300);
+
+/// The installed packages, in the order they're listed in the packages tab.
+pub fn compact_packages<'a>(
+    packages: &'a [Package],
+    package_sizes: &HashMap<String, u64>,
+) -> Vec<&'a Package> {
+    packages
+        .iter()
+        .filter(|package| matches!(package.state, PackageState::Installed))
+        .sorted_by(|a, b| get_setting().sort_by.get_ordering(a, b, package_sizes))
+        .collect()
+}
+
+/// A plain list of the installed packages and nothing else, meant to be used with the keyboard.
+pub fn compact_view<'a>(
+    packages: &[&'a Package],
+    focused_package_index: Option<usize>,
+) -> Element<'a, GuiMessage> {
+    let default_package = match get_setting().active_profile() {
+        Some(profile) => profile.default_package.clone(),
+        None => get_setting().default_package.clone(),
+    };
+
+    let list = packages.iter().enumerate().fold(
+        Column::new().spacing(2),
+        |column, (position, package)| {
+            let focused = focused_package_index == Some(position);
+            let mut row = Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(if focused { ">" } else { " " }))
+                .push(Text::new(&package.name).width(Length::Fill));
+            if default_package.as_ref() == Some(*package) {
+                row = row.push(
+                    Text::new("Default")
+                        .size(TEXT_SIZE - 4)
+                        .color(get_setting().theme.highlight_text()),
+                );
+            }
+
+            let button = Button::new(row)
+                .width(Length::Fill)
+                .on_press(GuiMessage::PackageMessage((
+                    package.index,
+                    PackageMessage::OpenBlender,
+                )))
+                .style(get_setting().theme);
+            column.push(if focused {
+                Container::new(button).style(get_setting().theme.focused_container(false))
+            } else {
+                Container::new(button)
+            })
+        },
+    );
+
+    let body: Element<'_, GuiMessage> = if packages.is_empty() {
+        Container::new(Text::new("No packages installed").size(TEXT_SIZE * 2))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .center_y()
+            .into()
+    } else {
+        Scrollable::new(list.padding(5)).height(Length::Fill).into()
+    };
+
+    Container::new(
+        Column::new().spacing(5).padding(5).push(body).push(
+            Text::new(
+                "[Up/Down] Move  [Enter] Open  [Space] Default  [Del] Remove  [Esc] Full view",
+            )
+            .size(TEXT_SIZE - 4),
+        ),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(get_setting().theme.normal_container())
+    .into()
+}
@@ -0,0 +1,109 @@
+use super::message::GuiMessage;
+use crate::{
+    helpers::format_size,
+    package::Package,
+    settings::{get_setting, TEXT_SIZE},
+};
+use iced::{
+    alignment::Horizontal,
+    pure::{
+        widget::{Button, Column, Container, Row, Text},
+        Element,
+    },
+    Alignment, Length, Space,
+};
+
+/// Side by side table of the build info of two packages, with the values that differ
+/// highlighted.
+pub fn comparison_view<'a>(first: &'a Package, second: &'a Package) -> Element<'a, GuiMessage> {
+    let size = |package: &Package| match package.size {
+        Some(size) => format_size(size),
+        None => String::from("Unknown"),
+    };
+
+    let rows = [
+        (
+            "Version",
+            first.version.to_string(),
+            second.version.to_string(),
+        ),
+        (
+            "Date",
+            first.get_formatted_date_time(),
+            second.get_formatted_date_time(),
+        ),
+        ("Build", first.build.to_string(), second.build.to_string()),
+        ("Commit", first.commit.clone(), second.commit.clone()),
+        ("Install size", size(first), size(second)),
+        (
+            "Times opened",
+            first.open_count.to_string(),
+            second.open_count.to_string(),
+        ),
+    ];
+
+    let cell = |value: String, differs: bool| {
+        let text = Text::new(value).width(Length::FillPortion(2));
+        if differs {
+            text.color(get_setting().theme.highlight_text())
+        } else {
+            text
+        }
+    };
+
+    let table = rows.into_iter().fold(
+        Column::new().spacing(10).push(
+            Row::new()
+                .spacing(10)
+                .push(Space::with_width(Length::FillPortion(1)))
+                .push(
+                    Text::new(&first.name)
+                        .size(TEXT_SIZE + 4)
+                        .width(Length::FillPortion(2)),
+                )
+                .push(
+                    Text::new(&second.name)
+                        .size(TEXT_SIZE + 4)
+                        .width(Length::FillPortion(2)),
+                ),
+        ),
+        |column, (label, first_value, second_value)| {
+            let differs = first_value != second_value;
+            column.push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new(label).width(Length::FillPortion(1)))
+                    .push(cell(first_value, differs))
+                    .push(cell(second_value, differs)),
+            )
+        },
+    );
+
+    Container::new(
+        Column::new()
+            .padding(20)
+            .spacing(20)
+            .push(
+                Row::new()
+                    .align_items(Alignment::Center)
+                    .push(
+                        Text::new("Compare packages")
+                            .size(TEXT_SIZE * 2)
+                            .color(get_setting().theme.highlight_text())
+                            .width(Length::Fill),
+                    )
+                    .push(
+                        Button::new(
+                            Text::new("[X] Close").horizontal_alignment(Horizontal::Center),
+                        )
+                        .on_press(GuiMessage::CloseComparison)
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(table),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .style(get_setting().theme.normal_container())
+    .into()
+}
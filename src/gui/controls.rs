@@ -1,25 +1,126 @@
-use super::{sort_by::SortBy, GuiMessage};
+use super::{
+    filters::{DateRangePreset, DATE_FORMAT},
+    sort_by::SortBy,
+    GuiMessage,
+};
 use crate::{
+    helpers::format_size,
     releases::UpdateCount,
-    settings::{get_setting, CAN_CONNECT, FETCHING, INSTALLING},
+    settings::{
+        get_setting, CAN_CONNECT, FETCHING, INSTALLING, LOADING_STAGE, LOADING_STAGES, TEXT_SIZE,
+    },
 };
 use iced::{
-    pure::widget::{Button, Checkbox, Column, Container, PickList, Row, Scrollable, Text},
+    pure::widget::{
+        Button, Checkbox, Column, Container, PickList, ProgressBar, Row, Scrollable, Text,
+        TextInput,
+    },
     Alignment, Length, Rule, Space,
 };
 use std::sync::atomic::Ordering;
 
+/// How far fetching everything has got, going by the release type being fetched.
+#[derive(Clone, Copy, Debug)]
+pub struct FetchProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub current_type: &'static str,
+}
+
+impl FetchProgress {
+    /// Reads the stage the fetch in progress is at.
+    pub fn current() -> Self {
+        let stage = LOADING_STAGE.load(Ordering::Relaxed);
+        Self {
+            completed: stage,
+            total: LOADING_STAGES.len(),
+            current_type: LOADING_STAGES[stage],
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 pub struct Controls {
     pub checking_connection: bool,
+    /// Whether a check for updates is on its way, so it isn't started twice.
+    pub fetching: bool,
+    /// Whether a build type's latest or archive packages are being fetched.
+    pub fetching_daily: bool,
+    pub fetching_experimental: bool,
+    pub fetching_patch: bool,
+    pub fetching_stable: bool,
+    pub fetching_lts: bool,
+    /// Set while everything is being fetched at once.
+    pub fetch_progress: Option<FetchProgress>,
+    pub search: String,
+    pub compare_mode: bool,
+    /// Names of the installed packages ticked for comparison, at most two.
+    pub compare_selection: Vec<String>,
+    /// Name typed in for a new package group.
+    pub new_group: String,
+    /// The group being renamed along with its new name so far.
+    pub renaming_group: Option<(String, String)>,
+    pub group_mode: bool,
+    /// Names of the packages ticked to be added to or removed from a group.
+    pub group_selection: Vec<String>,
+    /// Whether the packages can be ticked for the batch actions.
+    pub multi_select: bool,
+    /// The version range as typed in, only stored in the filters once it's valid.
+    pub min_version: String,
+    pub max_version: String,
+    pub version_range_error: Option<String>,
+    /// The date range as typed in, stored the same way as the version range.
+    pub date_from: String,
+    pub date_to: String,
+    pub date_range_error: Option<String>,
 }
 
 impl Controls {
-    pub fn view(&self, update_count: UpdateCount) -> Container<'_, GuiMessage> {
+    pub fn new() -> Self {
+        Self {
+            min_version: get_setting()
+                .filters
+                .min_version
+                .clone()
+                .unwrap_or_default(),
+            max_version: get_setting()
+                .filters
+                .max_version
+                .clone()
+                .unwrap_or_default(),
+            date_from: get_setting()
+                .filters
+                .date_from
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            date_to: get_setting()
+                .filters
+                .date_to
+                .map(|date| date.format(DATE_FORMAT).to_string())
+                .unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    pub fn view(
+        &self,
+        update_count: UpdateCount,
+        installed_size: u64,
+        minutes_to_check: u64,
+        downloads_paused: bool,
+        rate_limited: bool,
+    ) -> Container<'_, GuiMessage> {
         let update_button = {
-            let button = Button::new(Text::new("[C] Check for updates")).style(get_setting().theme);
+            let button = Button::new(Text::new(if self.fetching {
+                "[C] Checking..."
+            } else {
+                "[C] Check for updates"
+            }))
+            .style(get_setting().theme);
 
-            if CAN_CONNECT.load(Ordering::Relaxed)
+            if !self.fetching
+                && !rate_limited
+                && CAN_CONNECT.load(Ordering::Relaxed)
                 && !INSTALLING.load(Ordering::Relaxed)
                 && !FETCHING.load(Ordering::Relaxed)
             {
@@ -29,10 +130,48 @@ impl Controls {
             }
         };
 
+        let install_all_button = {
+            let button = Button::new(Text::new(match update_count.all {
+                Some(count) => format!("[A] Install all updates [{}]", count),
+                None => String::from("[A] Install all updates"),
+            }))
+            .style(get_setting().theme);
+
+            if update_count.all.is_some()
+                && CAN_CONNECT.load(Ordering::Relaxed)
+                && !FETCHING.load(Ordering::Relaxed)
+            {
+                button.on_press(GuiMessage::InstallAllUpdates)
+            } else {
+                button
+            }
+        };
+
+        let pause_button = {
+            let button = Button::new(Text::new(if downloads_paused {
+                "[>] Resume all"
+            } else {
+                "[||] Pause all"
+            }))
+            .style(get_setting().theme);
+
+            // Still resumable once the downloads are cancelled while paused.
+            if INSTALLING.load(Ordering::Relaxed) || downloads_paused {
+                button.on_press(GuiMessage::ToggleDownloadsPaused)
+            } else {
+                button
+            }
+        };
+
+        let search = TextInput::new("Search", &self.search, GuiMessage::SearchChanged)
+            .padding(5)
+            .style(get_setting().theme);
+
         let filter_row = |filter,
                           label,
                           checkbox_message: fn(bool) -> GuiMessage,
                           fetch_button,
+                          fetching: bool,
                           button_message: Option<GuiMessage>| {
             let row = Row::new()
                 .height(Length::Units(25))
@@ -43,11 +182,14 @@ impl Controls {
                         .style(get_setting().theme),
                 );
             if fetch_button {
-                let button = Button::new(Text::new("[F]")).style(get_setting().theme);
+                let button = Button::new(Text::new(if fetching { "Fetching..." } else { "[F]" }))
+                    .style(get_setting().theme);
 
                 match button_message {
                     Some(button_message) => {
-                        if CAN_CONNECT.load(Ordering::Relaxed)
+                        if !fetching
+                            && !rate_limited
+                            && CAN_CONNECT.load(Ordering::Relaxed)
                             && !INSTALLING.load(Ordering::Relaxed)
                             && !FETCHING.load(Ordering::Relaxed)
                         {
@@ -75,12 +217,14 @@ impl Controls {
                 },
                 GuiMessage::FilterUpdatesChanged,
                 false,
+                false,
                 None,
             ))
             .push(filter_row(
-                get_setting().filters.bookmarks,
-                String::from("Bookmarks"),
-                GuiMessage::FilterBookmarksChanged,
+                get_setting().filters.favourites,
+                String::from("Favourites"),
+                GuiMessage::FilterFavouritesChanged,
+                false,
                 false,
                 None,
             ))
@@ -89,6 +233,7 @@ impl Controls {
                 String::from("Installed"),
                 GuiMessage::FilterInstalledChanged,
                 false,
+                false,
                 None,
             ))
             .push(Rule::horizontal(5).style(get_setting().theme))
@@ -97,6 +242,7 @@ impl Controls {
                 String::from("All"),
                 GuiMessage::FilterAllChanged,
                 true,
+                false,
                 Some(GuiMessage::FetchAll),
             ))
             .push(filter_row(
@@ -109,6 +255,7 @@ impl Controls {
                 },
                 GuiMessage::FilterDailyLatestChanged,
                 true,
+                self.fetching_daily,
                 Some(GuiMessage::FetchDailyLatest),
             ))
             .push(filter_row(
@@ -116,6 +263,7 @@ impl Controls {
                 String::from("Daily (archive)"),
                 GuiMessage::FilterDailyArchiveChanged,
                 true,
+                self.fetching_daily,
                 Some(GuiMessage::FetchDailyArchive),
             ))
             .push(filter_row(
@@ -128,6 +276,7 @@ impl Controls {
                 },
                 GuiMessage::FilterExperimentalLatestChanged,
                 true,
+                self.fetching_experimental,
                 Some(GuiMessage::FetchExperimentalLatest),
             ))
             .push(filter_row(
@@ -135,6 +284,7 @@ impl Controls {
                 String::from("Experimental (archive)"),
                 GuiMessage::FilterExperimentalArchiveChanged,
                 true,
+                self.fetching_experimental,
                 Some(GuiMessage::FetchExperimentalArchive),
             ))
             .push(filter_row(
@@ -147,6 +297,7 @@ impl Controls {
                 },
                 GuiMessage::FilterPatchLatestChanged,
                 true,
+                self.fetching_patch,
                 Some(GuiMessage::FetchPatchLatest),
             ))
             .push(filter_row(
@@ -154,6 +305,7 @@ impl Controls {
                 String::from("Patch (archive)"),
                 GuiMessage::FilterPatchArchiveChanged,
                 true,
+                self.fetching_patch,
                 Some(GuiMessage::FetchPatchArchive),
             ))
             .push(filter_row(
@@ -166,6 +318,7 @@ impl Controls {
                 },
                 GuiMessage::FilterStableLatestChanged,
                 true,
+                self.fetching_stable,
                 Some(GuiMessage::FetchStableLatest),
             ))
             .push(filter_row(
@@ -173,6 +326,7 @@ impl Controls {
                 String::from("Stable (archive)"),
                 GuiMessage::FilterStableArchiveChanged,
                 true,
+                self.fetching_stable,
                 Some(GuiMessage::FetchStableArchive),
             ))
             .push(filter_row(
@@ -185,9 +339,247 @@ impl Controls {
                 },
                 GuiMessage::FilterLtsChanged,
                 true,
+                self.fetching_lts,
                 Some(GuiMessage::FetchLts),
+            ))
+            .push(Rule::horizontal(5).style(get_setting().theme));
+
+        let version_row = |label, placeholder, value, message: fn(String) -> GuiMessage| {
+            Row::new()
+                .height(Length::Units(25))
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .push(Text::new(label).width(Length::Fill))
+                .push(
+                    TextInput::new(placeholder, value, message)
+                        .padding(2)
+                        .width(Length::Units(60))
+                        .style(get_setting().theme),
+                )
+        };
+
+        let filters = filters
+            .push(version_row(
+                "Minimum version",
+                "3.0",
+                &self.min_version,
+                GuiMessage::FilterMinVersionChanged,
+            ))
+            .push(version_row(
+                "Maximum version",
+                "3.6",
+                &self.max_version,
+                GuiMessage::FilterMaxVersionChanged,
             ));
 
+        let filters = match &self.version_range_error {
+            Some(error) => filters.push(
+                Text::new(error)
+                    .size(TEXT_SIZE - 4)
+                    .color(get_setting().theme.highlight_text()),
+            ),
+            None => filters,
+        };
+
+        let date_row = |label, value, message: fn(String) -> GuiMessage| {
+            Row::new()
+                .height(Length::Units(25))
+                .spacing(5)
+                .align_items(Alignment::Center)
+                .push(Text::new(label).width(Length::Fill))
+                .push(
+                    TextInput::new("2022-06-02", value, message)
+                        .padding(2)
+                        .width(Length::Units(95))
+                        .style(get_setting().theme),
+                )
+        };
+
+        let date_presets =
+            DateRangePreset::ALL
+                .iter()
+                .fold(Row::new().spacing(3), |row, preset| {
+                    row.push(
+                        Button::new(Text::new(preset.to_string()).size(TEXT_SIZE - 4))
+                            .padding(3)
+                            .on_press(GuiMessage::DateRangePresetPicked(*preset))
+                            .style(get_setting().theme),
+                    )
+                });
+
+        let filters = filters
+            .push(date_row(
+                "Built from",
+                &self.date_from,
+                GuiMessage::FilterDateFromChanged,
+            ))
+            .push(date_row(
+                "Built until",
+                &self.date_to,
+                GuiMessage::FilterDateToChanged,
+            ))
+            .push(date_presets);
+
+        let filters = match &self.date_range_error {
+            Some(error) => filters.push(
+                Text::new(error)
+                    .size(TEXT_SIZE - 4)
+                    .color(get_setting().theme.highlight_text()),
+            ),
+            None => filters,
+        };
+
+        let groups = get_setting().package_groups.iter().fold(
+            Column::new()
+                .spacing(5)
+                .push(Text::new("Groups").color(get_setting().theme.highlight_text())),
+            |column, group| {
+                let row = Row::new()
+                    .spacing(5)
+                    .height(Length::Units(25))
+                    .align_items(Alignment::Center);
+
+                let row = match &self.renaming_group {
+                    Some((name, draft)) if *name == group.name => row
+                        .push(
+                            TextInput::new(&group.name, draft, GuiMessage::GroupRenameChanged)
+                                .on_submit(GuiMessage::GroupRenamed)
+                                .padding(2)
+                                .width(Length::Fill)
+                                .style(get_setting().theme),
+                        )
+                        .push(
+                            Button::new(Text::new("[S]"))
+                                .on_press(GuiMessage::GroupRenamed)
+                                .style(get_setting().theme),
+                        ),
+                    _ => {
+                        let name = group.name.clone();
+                        row.push(
+                            Checkbox::new(
+                                get_setting().filters.groups.contains(&group.name),
+                                format!("{} [{}]", group.name, group.packages.len()),
+                                move |active| GuiMessage::GroupFilterChanged(name.clone(), active),
+                            )
+                            .width(Length::Fill)
+                            .style(get_setting().theme),
+                        )
+                        .push(
+                            Button::new(Text::new("[R]"))
+                                .on_press(GuiMessage::GroupRenameStarted(group.name.clone()))
+                                .style(get_setting().theme),
+                        )
+                        .push(
+                            Button::new(Text::new("[X]"))
+                                .on_press(GuiMessage::GroupDeleted(group.name.clone()))
+                                .style(get_setting().theme),
+                        )
+                    }
+                };
+
+                let column = column.push(row);
+
+                if self.group_mode {
+                    let selected = !self.group_selection.is_empty();
+                    let button = |label, message| {
+                        let button = Button::new(Text::new(label))
+                            .width(Length::Fill)
+                            .style(get_setting().theme);
+                        if selected {
+                            button.on_press(message)
+                        } else {
+                            button
+                        }
+                    };
+                    column.push(
+                        Row::new()
+                            .spacing(5)
+                            .push(button(
+                                "[+] Add ticked",
+                                GuiMessage::AddToGroup(group.name.clone()),
+                            ))
+                            .push(button(
+                                "[-] Remove",
+                                GuiMessage::RemoveFromGroup(group.name.clone()),
+                            )),
+                    )
+                } else {
+                    column
+                }
+            },
+        );
+
+        let groups = groups
+            .push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(
+                        TextInput::new("New group", &self.new_group, GuiMessage::NewGroupChanged)
+                            .on_submit(GuiMessage::GroupCreated)
+                            .padding(2)
+                            .width(Length::Fill)
+                            .style(get_setting().theme),
+                    )
+                    .push({
+                        let button = Button::new(Text::new("[+]")).style(get_setting().theme);
+                        if self.new_group.trim().is_empty() {
+                            button
+                        } else {
+                            button.on_press(GuiMessage::GroupCreated)
+                        }
+                    }),
+            )
+            .push(
+                Checkbox::new(
+                    self.group_mode,
+                    "Pick packages for groups",
+                    GuiMessage::GroupModeToggled,
+                )
+                .width(Length::Fill)
+                .style(get_setting().theme),
+            );
+
+        let tags = get_setting().known_tags.iter().fold(
+            Column::new()
+                .spacing(5)
+                .push(Text::new("Tags").color(get_setting().theme.highlight_text())),
+            |column, tag| {
+                let name = tag.clone();
+                column.push(
+                    Checkbox::new(
+                        get_setting().filters.tags.contains(tag),
+                        tag,
+                        move |active| GuiMessage::TagFilterChanged(name.clone(), active),
+                    )
+                    .width(Length::Fill)
+                    .style(get_setting().theme),
+                )
+            },
+        );
+
+        let tags = if get_setting().known_tags.is_empty() {
+            tags.push(Text::new("Tag installed packages with [T] Tags").size(TEXT_SIZE - 4))
+        } else {
+            tags
+        };
+
+        let compare_mode = Checkbox::new(
+            self.compare_mode,
+            "Compare mode",
+            GuiMessage::CompareModeToggled,
+        )
+        .width(Length::Fill)
+        .style(get_setting().theme);
+
+        let multi_select = Button::new(Text::new(if self.multi_select {
+            "[M] Multi-select [on]"
+        } else {
+            "[M] Multi-select"
+        }))
+        .on_press(GuiMessage::MultiSelectToggled)
+        .style(get_setting().theme);
+
         let sorting = Row::new()
             .spacing(8)
             .align_items(Alignment::Center)
@@ -202,15 +594,60 @@ impl Controls {
                 .style(get_setting().theme),
             );
 
+        let fetch_progress = match self.fetch_progress {
+            Some(progress) => Column::new()
+                .spacing(3)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(format!(
+                        "Fetching {} ({}/{})...",
+                        progress.current_type,
+                        progress.completed + 1,
+                        progress.total
+                    ))
+                    .size(TEXT_SIZE - 4),
+                )
+                .push(
+                    ProgressBar::new(0.0..=progress.total as f32, progress.completed as f32)
+                        .height(Length::Units(5))
+                        .style(get_setting().theme),
+                ),
+            None => Column::new(),
+        };
+
         let scrollable = Scrollable::new(
             Column::new()
                 .spacing(5)
                 .padding(10)
                 .align_items(Alignment::Center)
                 .push(update_button)
+                .push(
+                    Text::new(format!("Channel: {}", get_setting().update_channel))
+                        .color(get_setting().theme.highlight_text()),
+                )
+                .push(
+                    Text::new(format!("Next check in {} min", minutes_to_check))
+                        .size(TEXT_SIZE - 4),
+                )
+                .push(fetch_progress)
+                .push(install_all_button)
+                .push(pause_button)
+                .push(search)
                 .push(filters)
                 .push(Space::with_height(Length::Units(3)))
-                .push(sorting),
+                .push(sorting)
+                .push(Space::with_height(Length::Units(3)))
+                .push(groups)
+                .push(Space::with_height(Length::Units(3)))
+                .push(tags)
+                .push(Space::with_height(Length::Units(3)))
+                .push(compare_mode)
+                .push(multi_select)
+                .push(Space::with_height(Length::Units(3)))
+                .push(Text::new(format!(
+                    "Total installed: {}",
+                    format_size(installed_size)
+                ))),
         );
 
         if CAN_CONNECT.load(Ordering::Relaxed) {
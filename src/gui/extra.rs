@@ -1,14 +1,18 @@
-use crate::releases::Releases;
+use super::{modal::ConfirmModal, monitor::Monitor};
+use crate::{
+    file_association,
+    package::{BuildHealth, Change, IntegrityStatus, Package},
+    releases::launcher::LauncherRelease,
+    settings::{get_setting, FontFamily, KeyAction},
+};
 use clap::crate_version;
 use iced::Executor;
-use self_update::update::Release;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    time::Instant,
+};
 
-#[derive(Debug)]
-pub struct GuiFlags {
-    pub releases: Releases,
-    pub file_path: Option<String>,
-    pub self_releases: Option<Vec<Release>>,
-}
 #[derive(Debug, Default)]
 pub struct GuiState {
     pub release_versions: Vec<String>,
@@ -17,15 +21,125 @@ pub struct GuiState {
     pub installing_release: bool,
     pub installed_release: bool,
     pub disk_space: Option<DiskSpace>,
+    pub window_changed: Option<Instant>,
+    /// Set while files are dragged over the window, so the first one dropped replaces the
+    /// current selection and the rest get added to it.
+    pub files_hovered: bool,
+    pub launcher_release: Option<LauncherRelease>,
+    pub updating_launcher: bool,
+    pub launcher_update_error: Option<String>,
+    /// Where the installed packages were before changing the packages location, so they can
+    /// be moved over.
+    pub previous_packages_dir: Option<PathBuf>,
+    /// Packages still waiting to be moved, along with how many there were to begin with.
+    pub migrating_packages: Vec<PathBuf>,
+    pub migration_total: usize,
+    pub move_packages_error: Option<String>,
+    pub proxy_url: String,
+    /// The font family name typed in, kept around while another font is picked.
+    pub custom_font: String,
+    pub proxy_bypass: String,
+    pub buildbot_api_url: String,
+    /// The mirror URL being added.
+    pub new_mirror: String,
+    /// How the last test of each mirror went, by URL.
+    pub mirror_tests: HashMap<String, String>,
+    /// The archive URL, name and version of the custom package being added.
+    pub custom_package_url: String,
+    pub custom_package_name: String,
+    pub custom_package_version: String,
+    /// Whether the URL of the custom package being added is being checked.
+    pub checking_custom_package: bool,
+    pub naming_template: String,
+    pub copy_info_template: String,
+    /// Name of the launch profile being created.
+    pub new_profile: String,
+    /// The launch arguments and environment variables being edited for each profile, in order.
+    pub profile_drafts: Vec<(String, String)>,
+    /// A non-fatal error shown in a banner under the tabs until dismissed.
+    pub error: Option<String>,
+    /// The action waiting for a new key combination to be pressed for it.
+    pub rebinding: Option<KeyAction>,
+    /// The question waiting to be answered, covering the current tab.
+    pub confirm_modal: Option<ConfirmModal>,
+    /// The two packages shown side by side, covering the current tab.
+    pub comparing: Option<(Package, Package)>,
+    /// The installed package and its update whose commits are shown, covering the current tab.
+    pub changelog: Option<(Package, Package)>,
+    /// The commits between them, once fetched.
+    pub changelog_commits: Option<Result<Vec<Change>, String>>,
+    /// Names of the packages being installed automatically at launch.
+    pub background_installs: HashSet<String>,
+    /// Results of the integrity checks run this session, by package name.
+    pub integrity: HashMap<String, IntegrityStatus>,
+    /// Packages that turned up in a fetch this session and haven't been scrolled into view yet,
+    /// by name.
+    pub new_since_last_session: HashSet<String>,
+    /// How far down the packages list and the settings are scrolled, from 0 to 1, so switching
+    /// tabs brings them back to where they were.
+    pub packages_scroll_offset: f32,
+    pub settings_scroll_offset: f32,
+    /// The monitors there were at launch, to tell which one the window is moved onto.
+    pub monitors: Vec<Monitor>,
+    /// Whether the test launches run this session went through, by package name.
+    pub test_launches: HashMap<String, bool>,
+    /// Build health asked from the buildbot this session, by package name.
+    pub build_health: HashMap<String, BuildHealth>,
+    /// The shown packages still missing their build health, asked for once they stop changing.
+    pub build_health_wanted: Vec<String>,
+    /// Bumped every time `build_health_wanted` changes, so only the last wait goes through.
+    pub build_health_generation: u64,
+    /// Packages whose build health is being asked for.
+    pub build_health_pending: HashSet<String>,
+    /// Whether a sync happened since the packages directory was last looked through for packages
+    /// installed by hand.
+    pub external_scan_wanted: bool,
+    /// Whether it's being looked through.
+    pub scanning_external: bool,
+    /// Disk usage of installed packages by name, only measured once sorting by size.
+    pub package_sizes: HashMap<String, u64>,
+    /// Whether .blend files are set to open with Ablavema, checked once at launch.
+    pub file_association_registered: bool,
+    /// Minutes since updates were last checked while the launcher is open.
+    pub elapsed_minutes: u64,
+    /// Whether restoring the default settings also clears the default package, favourites and
+    /// recent files.
+    pub reset_package_data: bool,
 }
 
 impl GuiState {
     pub fn new() -> Self {
         Self {
             pick_list_selected_releases: crate_version!().to_string(),
+            proxy_url: get_setting().proxy_url.clone().unwrap_or_default(),
+            proxy_bypass: get_setting().proxy_bypass.join(", "),
+            buildbot_api_url: get_setting().buildbot_api_url.clone(),
+            naming_template: get_setting().naming_template.clone().unwrap_or_default(),
+            copy_info_template: get_setting().copy_info_template.clone(),
+            profile_drafts: GuiState::profile_drafts(),
+            custom_font: match &get_setting().font_family {
+                FontFamily::Custom(name) => name.clone(),
+                _ => String::new(),
+            },
+            file_association_registered: file_association::is_registered(),
             ..Default::default()
         }
     }
+
+    pub fn profile_drafts() -> Vec<(String, String)> {
+        get_setting()
+            .profiles
+            .iter()
+            .map(|profile| (profile.args_text(), profile.env_text()))
+            .collect()
+    }
+}
+
+/// A short message shown in the corner of the window until it expires.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub message: String,
+    pub expires_at: Instant,
 }
 
 pub struct GlobalTokio;
@@ -53,6 +167,35 @@ impl Choice {
     pub const ALL: [Choice; 2] = [Choice::Enable, Choice::Disable];
 }
 
+/// How many installed packages of a build to keep, as offered on the Settings tab.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct KeepCount(pub Option<usize>);
+
+impl KeepCount {
+    pub const ALL: [KeepCount; 11] = [
+        KeepCount(None),
+        KeepCount(Some(1)),
+        KeepCount(Some(2)),
+        KeepCount(Some(3)),
+        KeepCount(Some(4)),
+        KeepCount(Some(5)),
+        KeepCount(Some(6)),
+        KeepCount(Some(7)),
+        KeepCount(Some(8)),
+        KeepCount(Some(9)),
+        KeepCount(Some(10)),
+    ];
+}
+
+impl std::fmt::Display for KeepCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(count) => write!(f, "{}", count),
+            None => write!(f, "All"),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum BuildTypeSettings {
     All,
@@ -1,13 +1,27 @@
 use crate::{
+    helpers::parse_semver,
     package::{BuildType, Package, PackageState, PackageStatus},
     settings::get_setting,
 };
+use chrono::{Datelike, Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+/// How the dates of the range are typed in.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A named set of packages picked by hand, such as the builds used by a project.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+pub struct PackageGroup {
+    pub name: String,
+    pub packages: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
 pub struct Filters {
     pub updates: bool,
-    pub bookmarks: bool,
+    #[serde(alias = "bookmarks")]
+    pub favourites: bool,
     pub installed: bool,
     pub all: bool,
     pub daily_latest: bool,
@@ -19,10 +33,77 @@ pub struct Filters {
     pub stable_latest: bool,
     pub stable_archive: bool,
     pub lts: bool,
+    /// Names of the groups whose packages are shown.
+    #[serde(default)]
+    pub groups: BTreeSet<String>,
+    /// Packages with any of these tags are shown.
+    #[serde(default)]
+    pub tags: BTreeSet<String>,
+    /// The lowest version shown, like `3.0`.
+    #[serde(default)]
+    pub min_version: Option<String>,
+    /// The highest version shown, including its patches and pre-releases.
+    #[serde(default)]
+    pub max_version: Option<String>,
+    /// The first day the shown packages were built on.
+    #[serde(default)]
+    pub date_from: Option<NaiveDate>,
+    /// The last day the shown packages were built on, that day included.
+    #[serde(default)]
+    pub date_to: Option<NaiveDate>,
+}
+
+/// Ranges of build dates picked with a single click.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DateRangePreset {
+    LastWeek,
+    LastMonth,
+    ThisYear,
+    Any,
+}
+
+impl DateRangePreset {
+    pub const ALL: [DateRangePreset; 4] = [
+        DateRangePreset::LastWeek,
+        DateRangePreset::LastMonth,
+        DateRangePreset::ThisYear,
+        DateRangePreset::Any,
+    ];
+
+    /// The start and end of the range as of today, both left open for `Any`.
+    pub fn range(self) -> (Option<NaiveDate>, Option<NaiveDate>) {
+        let today = Local::today().naive_local();
+        match self {
+            DateRangePreset::LastWeek => (Some(today - Duration::days(7)), Some(today)),
+            DateRangePreset::LastMonth => (Some(today - Duration::days(30)), Some(today)),
+            DateRangePreset::ThisYear => (NaiveDate::from_ymd_opt(today.year(), 1, 1), Some(today)),
+            DateRangePreset::Any => (None, None),
+        }
+    }
+}
+
+impl std::fmt::Display for DateRangePreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                DateRangePreset::LastWeek => "7 days",
+                DateRangePreset::LastMonth => "30 days",
+                DateRangePreset::ThisYear => "This year",
+                DateRangePreset::Any => "Any",
+            }
+        )
+    }
 }
 
 impl Filters {
-    pub fn matches(&self, package: &Package) -> bool {
+    /// Pinned packages always match, so they stay in view while the filters change.
+    pub fn matches(&self, package: &Package, search: &str, pinned: &HashSet<String>) -> bool {
+        if pinned.contains(&package.name) {
+            return true;
+        }
+
         let mut matches = match &package.build_type {
             BuildType::Daily {
                 latest,
@@ -49,6 +130,36 @@ impl Filters {
             BuildType::None => unreachable!("uninitialised build type"),
         };
 
+        // Favourites are shown no matter their build type.
+        if self.favourites {
+            matches = get_setting().favourite_packages.contains(&package.name);
+        }
+
+        // Same for groups, which are picked by hand as well. Packages in any of the active
+        // groups are shown.
+        if !self.groups.is_empty() {
+            let in_group = get_setting()
+                .package_groups
+                .iter()
+                .filter(|group| self.groups.contains(&group.name))
+                .any(|group| group.packages.contains(&package.name));
+            matches = if self.favourites {
+                matches && in_group
+            } else {
+                in_group
+            };
+        }
+
+        // Tags narrow down the favourites and groups, if any of those are active too.
+        if !self.tags.is_empty() {
+            let tagged = package.tags.iter().any(|tag| self.tags.contains(tag));
+            matches = if self.favourites || !self.groups.is_empty() {
+                matches && tagged
+            } else {
+                tagged
+            };
+        }
+
         if !matches {
             return false;
         }
@@ -69,13 +180,115 @@ impl Filters {
             return false;
         }
 
-        if self.bookmarks {
-            matches = get_setting().bookmarks.contains(&package.name);
+        // Pre-releases of the minimum version are let through, since `3.0` is meant to include
+        // the 3.0 alphas as well.
+        if let Some(version) = parse_semver(&package.version.to_string()) {
+            let version = (version.major, version.minor, version.patch);
+            if let Some(min_version) = self.min_version.as_deref().and_then(parse_semver) {
+                matches = version >= (min_version.major, min_version.minor, min_version.patch);
+            }
+            // A maximum without a patch number, like `3.3`, covers every 3.3 patch.
+            if let Some(max_version) = self.max_version.as_deref() {
+                if let Some(parsed) = parse_semver(max_version) {
+                    matches = matches
+                        && if max_version.trim().matches('.').count() < 2 {
+                            (version.0, version.1) <= (parsed.major, parsed.minor)
+                        } else {
+                            version <= (parsed.major, parsed.minor, parsed.patch)
+                        };
+                }
+            }
+        }
+
+        if !matches {
+            return false;
+        }
+
+        let date = package.date.date();
+        if self.date_from.is_some_and(|date_from| date < date_from)
+            || self.date_to.is_some_and(|date_to| date > date_to)
+        {
+            return false;
+        }
+
+        if get_setting().hide_pre_release {
+            matches = parse_semver(&package.version.to_string())
+                .map(|version| version.pre.is_empty())
+                .unwrap_or(true);
+        }
+
+        if !matches {
+            return false;
+        }
+
+        if !search.is_empty() {
+            let search = search.to_lowercase();
+            matches = package.name.to_lowercase().contains(&search)
+                || package.version.to_string().to_lowercase().contains(&search)
+                || package.build.to_string().to_lowercase().contains(&search);
         }
 
         matches
     }
 
+    /// Checks the versions typed in for the range, giving back what should be stored. Empty
+    /// ones leave that end of the range open.
+    pub fn validate_version_range(
+        min_version: &str,
+        max_version: &str,
+    ) -> Result<(Option<String>, Option<String>), String> {
+        let parse = |version: &str| match version.trim() {
+            "" => Ok(None),
+            version => match parse_semver(version) {
+                Some(parsed) => Ok(Some((version.to_string(), parsed))),
+                None => Err(format!("'{}' isn't a version", version)),
+            },
+        };
+
+        let min_version = parse(min_version)?;
+        let max_version = parse(max_version)?;
+
+        if let (Some((_, min)), Some((_, max))) = (&min_version, &max_version) {
+            if (min.major, min.minor) > (max.major, max.minor)
+                || (min.major, min.minor) == (max.major, max.minor)
+                    && max_version.as_ref().unwrap().0.matches('.').count() >= 2
+                    && min.patch > max.patch
+            {
+                return Err(String::from("The minimum is above the maximum"));
+            }
+        }
+
+        Ok((
+            min_version.map(|(version, _)| version),
+            max_version.map(|(version, _)| version),
+        ))
+    }
+
+    /// Checks the dates typed in for the range, like `validate_version_range` does for the
+    /// versions.
+    pub fn validate_date_range(
+        date_from: &str,
+        date_to: &str,
+    ) -> Result<(Option<NaiveDate>, Option<NaiveDate>), String> {
+        let parse = |date: &str| match date.trim() {
+            "" => Ok(None),
+            date => NaiveDate::parse_from_str(date, DATE_FORMAT)
+                .map(Some)
+                .map_err(|_| format!("'{}' isn't a date like 2022-06-02", date)),
+        };
+
+        let date_from = parse(date_from)?;
+        let date_to = parse(date_to)?;
+
+        if let (Some(from), Some(to)) = (date_from, date_to) {
+            if from > to {
+                return Err(String::from("The start date is after the end date"));
+            }
+        }
+
+        Ok((date_from, date_to))
+    }
+
     pub fn refresh_all(&mut self) {
         self.all = self.daily_latest
             && self.daily_archive
@@ -93,7 +306,7 @@ impl Default for Filters {
     fn default() -> Self {
         Self {
             updates: false,
-            bookmarks: false,
+            favourites: false,
             installed: false,
             all: true,
             daily_latest: true,
@@ -105,6 +318,12 @@ impl Default for Filters {
             stable_latest: true,
             stable_archive: true,
             lts: true,
+            groups: BTreeSet::new(),
+            tags: BTreeSet::new(),
+            min_version: None,
+            max_version: None,
+            date_from: None,
+            date_to: None,
         }
     }
 }
@@ -1,17 +1,33 @@
+#[cfg(feature = "gui")]
 use super::{package::PackageMessage, GuiMessage};
-use crate::{package::Package, settings::get_setting};
-use iced_futures::{
-    futures::stream::{unfold, BoxStream},
-    subscription,
+use crate::{
+    helpers::{download_client, http_client, mirror_url},
+    package::Package,
+    settings::get_setting,
 };
+use futures::stream::{unfold, BoxStream};
+#[cfg(feature = "gui")]
+use iced_native::subscription;
+use reqwest::{header::RANGE, Response, StatusCode};
 use ron::ser::{to_string_pretty, PrettyConfig};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "gui")]
+use std::hash::{Hash, Hasher};
 use std::{
+    collections::VecDeque,
     fs::{create_dir_all, rename, File},
-    hash::{Hash, Hasher},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
 };
-use tokio::fs::{remove_dir_all, remove_file};
+use tokio::{
+    fs::{remove_dir_all, remove_file},
+    sync::{watch, OwnedSemaphorePermit, Semaphore},
+    time::timeout,
+};
+use tokio_util::sync::CancellationToken;
+use url::Url;
 
 #[cfg(target_os = "linux")]
 use bzip2::read::BzDecoder;
@@ -24,24 +40,27 @@ use tar::Archive;
 #[cfg(target_os = "linux")]
 use xz2::read::XzDecoder;
 
+#[cfg(target_os = "macos")]
+use crate::helpers::extract_dmg;
+
 #[cfg(target_os = "windows")]
-use std::{io::Read, thread::sleep, time::Duration};
+use std::{io::Read, thread::sleep};
 #[cfg(target_os = "windows")]
 use zip::{read::ZipFile, ZipArchive};
 
 macro_rules! unwrap_or_return {
-    ($index:expr, $result:expr) => {
+    ($index:expr, $result:expr, $reason:literal) => {
         match $result {
             Ok(x) => x,
             Err(e) => {
-                eprintln!(
+                log::error!(
                     "GUI install error at {}:{}, which was:\n{:#?}",
                     file!(),
                     line!(),
                     e
                 );
                 return Some((
-                    ($index, Progress::Errored(e.to_string())),
+                    ($index, Progress::Errored(format!("{}: {}", $reason, e))),
                     State::FinishedInstalling,
                 ));
             }
@@ -49,109 +68,267 @@ macro_rules! unwrap_or_return {
     };
 }
 
-pub struct Install(Package);
+const SPEED_WINDOW: Duration = Duration::from_secs(3);
+
+/// The slots for downloads running at the same time, shared by every installation.
+#[derive(Clone, Debug)]
+pub struct DownloadSlots {
+    semaphore: Arc<Semaphore>,
+    /// Slots taken away while downloads were running in them, still to be dropped once those
+    /// finish. Raising the limit again gives these back before adding new ones.
+    owed: Arc<Mutex<usize>>,
+}
+
+impl DownloadSlots {
+    pub fn new(count: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(count)),
+            owed: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    /// Waits for a slot to be free.
+    async fn acquire(&self) -> DownloadSlot {
+        DownloadSlot {
+            permit: Some(self.semaphore.clone().acquire_owned().await.unwrap()),
+            owed: self.owed.clone(),
+        }
+    }
+
+    /// Goes from `old` slots to `new` ones. Slots in use are only taken away once their downloads
+    /// finish.
+    pub fn resize(&self, old: usize, new: usize) {
+        let mut owed = self.owed.lock().unwrap_or_else(PoisonError::into_inner);
+        if new > old {
+            let given_back = (new - old).min(*owed);
+            *owed -= given_back;
+            self.semaphore.add_permits(new - old - given_back);
+        } else if new < old {
+            let taken = self.semaphore.forget_permits(old - new);
+            *owed += old - new - taken;
+        }
+    }
+}
+
+/// A download's slot, freed when dropped unless the limit was lowered meanwhile.
+struct DownloadSlot {
+    permit: Option<OwnedSemaphorePermit>,
+    owed: Arc<Mutex<usize>>,
+}
+
+impl Drop for DownloadSlot {
+    fn drop(&mut self) {
+        let mut owed = self.owed.lock().unwrap_or_else(PoisonError::into_inner);
+        if *owed > 0 {
+            *owed -= 1;
+            if let Some(permit) = self.permit.take() {
+                permit.forget();
+            }
+        }
+    }
+}
+
+pub struct Install {
+    package: Package,
+    cancellation_token: CancellationToken,
+    download_slots: DownloadSlots,
+    /// Holds the download while it's true.
+    paused: watch::Receiver<bool>,
+}
 
 impl Install {
-    pub fn package(package: Package) -> iced::Subscription<GuiMessage> {
-        iced::Subscription::from_recipe(Install(package)).map(|(index, progress)| {
+    #[cfg(feature = "gui")]
+    pub fn package(
+        package: Package,
+        cancellation_token: CancellationToken,
+        download_slots: DownloadSlots,
+        paused: watch::Receiver<bool>,
+    ) -> iced::Subscription<GuiMessage> {
+        iced::Subscription::from_recipe(Install {
+            package,
+            cancellation_token,
+            download_slots,
+            paused,
+        })
+        .map(|(index, progress)| {
             GuiMessage::PackageMessage((index, PackageMessage::InstallationProgress(progress)))
         })
     }
-}
-
-impl<H, I> subscription::Recipe<H, I> for Install
-where
-    H: Hasher,
-{
-    type Output = (usize, Progress);
 
-    fn hash(&self, state: &mut H) {
-        std::any::TypeId::of::<Self>().hash(state);
-        self.0.name.hash(state);
-        self.0.date.hash(state);
+    /// Runs the installation outside of the GUI, yielding the same progress as the subscription.
+    pub fn standalone(
+        package: Package,
+        download_slots: DownloadSlots,
+    ) -> BoxStream<'static, (usize, Progress)> {
+        Install {
+            package,
+            cancellation_token: CancellationToken::new(),
+            download_slots,
+            paused: watch::channel(false).1,
+        }
+        .into_stream()
     }
 
-    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Self::Output> {
+    fn into_stream(self) -> BoxStream<'static, (usize, Progress)> {
         Box::pin(unfold(
             State::ReadyToInstall {
-                index: self.0.index,
-                package: self.0,
+                index: self.package.index,
+                package: self.package,
+                cancellation_token: self.cancellation_token,
+                download_slots: self.download_slots,
+                paused: self.paused,
             },
             |state| async move {
                 match state {
-                    State::ReadyToInstall { index, package } => {
-                        let response = reqwest::get(&package.url).await;
+                    State::ReadyToInstall {
+                        index,
+                        package,
+                        cancellation_token,
+                        download_slots,
+                        paused,
+                    } => {
+                        // Stays queued until one of the concurrent download slots is free.
+                        let download_slot = tokio::select! {
+                            download_slot = download_slots.acquire() => download_slot,
+                            _ = cancellation_token.cancelled() => {
+                                return Some((
+                                    (index, Progress::Cancelled),
+                                    State::FinishedInstalling,
+                                ));
+                            }
+                        };
+
+                        let file = match archive_file_name(&package.url) {
+                            Some(file_name) => get_setting().cache_dir.join(file_name),
+                            None => {
+                                return Some((
+                                    (
+                                        index,
+                                        Progress::Errored(format!(
+                                            "couldn't prepare the download: {} has no file name",
+                                            package.url
+                                        )),
+                                    ),
+                                    State::FinishedInstalling,
+                                ));
+                            }
+                        };
+
+                        // Whatever was left of an earlier download gets picked up where it
+                        // stopped, as long as the server supports ranges.
+                        let resume_from = if get_setting().resume_downloads {
+                            file.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+                        } else {
+                            0
+                        };
+
+                        let response = tokio::select! {
+                            response = send_download_request(&package.url, resume_from) => response,
+                            _ = cancellation_token.cancelled() => {
+                                // Cancelling means the package isn't wanted, so whatever was
+                                // kept around for resuming goes too.
+                                clean_up(&file).await;
+
+                                return Some((
+                                    (index, Progress::Cancelled),
+                                    State::FinishedInstalling,
+                                ));
+                            }
+                        };
 
                         match response {
                             Ok(response) => {
-                                if let Some(total) = response.content_length() {
-                                    create_dir_all(&get_setting().cache_dir).unwrap();
-
-                                    let file = get_setting()
-                                        .cache_dir
-                                        .join(package.url.split_terminator('/').last().unwrap());
-
-                                    // TODO: Give option to reuse previously downloaded packages.
-                                    // Could have an extra button [Install from cache].
-                                    // Useful for reinstalling. Will need to make sure to show an
-                                    // error if that package is corrupted due to stopping the
-                                    // download midway. Or make it delete whatever was downloaded
-                                    // if the install was canceled. Though it's possible the file
-                                    // will still be left there if the program crashed, so having
-                                    // both is recommended.
-                                    // Also worth considering not deleting the entry from the
-                                    // database if there's a valid downloaded archive, so the user
-                                    // can reinstall it even if it becomes unavailable like is the
-                                    // case with daily and experimental packages.
-                                    if file.exists() {
-                                        unwrap_or_return!(index, remove_file(&file).await);
-                                    }
-
-                                    let package_dir =
-                                        get_setting().packages_dir.join(&package.name);
-
-                                    if package_dir.exists() {
-                                        unwrap_or_return!(
+                                if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                                    // The archive left behind doesn't fit what's on the server
+                                    // anymore, so start over without it.
+                                    clean_up(&file).await;
+
+                                    return Some((
+                                        (index, Progress::Started(None)),
+                                        State::ReadyToInstall {
                                             index,
-                                            remove_dir_all(&package_dir).await
-                                        );
-                                    }
+                                            package,
+                                            cancellation_token,
+                                            download_slots,
+                                            paused,
+                                        },
+                                    ));
+                                }
+
+                                let resumed = resume_from > 0
+                                    && response.status() == StatusCode::PARTIAL_CONTENT;
+                                let already_downloaded = if resumed { resume_from } else { 0 };
+
+                                // Chunked responses don't say how much is coming, so the
+                                // download goes on without a total.
+                                let total = response
+                                    .content_length()
+                                    .map(|length| length + already_downloaded);
 
-                                    let destination = unwrap_or_return!(
+                                create_dir_all(&get_setting().cache_dir).unwrap();
+
+                                // TODO: Give option to reuse previously downloaded packages.
+                                // Could have an extra button [Install from cache].
+                                // Useful for reinstalling. Will need to make sure to show an
+                                // error if that package is corrupted due to stopping the
+                                // download midway. Or make it delete whatever was downloaded
+                                // if the install was canceled. Though it's possible the file
+                                // will still be left there if the program crashed, so having
+                                // both is recommended.
+                                // Also worth considering not deleting the entry from the
+                                // database if there's a valid downloaded archive, so the user
+                                // can reinstall it even if it becomes unavailable like is the
+                                // case with daily and experimental packages.
+                                if file.exists() && !resumed {
+                                    unwrap_or_return!(
                                         index,
-                                        tokio::fs::OpenOptions::new()
-                                            .create(true)
-                                            .append(true)
-                                            .open(&file)
-                                            .await
+                                        remove_file(&file).await,
+                                        "couldn't prepare the download"
                                     );
+                                }
 
-                                    Some((
-                                        (index, Progress::Started),
-                                        State::Downloading {
-                                            response,
-                                            file,
-                                            destination,
-                                            total,
-                                            downloaded: 0,
-                                            index,
-                                            package,
-                                        },
-                                    ))
-                                } else {
-                                    Some((
-                                        (
-                                            index,
-                                            Progress::Errored(String::from(
-                                                "cannot find content length",
-                                            )),
-                                        ),
-                                        State::FinishedInstalling,
-                                    ))
+                                let package_dir = get_setting().packages_dir.join(&package.name);
+
+                                if package_dir.exists() {
+                                    unwrap_or_return!(
+                                        index,
+                                        remove_dir_all(&package_dir).await,
+                                        "couldn't prepare the download"
+                                    );
                                 }
+
+                                let destination = unwrap_or_return!(
+                                    index,
+                                    tokio::fs::OpenOptions::new()
+                                        .create(true)
+                                        .append(true)
+                                        .open(&file)
+                                        .await,
+                                    "couldn't prepare the download"
+                                );
+
+                                Some((
+                                    (index, Progress::Started(total)),
+                                    State::Downloading {
+                                        response,
+                                        file,
+                                        destination,
+                                        total,
+                                        downloaded: already_downloaded,
+                                        samples: VecDeque::from([(
+                                            Instant::now(),
+                                            already_downloaded,
+                                        )]),
+                                        index,
+                                        package,
+                                        cancellation_token,
+                                        download_slot,
+                                        paused,
+                                    },
+                                ))
                             }
                             Err(e) => Some((
-                                (index, Progress::Errored(e.to_string())),
+                                (index, Progress::Errored(format!("network error: {}", e))),
                                 State::FinishedInstalling,
                             )),
                         }
@@ -162,9 +339,39 @@ where
                         mut destination,
                         total,
                         downloaded,
+                        mut samples,
                         index,
                         package,
-                    } => match response.chunk().await {
+                        cancellation_token,
+                        download_slot,
+                        mut paused,
+                    } => match tokio::select! {
+                        chunk = async {
+                            wait_for_resume(&mut paused).await;
+                            timeout(read_timeout(), response.chunk()).await
+                        } => match chunk {
+                            Ok(chunk) => chunk,
+                            Err(_) => {
+                                return Some((
+                                    (
+                                        index,
+                                        Progress::Errored(format!(
+                                            "network error: no data received for {} seconds",
+                                            read_timeout().as_secs()
+                                        )),
+                                    ),
+                                    State::FinishedInstalling,
+                                ));
+                            }
+                        },
+                        _ = cancellation_token.cancelled() => {
+                            // Only interrupted or failed downloads are kept for resuming.
+                            drop(destination);
+                            clean_up(&file).await;
+
+                            return Some(((index, Progress::Cancelled), State::FinishedInstalling));
+                        }
+                    } {
                         // TODO: Handle case when temporarily banned for making too many requests.
                         // I had this happen when testing too frequently. Probably not an issue for
                         // normal users, but it may make the download hang, in which case there's a
@@ -175,38 +382,124 @@ where
                         Ok(Some(chunk)) => {
                             unwrap_or_return!(
                                 index,
-                                tokio::io::AsyncWriteExt::write_all(&mut destination, &chunk).await
+                                tokio::io::AsyncWriteExt::write_all(&mut destination, &chunk).await,
+                                "couldn't save the download"
                             );
 
                             let downloaded = downloaded + chunk.len() as u64;
-                            let percentage = (downloaded as f32 / total as f32) * 100.0;
+                            let percentage = match total {
+                                Some(total) => (downloaded as f32 / total as f32) * 100.0,
+                                None => 0.0,
+                            };
+
+                            // The speed is averaged over the last few seconds so it doesn't jump
+                            // around with every chunk.
+                            let now = Instant::now();
+                            samples.push_back((now, downloaded));
+                            while samples.len() > 2
+                                && now.duration_since(samples[1].0) >= SPEED_WINDOW
+                            {
+                                samples.pop_front();
+                            }
+                            let (oldest_time, oldest_downloaded) = samples[0];
+                            let elapsed = now.duration_since(oldest_time).as_secs_f64();
+                            let bytes_per_sec = if elapsed > 0.0 {
+                                (downloaded - oldest_downloaded) as f64 / elapsed
+                            } else {
+                                0.0
+                            };
+                            let eta_secs = match total {
+                                Some(total) if bytes_per_sec > 0.0 => Some(
+                                    (total.saturating_sub(downloaded) as f64 / bytes_per_sec).ceil()
+                                        as u64,
+                                ),
+                                _ => None,
+                            };
 
                             Some((
-                                (index, Progress::DownloadProgress(percentage)),
+                                (
+                                    index,
+                                    Progress::DownloadProgress {
+                                        progress: percentage,
+                                        downloaded,
+                                        bytes_per_sec,
+                                        eta_secs,
+                                        chunk_size: chunk.len() as u64,
+                                    },
+                                ),
                                 State::Downloading {
                                     response,
                                     file,
                                     destination,
                                     total,
                                     downloaded,
+                                    samples,
                                     index,
                                     package,
+                                    cancellation_token,
+                                    download_slot,
+                                    paused,
                                 },
                             ))
                         }
                         Ok(None) => Some((
                             (index, Progress::FinishedDownloading),
-                            State::FinishedDownloading {
+                            State::Verifying {
                                 file,
                                 index,
                                 package,
                             },
                         )),
                         Err(e) => Some((
-                            (index, Progress::Errored(e.to_string())),
+                            (index, Progress::Errored(format!("network error: {}", e))),
                             State::FinishedInstalling,
                         )),
                     },
+                    State::Verifying {
+                        file,
+                        index,
+                        package,
+                    } => match fetch_checksum(&package).await {
+                        Some(expected) => {
+                            let actual = unwrap_or_return!(
+                                index,
+                                hash_file(&file),
+                                "couldn't verify the download"
+                            );
+
+                            if actual.eq_ignore_ascii_case(&expected) {
+                                Some((
+                                    (index, Progress::ChecksumVerified),
+                                    State::FinishedDownloading {
+                                        file,
+                                        index,
+                                        package,
+                                    },
+                                ))
+                            } else {
+                                clean_up(&file).await;
+
+                                Some((
+                                    (
+                                        index,
+                                        Progress::ChecksumFailed(format!(
+                                            "checksum mismatch, expected {} but got {}",
+                                            expected, actual
+                                        )),
+                                    ),
+                                    State::FinishedInstalling,
+                                ))
+                            }
+                        }
+                        None => Some((
+                            (index, Progress::ChecksumUnavailable),
+                            State::FinishedDownloading {
+                                file,
+                                index,
+                                package,
+                            },
+                        )),
+                    },
                     State::FinishedDownloading {
                         file,
                         index,
@@ -218,34 +511,37 @@ where
                         let extraction_dir = get_setting().cache_dir.join(&package.name);
                         create_dir_all(&extraction_dir).unwrap();
 
-                        let archive = if file.extension().unwrap() == "xz" {
-                            #[cfg(not(target_os = "linux"))]
-                            unreachable!("Linux extraction on non-Linux OS");
+                        let extension = file
+                            .extension()
+                            .and_then(|extension| extension.to_str())
+                            .unwrap_or_default();
+                        // Only the archives this system can extract have an arm, anything else
+                        // like a custom package for another OS ends up as an error.
+                        let archive = match extension {
                             #[cfg(target_os = "linux")]
-                            DownloadedArchive::TarXz { extraction_dir }
-                        } else if file.extension().unwrap() == "bz2" {
-                            #[cfg(not(target_os = "linux"))]
-                            unreachable!("Linux extraction on non-Linux OS");
+                            "xz" => DownloadedArchive::TarXz { extraction_dir },
                             #[cfg(target_os = "linux")]
-                            DownloadedArchive::TarBz { extraction_dir }
-                        } else if file.extension().unwrap() == "gz" {
-                            #[cfg(not(target_os = "linux"))]
-                            unreachable!("Linux extraction on non-Linux OS");
+                            "bz2" => DownloadedArchive::TarBz { extraction_dir },
                             #[cfg(target_os = "linux")]
-                            DownloadedArchive::TarGz { extraction_dir }
-                        } else if file.extension().unwrap() == "zip" {
-                            #[cfg(not(target_os = "windows"))]
-                            unreachable!("Windows extraction on non-Windows OS");
+                            "gz" => DownloadedArchive::TarGz { extraction_dir },
                             #[cfg(target_os = "windows")]
-                            {
+                            "zip" => {
                                 // This is a workaround for a relatively common extraction error
                                 // where apparently the extraction starts just before the file
                                 // was completely written, so it was giving an "invalid Zip
                                 // archive" error.
                                 sleep(Duration::from_millis(250));
 
-                                let zip = unwrap_or_return!(index, File::open(&file));
-                                let archive = unwrap_or_return!(index, ZipArchive::new(zip));
+                                let zip = unwrap_or_return!(
+                                    index,
+                                    File::open(&file),
+                                    "extraction failed"
+                                );
+                                let archive = unwrap_or_return!(
+                                    index,
+                                    ZipArchive::new(zip),
+                                    "extraction failed"
+                                );
 
                                 // This handles some archives that don't have an inner directory.
                                 let extraction_dir =
@@ -267,10 +563,21 @@ where
                                     extracted: 0,
                                 }
                             }
-                        } else if file.extension().unwrap() == "dmg" {
-                            todo!("macos extraction");
-                        } else {
-                            panic!("Unknown archive extension");
+                            #[cfg(target_os = "macos")]
+                            "dmg" => DownloadedArchive::Dmg { extraction_dir },
+                            _ => {
+                                return Some((
+                                    (
+                                        index,
+                                        Progress::Errored(format!(
+                                            "extraction failed: {} can't be extracted on this \
+                                            system",
+                                            file.display()
+                                        )),
+                                    ),
+                                    State::FinishedInstalling,
+                                ));
+                            }
                         };
 
                         Some((
@@ -291,13 +598,20 @@ where
                     } => match archive {
                         #[cfg(target_os = "linux")]
                         DownloadedArchive::TarXz { extraction_dir } => {
-                            let tar_xz = unwrap_or_return!(index, File::open(&file));
+                            let tar_xz =
+                                unwrap_or_return!(index, File::open(&file), "extraction failed");
                             let tar = XzDecoder::new(tar_xz);
                             let mut archive = Archive::new(tar);
 
-                            for entry in unwrap_or_return!(index, archive.entries()) {
-                                let mut file = unwrap_or_return!(index, entry);
-                                unwrap_or_return!(index, file.unpack_in(&extraction_dir));
+                            for entry in
+                                unwrap_or_return!(index, archive.entries(), "extraction failed")
+                            {
+                                let mut file = unwrap_or_return!(index, entry, "extraction failed");
+                                unwrap_or_return!(
+                                    index,
+                                    file.unpack_in(&extraction_dir),
+                                    "extraction failed"
+                                );
                             }
 
                             Some((
@@ -307,13 +621,20 @@ where
                         }
                         #[cfg(target_os = "linux")]
                         DownloadedArchive::TarGz { extraction_dir } => {
-                            let tar_gz = unwrap_or_return!(index, File::open(&file));
+                            let tar_gz =
+                                unwrap_or_return!(index, File::open(&file), "extraction failed");
                             let tar = GzDecoder::new(tar_gz);
                             let mut archive = Archive::new(tar);
 
-                            for entry in unwrap_or_return!(index, archive.entries()) {
-                                let mut file = unwrap_or_return!(index, entry);
-                                unwrap_or_return!(index, file.unpack_in(&extraction_dir));
+                            for entry in
+                                unwrap_or_return!(index, archive.entries(), "extraction failed")
+                            {
+                                let mut file = unwrap_or_return!(index, entry, "extraction failed");
+                                unwrap_or_return!(
+                                    index,
+                                    file.unpack_in(&extraction_dir),
+                                    "extraction failed"
+                                );
                             }
 
                             Some((
@@ -323,13 +644,20 @@ where
                         }
                         #[cfg(target_os = "linux")]
                         DownloadedArchive::TarBz { extraction_dir } => {
-                            let tar_bz2 = unwrap_or_return!(index, File::open(&file));
+                            let tar_bz2 =
+                                unwrap_or_return!(index, File::open(&file), "extraction failed");
                             let tar = BzDecoder::new(tar_bz2);
                             let mut archive = Archive::new(tar);
 
-                            for entry in unwrap_or_return!(index, archive.entries()) {
-                                let mut file = unwrap_or_return!(index, entry);
-                                unwrap_or_return!(index, file.unpack_in(&extraction_dir));
+                            for entry in
+                                unwrap_or_return!(index, archive.entries(), "extraction failed")
+                            {
+                                let mut file = unwrap_or_return!(index, entry, "extraction failed");
+                                unwrap_or_return!(
+                                    index,
+                                    file.unpack_in(&extraction_dir),
+                                    "extraction failed"
+                                );
                             }
 
                             Some((
@@ -337,6 +665,19 @@ where
                                 State::FinishedExtracting { index, package },
                             ))
                         }
+                        #[cfg(target_os = "macos")]
+                        DownloadedArchive::Dmg { extraction_dir } => {
+                            unwrap_or_return!(
+                                index,
+                                extract_dmg(&file, &extraction_dir.join("inner")),
+                                "extraction failed"
+                            );
+
+                            Some((
+                                (index, Progress::FinishedExtracting),
+                                State::FinishedExtracting { index, package },
+                            ))
+                        }
                         #[cfg(target_os = "windows")]
                         DownloadedArchive::Zip {
                             mut archive,
@@ -346,25 +687,44 @@ where
                         } => {
                             {
                                 // TODO: Show progress with bytes to avoid looking stuck.
-                                let mut entry: ZipFile<'_> =
-                                    unwrap_or_return!(index, archive.by_index(extracted as usize));
+                                let mut entry: ZipFile<'_> = unwrap_or_return!(
+                                    index,
+                                    archive.by_index(extracted as usize),
+                                    "extraction failed"
+                                );
                                 let (_, path) = entry.name().split_once('/').unwrap();
                                 let entry_path = format!("inner/{}", path);
 
                                 if entry.is_dir() {
                                     let extracted_dir_path = extraction_dir.join(entry_path);
-                                    unwrap_or_return!(index, create_dir_all(extracted_dir_path));
+                                    unwrap_or_return!(
+                                        index,
+                                        create_dir_all(extracted_dir_path),
+                                        "extraction failed"
+                                    );
                                 } else if entry.is_file() {
                                     let mut buffer: Vec<u8> = Vec::new();
-                                    unwrap_or_return!(index, entry.read_to_end(&mut buffer));
+                                    unwrap_or_return!(
+                                        index,
+                                        entry.read_to_end(&mut buffer),
+                                        "extraction failed"
+                                    );
                                     let extracted_file_path = extraction_dir.join(entry_path);
                                     unwrap_or_return!(
                                         index,
-                                        create_dir_all(extracted_file_path.parent().unwrap())
+                                        create_dir_all(extracted_file_path.parent().unwrap()),
+                                        "extraction failed"
+                                    );
+                                    let mut file = unwrap_or_return!(
+                                        index,
+                                        File::create(extracted_file_path),
+                                        "extraction failed"
+                                    );
+                                    unwrap_or_return!(
+                                        index,
+                                        file.write(&buffer),
+                                        "extraction failed"
                                     );
-                                    let mut file =
-                                        unwrap_or_return!(index, File::create(extracted_file_path));
-                                    unwrap_or_return!(index, file.write(&buffer));
                                 }
                             }
 
@@ -396,7 +756,7 @@ where
                             }
                         }
                     },
-                    State::FinishedExtracting { index, package } => {
+                    State::FinishedExtracting { index, mut package } => {
                         #[cfg(target_os = "linux")]
                         let extracted_path = glob(&format!(
                             "{}/*",
@@ -411,24 +771,59 @@ where
                         .unwrap()
                         .unwrap();
 
-                        #[cfg(target_os = "windows")]
+                        #[cfg(any(target_os = "windows", target_os = "macos"))]
                         let extracted_path =
                             get_setting().cache_dir.join(&package.name).join("inner");
 
+                        let templated_name = get_setting()
+                            .naming_template
+                            .as_deref()
+                            .and_then(|template| package.templated_name(template));
+                        if let Some(name) = templated_name {
+                            if get_setting().packages_dir.join(&name).exists() {
+                                log::warn!(
+                                    "Keeping the name of {} since {} is already taken.",
+                                    package.name,
+                                    name
+                                );
+                            } else {
+                                package.name = name;
+                            }
+                        }
+
                         let mut package_path = get_setting().packages_dir.join(&package.name);
 
                         // TODO: Fix moving directories across filesystems.
                         // Can probably use the `fs_extra` crate, which I'm already depending on.
                         // Actually, I tried it but that crate fails to move links.
-                        unwrap_or_return!(index, rename(extracted_path, &package_path));
+                        unwrap_or_return!(
+                            index,
+                            rename(extracted_path, &package_path),
+                            "couldn't move the package into place"
+                        );
+
+                        // Not having a manifest only means the integrity check can't look
+                        // past the executable, so it's no reason to fail the installation.
+                        if let Err(e) = package.save_manifest() {
+                            log::warn!("Couldn't save the manifest of {}: {}", package.name, e);
+                        }
 
                         package_path.push("package_info.ron");
-                        let mut file = unwrap_or_return!(index, File::create(&package_path));
+                        let mut file = unwrap_or_return!(
+                            index,
+                            File::create(&package_path),
+                            "couldn't save the package info"
+                        );
                         let package_info = unwrap_or_return!(
                             index,
-                            to_string_pretty(&package, PrettyConfig::new())
+                            to_string_pretty(&package, PrettyConfig::new()),
+                            "couldn't save the package info"
+                        );
+                        unwrap_or_return!(
+                            index,
+                            file.write_all(package_info.as_bytes()),
+                            "couldn't save the package info"
                         );
-                        unwrap_or_return!(index, file.write_all(package_info.as_bytes()));
 
                         Some((
                             (index, Progress::FinishedInstalling),
@@ -436,7 +831,7 @@ where
                         ))
                     }
                     State::FinishedInstalling => {
-                        let _: () = iced::futures::future::pending().await;
+                        let _: () = futures::future::pending().await;
 
                         None
                     }
@@ -446,14 +841,160 @@ where
     }
 }
 
+#[cfg(feature = "gui")]
+impl<H, I> subscription::Recipe<H, I> for Install
+where
+    H: Hasher,
+{
+    type Output = (usize, Progress);
+
+    fn hash(&self, state: &mut H) {
+        std::any::TypeId::of::<Self>().hash(state);
+        self.package.name.hash(state);
+        self.package.date.hash(state);
+    }
+
+    fn stream(self: Box<Self>, _input: BoxStream<'static, I>) -> BoxStream<'static, Self::Output> {
+        self.into_stream()
+    }
+}
+
+/// Adds the package to the ones being installed, unless it's already there because install was
+/// clicked again before the first availability check came back. Returns whether it was added.
+#[cfg(feature = "gui")]
+pub fn queue(installing: &mut Vec<(Package, CancellationToken)>, package: Package) -> bool {
+    if installing
+        .iter()
+        .any(|(a_package, _)| a_package == &package)
+    {
+        return false;
+    }
+    installing.push((package, CancellationToken::new()));
+    true
+}
+
+/// Requests the package's archive, going through the mirrors in order when the package's own
+/// server can't be reached. The first one that answers is used.
+async fn send_download_request(url: &str, resume_from: u64) -> reqwest::Result<Response> {
+    let request = |url: &str| {
+        let request = download_client().get(url);
+        if resume_from > 0 {
+            request.header(RANGE, format!("bytes={}-", resume_from))
+        } else {
+            request
+        }
+    };
+
+    let mut result = request(url).send().await;
+    let mirror_urls = get_setting().mirror_urls.clone();
+    for mirror in mirror_urls {
+        match &result {
+            Err(e) if e.is_connect() || e.is_timeout() => (),
+            _ => break,
+        }
+        if let Some(mirrored) = mirror_url(url, &mirror) {
+            log::info!("Couldn't reach {}, trying {}.", url, mirrored);
+            result = request(&mirrored).send().await;
+        }
+    }
+    result
+}
+
+/// The name the archive is saved under in the cache, going by the path of the URL alone.
+fn archive_file_name(url: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let file_name = url.path_segments()?.next_back()?;
+    (!file_name.is_empty()).then(|| file_name.to_string())
+}
+
+fn read_timeout() -> Duration {
+    Duration::from_secs(get_setting().read_timeout_secs)
+}
+
+/// Removes a partially downloaded or corrupted archive.
+async fn clean_up(file: &Path) {
+    if file.exists() {
+        if let Err(e) = remove_file(file).await {
+            log::error!("Couldn't remove {:?}: {}", file, e);
+        }
+    }
+}
+
+/// Looks for the SHA256 checksum of a package, either in a file next to the archive like the
+/// builder has or in the list covering every archive of a stable release.
+async fn fetch_checksum(package: &Package) -> Option<String> {
+    let file_name = package.url.rsplit('/').next()?;
+    let directory = package.url.strip_suffix(file_name)?;
+    let urls = [
+        format!("{}.sha256", package.url),
+        format!("{}blender-{}.sha256", directory, package.version),
+    ];
+
+    for url in urls {
+        let text = match http_client().get(&url).send().await {
+            Ok(response) if response.status().is_success() => match response.text().await {
+                Ok(text) => text,
+                Err(_) => continue,
+            },
+            _ => continue,
+        };
+
+        for line in text.lines() {
+            let mut parts = line.split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(hash), None) => return Some(hash.to_string()),
+                (Some(hash), Some(name)) if name.trim_start_matches('*') == file_name => {
+                    return Some(hash.to_string())
+                }
+                _ => (),
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns right away unless downloads are paused, in which case it waits for them to resume.
+async fn wait_for_resume(paused: &mut watch::Receiver<bool>) {
+    while *paused.borrow() {
+        if paused.changed().await.is_err() {
+            break;
+        }
+    }
+}
+
+fn hash_file(file: &Path) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(file)?, &mut hasher)?;
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
 #[derive(Clone, Debug)]
 pub enum Progress {
-    Started,
-    DownloadProgress(f32),
+    /// Carries the size of the download, unless the server didn't say.
+    Started(Option<u64>),
+    DownloadProgress {
+        /// Stays at zero when the size of the download isn't known.
+        progress: f32,
+        downloaded: u64,
+        bytes_per_sec: f64,
+        eta_secs: Option<u64>,
+        /// Bytes received since the previous update.
+        chunk_size: u64,
+    },
     FinishedDownloading,
+    ChecksumVerified,
+    ChecksumUnavailable,
+    ChecksumFailed(String),
     ExtractionProgress(f32),
     FinishedExtracting,
     FinishedInstalling,
+    Cancelled,
     Errored(String),
 }
 
@@ -461,13 +1002,25 @@ enum State {
     ReadyToInstall {
         index: usize,
         package: Package,
+        cancellation_token: CancellationToken,
+        download_slots: DownloadSlots,
+        paused: watch::Receiver<bool>,
     },
     Downloading {
         response: reqwest::Response,
         file: PathBuf,
         destination: tokio::fs::File,
-        total: u64,
+        total: Option<u64>,
         downloaded: u64,
+        samples: VecDeque<(Instant, u64)>,
+        index: usize,
+        package: Package,
+        cancellation_token: CancellationToken,
+        download_slot: DownloadSlot,
+        paused: watch::Receiver<bool>,
+    },
+    Verifying {
+        file: PathBuf,
         index: usize,
         package: Package,
     },
@@ -503,4 +1056,86 @@ enum DownloadedArchive {
         total: u64,
         extracted: u64,
     },
+    #[cfg(target_os = "macos")]
+    Dmg { extraction_dir: PathBuf },
+}
+
+#[cfg(all(test, feature = "gui"))]
+mod tests {
+    use super::*;
+    use versions::Versioning;
+
+    #[test]
+    fn queue_ignores_a_second_install_of_the_same_package() {
+        let package = Package {
+            name: String::from("blender-3.2.0"),
+            ..Default::default()
+        };
+        let mut installing = Vec::new();
+
+        assert!(queue(&mut installing, package.clone()));
+        assert!(!queue(&mut installing, package));
+        assert_eq!(installing.len(), 1);
+    }
+
+    #[test]
+    fn queue_adds_different_packages() {
+        let first = Package {
+            name: String::from("blender-3.2.0"),
+            version: Versioning::new("3.2.0").unwrap(),
+            ..Default::default()
+        };
+        let second = Package {
+            name: String::from("blender-3.3.0"),
+            version: Versioning::new("3.3.0").unwrap(),
+            ..Default::default()
+        };
+        let mut installing = Vec::new();
+
+        assert!(queue(&mut installing, first));
+        assert!(queue(&mut installing, second));
+        assert_eq!(installing.len(), 2);
+    }
+
+    #[test]
+    fn archive_file_name_ignores_query_and_fragment() {
+        assert_eq!(
+            archive_file_name(
+                "https://example.com/builds/blender-3.2.0-linux-x64.tar.xz?token=1#a"
+            ),
+            Some(String::from("blender-3.2.0-linux-x64.tar.xz"))
+        );
+        assert_eq!(archive_file_name("https://example.com/builds/"), None);
+        assert_eq!(archive_file_name("not a url"), None);
+    }
+
+    #[test]
+    fn lowering_and_raising_the_limit_keeps_every_slot() {
+        let download_slots = DownloadSlots::new(8);
+        download_slots.resize(8, 1);
+        download_slots.resize(1, 8);
+        assert_eq!(download_slots.semaphore.available_permits(), 8);
+    }
+
+    #[tokio::test]
+    async fn slots_in_use_are_taken_away_once_freed() {
+        let download_slots = DownloadSlots::new(2);
+        let first = download_slots.acquire().await;
+        let second = download_slots.acquire().await;
+        download_slots.resize(2, 1);
+        drop(first);
+        assert_eq!(download_slots.semaphore.available_permits(), 0);
+        drop(second);
+        assert_eq!(download_slots.semaphore.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn raising_the_limit_gives_back_slots_still_in_use() {
+        let download_slots = DownloadSlots::new(2);
+        let slot = download_slots.acquire().await;
+        download_slots.resize(2, 0);
+        download_slots.resize(0, 2);
+        drop(slot);
+        assert_eq!(download_slots.semaphore.available_permits(), 2);
+    }
 }
@@ -1,49 +1,124 @@
 use super::{
-    extra::{BuildTypeSettings, Choice, DiskSpace, Location},
-    package::PackageMessage,
+    compact::compact_packages,
+    controls::{Controls, FetchProgress},
+    extra::{BuildTypeSettings, Choice, DiskSpace, GuiState, KeepCount, Location, Notification},
+    filters::{DateRangePreset, Filters, PackageGroup, DATE_FORMAT},
+    install,
+    modal::ConfirmModal,
+    monitor::monitor_at,
+    package::{PackageMessage, APPROXIMATE_PACKAGE_HEIGHT},
+    quick_switch::{key_index, quick_switch_packages},
     sort_by::SortBy,
     style::Theme,
-    tabs::recent_files::{RecentFile, RecentFileMessage},
+    tabs::{
+        packages::shown_packages,
+        recent_files::{RecentFile, RecentFileMessage},
+    },
+    wizard::WizardState,
     Gui, Tab,
 };
+#[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+use crate::helpers::tray::TrayEventKind;
 use crate::{
-    helpers::open_blender,
-    package::{Build, Package},
+    file_association,
+    helpers::{backup_blender_prefs, open_blender, test_mirror},
+    logger::{clear_logs, get_logs},
+    package::{
+        export_package_list, import_package_list, Build, BuildHealth, Change, IntegrityStatus,
+        Package, PackageState, PackageStatus,
+    },
     releases::{
-        daily_archive::DailyArchive, daily_latest::DailyLatest,
-        experimental_archive::ExperimentalArchive, experimental_latest::ExperimentalLatest,
-        lts::Lts, patch_archive::PatchArchive, patch_latest::PatchLatest,
-        stable_archive::StableArchive, stable_latest::StableLatest, ReleaseType,
+        commits::fetch_commits,
+        daily_archive::DailyArchive,
+        daily_latest::DailyLatest,
+        experimental_archive::ExperimentalArchive,
+        experimental_latest::ExperimentalLatest,
+        launcher::{LauncherRelease, LauncherUpdate},
+        lts::Lts,
+        patch_archive::PatchArchive,
+        patch_latest::PatchLatest,
+        stable_archive::StableArchive,
+        stable_latest::StableLatest,
+        ReleaseType,
     },
     settings::{
-        get_setting, save_settings, set_setting, ModifierKey, FETCHING, INSTALLING, PROJECT_DIRS,
+        get_setting, DownloadStats, FontFamily, KeyAction, KeyCombo, Keybindings, LaunchProfile,
+        ModifierKey, Settings, SettingsService, UpdateChannel, CAN_CONNECT, FETCHING, INSTALLING,
+        LOADING_STAGE, NO_PROFILE, PROJECT_DIRS,
     },
 };
-use iced::Command;
-use native_dialog::{FileDialog, MessageDialog, MessageType};
+use chrono::NaiveDate;
+use iced::{clipboard, window, Command};
+use native_dialog::FileDialog;
 use self_update::update::Release;
 use std::{
-    fs::{create_dir_all, remove_dir_all},
-    path::PathBuf,
+    collections::HashMap,
+    fs::{create_dir_all, read_dir, remove_dir_all},
+    mem,
+    path::{Path, PathBuf},
     process::exit,
     sync::atomic::Ordering,
+    time::{Duration, Instant},
 };
+use url::Url;
+
+/// How long notifications about background events stay on screen.
+const NOTIFICATION_DURATION: Duration = Duration::from_secs(5);
 
 #[derive(Clone, Debug)]
 pub enum GuiMessage {
     PackageMessage((usize, PackageMessage)),
     RecentFileMessage((String, RecentFileMessage)),
-    Bookmark(Package),
-    CheckAvailability(Option<(bool, bool, Package)>),
+    ToggleFavourite(Package),
+    NoteSaved(Package),
+    CopyPackageInfo(Package),
+    TagsSaved(Package),
+    LaunchArgsSaved(Package),
+    EnvVarsSaved(Package),
+    /// Sent once a sandboxed Blender exits and its config directory is cleaned up.
+    SandboxClosed(Result<String, String>),
+    TogglePin(Package),
+    WizardStep(WizardState),
+    /// Closes the setup wizard, checking for updates if true.
+    WizardFinished(bool),
+    IntegrityResult(Package, IntegrityStatus),
+    /// Whether the package could be launched, with what Blender printed or why it failed.
+    TestLaunchResult(Package, bool, String),
+    CheckAvailability(Result<(bool, bool, Package), String>),
     InstallPackage(Package),
     CancelInstall(Package),
+    ToggleDownloadsPaused,
+    InstallationStopped(Package),
     PackageInstalled(Package),
+    /// Backs up the Blender config of the old default package when switching to the new one.
+    BackupBlenderPrefs(Package, Package),
+    BlenderPrefsBackedUp(Result<Option<PathBuf>, String>),
     PackageRemoved(Package),
+    ConfirmRemovingDefault(Package),
+    ShowConfirmModal(Box<ConfirmModal>),
+    ConfirmationAnswered(bool),
     OpenBlender(String),
     OpenBlenderWithFile(String),
+    /// A package asked to be installed from outside, like through the D-Bus service.
+    #[cfg(all(target_os = "linux", feature = "dbus"))]
+    InstallRequested(String),
+    /// Something was clicked on in the system tray.
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    TrayEvent(TrayEventKind),
+    /// The window's close button was pressed, which hides it to the tray when there's one.
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    CloseRequested,
     SelectFile,
+    RecentFileSelected(String),
     OpenBrowser(String),
     CheckForUpdates,
+    InstallAllUpdates,
+    InstallAllUpdatesConfirmed,
+    /// Sent every minute to check for updates once `minutes_between_updates` have passed.
+    ScheduledCheckTick,
+    SpinnerTick,
+    /// Sent every second while rate limited, until fetching is allowed again.
+    RateLimitTick,
     UpdatesChecked(
         (
             bool,
@@ -89,7 +164,7 @@ pub enum GuiMessage {
     FetchLts,
     LtsFetched((bool, Lts)),
     FilterUpdatesChanged(bool),
-    FilterBookmarksChanged(bool),
+    FilterFavouritesChanged(bool),
     FilterInstalledChanged(bool),
     FilterAllChanged(bool),
     FilterDailyLatestChanged(bool),
@@ -101,34 +176,163 @@ pub enum GuiMessage {
     FilterStableLatestChanged(bool),
     FilterStableArchiveChanged(bool),
     FilterLtsChanged(bool),
+    FilterMinVersionChanged(String),
+    FilterMaxVersionChanged(String),
+    FilterDateFromChanged(String),
+    FilterDateToChanged(String),
+    DateRangePresetPicked(DateRangePreset),
     SortingChanged(SortBy),
+    SizesMeasured(HashMap<String, u64>),
+    ExternalScanned(Vec<Package>),
+    CheckBuildHealth(u64),
+    BuildHealthFetched(HashMap<String, BuildHealth>),
+    BuildbotApiUrlChanged(String),
+    NewMirrorChanged(String),
+    MirrorAdded,
+    MirrorRemoved(usize),
+    /// Asks for the directory of a locally compiled build to add.
+    PickLocalBuild,
+    AddLocalBuild(PathBuf),
+    LocalBuildAdded(Result<Package, String>),
+    /// Stops listing a local build, leaving its files alone.
+    ForgetLocalBuild(String),
+    LocalBuildNotRemovable(Package),
+    CustomPackageUrlChanged(String),
+    CustomPackageNameChanged(String),
+    CustomPackageVersionChanged(String),
+    /// Checks the URL of the custom package being added before listing it.
+    AddCustomPackage,
+    CustomPackageChecked(Result<Package, String>),
+    /// Stops listing the custom package with the URL.
+    ForgetCustomPackage(String),
+    TestMirror(String),
+    MirrorTested((String, Result<Duration, String>)),
+    NamingTemplateChanged(String),
+    CopyInfoTemplateChanged(String),
+    ProfileSelected(String),
+    NewProfileChanged(String),
+    ProfileCreated,
+    ProfileDeleted(usize),
+    ProfileArgsChanged(usize, String),
+    ProfileEnvChanged(usize, String),
+    SearchChanged(String),
     TabChanged(Tab),
     CalculateDiskSpace(DiskSpace),
     BypassLauncher(Choice),
     ModifierKey(ModifierKey),
     UseLatestAsDefault(Choice),
+    BackupPrefsOnDefaultChange(Choice),
+    HidePreRelease(Choice),
     CheckUpdatesAtLaunch(Choice),
     MinutesBetweenUpdatesChanged(i64),
+    MaxConcurrentDownloadsChanged(u8),
+    ResumeDownloads(Choice),
+    ProxyUrlChanged(String),
+    FontFamilyChanged(FontFamily),
+    CustomFontChanged(String),
+    ConnectTimeoutChanged(u8),
+    ReadTimeoutChanged(u8),
+    ProxyBypassChanged(String),
+    UpdateChannelChanged(UpdateChannel),
     UpdateDailyLatest(Choice),
     UpdateExperimentalLatest(Choice),
     UpdatePatchLatest(Choice),
     UpdateStableLatest(Choice),
     UpdateLts(Choice),
+    AutoInstallUpdates(Choice),
+    AutoInstallDaily(bool),
+    AutoInstallExperimental(bool),
+    AutoInstallPatch(bool),
+    AutoInstallStable(bool),
+    AutoInstallLts(bool),
+    KeepCountDaily(KeepCount),
+    KeepCountExperimental(KeepCount),
+    KeepCountPatch(KeepCount),
+    KeepCountStable(KeepCount),
+    KeepCountLts(KeepCount),
+    AutoCleanupDaysChanged(u16),
+    ResetStats,
     ThemeChanged(Theme),
     ChangeLocation(Location),
     ResetLocation(Location),
+    MigratePackages(PathBuf),
+    PackageMigrated(Result<(), String>),
     RemoveDatabases(BuildTypeSettings),
     RemovePackages(BuildTypeSettings),
     RemoveCache,
     SelfUpdater(Choice),
     CheckSelfUpdatesAtLaunch(Choice),
     FetchSelfReleases,
-    PopulateSelfReleases(Option<Vec<Release>>),
+    PopulateSelfReleases(Result<Option<Vec<Release>>, String>),
     PickListVersionSelected(String),
     ChangeVersion,
-    VersionChanged(()),
+    VersionChanged(Result<(), String>),
+    DismissError,
+    LogsCopied,
+    LogsCleared,
+    /// A key combination, and whether a widget like a text input already used it.
+    KeyPressed(KeyCombo, bool),
+    RebindKey(KeyAction),
+    ResetKeybindings,
+    ExportSettings,
+    RegisterFileAssociation,
+    UnregisterFileAssociation,
+    ImportSettings,
+    ResetPackageDataToggled(bool),
+    ConfirmResetSettings,
+    ResetSettings,
+    ExportPackageList,
+    ImportPackageList,
+    ShowNotification(String, Duration),
+    PruneNotifications,
+    NewGroupChanged(String),
+    GroupCreated,
+    GroupRenameStarted(String),
+    GroupRenameChanged(String),
+    GroupRenamed,
+    GroupDeleted(String),
+    GroupFilterChanged(String, bool),
+    TagFilterChanged(String, bool),
+    GroupModeToggled(bool),
+    GroupSelectionChanged(Package, bool),
+    AddToGroup(String),
+    RemoveFromGroup(String),
+    QuickSwitchLaunch(String),
+    QuickSwitchDismissed,
+    LeaveCompactMode,
+    MultiSelectToggled,
+    PackageSelectionChanged(Package, bool),
+    SelectAllPackages(bool),
+    InstallSelected,
+    ConfirmRemovingSelected,
+    RemoveSelected,
+    ExportSelectedPackages,
+    CompareModeToggled(bool),
+    CompareSelectionChanged(Package, bool),
+    ComparePackages(Package, Package),
+    CloseComparison,
+    ChangelogRequested(Package),
+    ShowChangelog(Package, Package),
+    /// The name of the update the commits were fetched for, and the commits.
+    ChangelogFetched(String, Result<Vec<Change>, String>),
+    CloseChangelog,
     CheckConnection,
     ConnectionChecked(()),
+    PollConnection,
+    ConnectionStatusChanged(bool),
+    WindowResized(u32, u32),
+    /// How far down the list of packages is scrolled, from 0 to 1.
+    PackagesScrolled(f32),
+    /// How far down the settings are scrolled, from 0 to 1.
+    SettingsScrolled(f32),
+    WindowMoved(i32, i32),
+    SaveWindowGeometry(Instant),
+    FileHovered,
+    FileDropped(PathBuf),
+    FilesHoveredLeft,
+    LauncherUpdateAvailable(Option<LauncherRelease>),
+    UpdateLauncher,
+    LauncherUpdated(Result<(), String>),
 }
 
 impl Gui {
@@ -143,35 +347,178 @@ impl Gui {
             GuiMessage::RecentFileMessage((file, recent_file_message)) => match recent_file_message
             {
                 RecentFileMessage::OpenWithLastBlender(blender) => {
-                    self.file_path = Some(file);
+                    self.file_paths = vec![file];
                     Command::perform(Gui::pass_string(blender), GuiMessage::OpenBlenderWithFile)
                 }
                 RecentFileMessage::OpenWithDefaultBlender => {
-                    self.file_path = Some(file);
+                    self.file_paths = vec![file];
                     Command::perform(
                         Gui::pass_string(get_setting().default_package.clone().unwrap().name),
                         GuiMessage::OpenBlenderWithFile,
                     )
                 }
                 RecentFileMessage::Select => {
-                    self.file_path = Some(file);
+                    self.file_paths = vec![file];
                     Command::none()
                 }
                 RecentFileMessage::Remove => {
-                    set_setting().recent_files.remove(&PathBuf::from(file));
-                    save_settings();
+                    SettingsService::update(|settings| {
+                        settings.recent_files.remove(&PathBuf::from(file))
+                    });
                     self.recent_files = get_setting().recent_files.to_vec();
                     Command::none()
                 }
             },
-            GuiMessage::Bookmark(package) => {
-                set_setting().bookmarks.update(package.name);
-                set_setting().bookmarks.clean(&self.packages);
-                save_settings();
+            GuiMessage::ToggleFavourite(package) => {
+                SettingsService::update(|settings| {
+                    settings.favourite_packages.toggle(package.name);
+                    settings.favourite_packages.clean(&self.packages);
+                });
+                Command::none()
+            }
+            GuiMessage::NoteSaved(package) => {
+                if let Some(installed_package) = self
+                    .releases
+                    .installed
+                    .iter_mut()
+                    .find(|installed_package| **installed_package == package)
+                {
+                    installed_package.notes = package.notes;
+                    installed_package.save_info();
+                }
+                Command::none()
+            }
+            GuiMessage::CopyPackageInfo(package) => {
+                let info = package.fill_template(&get_setting().copy_info_template);
+                log::info!("Copied the info of {}.", package.name);
+                Command::batch([
+                    clipboard::write(info.clone()),
+                    self.update_message(GuiMessage::ShowNotification(
+                        format!("Copied \"{}\" to the clipboard.", info),
+                        NOTIFICATION_DURATION,
+                    )),
+                ])
+            }
+            GuiMessage::TagsSaved(package) => {
+                if let Some(installed_package) = self
+                    .releases
+                    .installed
+                    .iter_mut()
+                    .find(|installed_package| **installed_package == package)
+                {
+                    installed_package.tags = package.tags.clone();
+                    installed_package.save_info();
+                }
+                SettingsService::update(|settings| {
+                    for tag in package.tags {
+                        if !settings.known_tags.contains(&tag) {
+                            settings.known_tags.push(tag);
+                        }
+                    }
+                    settings.known_tags.sort();
+                });
+                Command::none()
+            }
+            GuiMessage::SandboxClosed(result) => {
+                match result {
+                    Ok(package) => log::info!("Removed the sandboxed config of {}.", package),
+                    Err(e) => {
+                        log::error!("Sandboxed Blender: {}", e);
+                        self.state.error = Some(e);
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::EnvVarsSaved(package) => {
+                if let Some(installed_package) = self
+                    .releases
+                    .installed
+                    .iter_mut()
+                    .find(|installed_package| **installed_package == package)
+                {
+                    installed_package.env_vars = package.env_vars;
+                    installed_package.save_info();
+                }
+                Command::none()
+            }
+            GuiMessage::WizardStep(wizard) => {
+                self.wizard = Some(wizard);
+                Command::none()
+            }
+            GuiMessage::WizardFinished(check_for_updates) => {
+                self.wizard = None;
+                SettingsService::update(|settings| settings.first_run = false);
+                if check_for_updates {
+                    self.update_message(GuiMessage::CheckForUpdates)
+                } else {
+                    Command::none()
+                }
+            }
+            GuiMessage::TogglePin(package) => {
+                if !self.pinned.remove(&package.name) {
+                    self.pinned.insert(package.name);
+                }
+                Command::none()
+            }
+            GuiMessage::LaunchArgsSaved(package) => {
+                if let Some(installed_package) = self
+                    .releases
+                    .installed
+                    .iter_mut()
+                    .find(|installed_package| **installed_package == package)
+                {
+                    installed_package.launch_args = package.launch_args;
+                    installed_package.save_info();
+                }
                 Command::none()
             }
-            GuiMessage::CheckAvailability(option) => match option {
-                Some((available, for_install, package)) => {
+            GuiMessage::IntegrityResult(package, status) => {
+                let message = match &status {
+                    IntegrityStatus::Ok => format!("{} looks intact.", package.name),
+                    IntegrityStatus::ExecutableMissing => {
+                        format!("The Blender executable of {} is missing.", package.name)
+                    }
+                    IntegrityStatus::Corrupted(problems) => {
+                        for problem in problems {
+                            log::warn!("{}: {}", package.name, problem);
+                        }
+                        format!(
+                            "Found {} problem(s) with {}, see the logs.",
+                            problems.len(),
+                            package.name
+                        )
+                    }
+                };
+                match status {
+                    IntegrityStatus::Ok => log::info!("{}", message),
+                    _ => log::warn!("{}", message),
+                }
+                self.state.integrity.insert(package.name, status);
+                self.sync();
+                self.update_message(GuiMessage::ShowNotification(message, NOTIFICATION_DURATION))
+            }
+            GuiMessage::TestLaunchResult(package, success, output) => {
+                self.state
+                    .test_launches
+                    .insert(package.name.clone(), success);
+                self.sync();
+                if success {
+                    log::info!("Test launch of {} went through: {}", package.name, output);
+                    self.update_message(GuiMessage::ShowNotification(
+                        format!("{} launches fine.", package.name),
+                        NOTIFICATION_DURATION,
+                    ))
+                } else {
+                    log::warn!("Test launch of {} failed: {}", package.name, output);
+                    self.state.error = Some(format!(
+                        "Test launch of {} failed: {}",
+                        package.name, output
+                    ));
+                    Command::none()
+                }
+            }
+            GuiMessage::CheckAvailability(result) => match result {
+                Ok((available, for_install, package)) => {
                     if available && for_install {
                         Command::perform(Gui::pass_package(package), GuiMessage::InstallPackage)
                     } else if !for_install {
@@ -259,7 +606,7 @@ impl Gui {
                                 self.releases.stable_archive.remove(index);
                                 self.releases.stable_archive.save();
                             }
-                            Build::Lts => {
+                            Build::Lts(_) => {
                                 let index = self
                                     .releases
                                     .lts
@@ -270,128 +617,377 @@ impl Gui {
                                 self.releases.lts.save();
                             }
                         }
+                        self.sync();
                         if for_install {
-                            let message =
-                                format!("Package '{}' is no longer available.", package.name);
-                            if MessageDialog::new()
-                                .set_type(MessageType::Info)
-                                .set_title("Ablavema")
-                                .set_text(&message)
-                                .show_alert()
-                                .is_err()
-                            {
-                                // TODO: Show a tooltip if dependencies not found.
-                                // Or just spawn a tooltip in the first place.
-                                #[cfg(target_os = "linux")]
-                                println!(
-                                    "Error: {}\nProbably need to install 'zenity' or 'kdialog' for a graphical dialog.",
-                                    &message
-                                );
-                            }
+                            self.update_message(GuiMessage::ShowNotification(
+                                format!("Package '{}' is no longer available.", package.name),
+                                NOTIFICATION_DURATION,
+                            ))
+                        } else {
+                            Command::none()
                         }
-                        self.sync();
-                        Command::none()
                     }
                 }
-                None => {
+                Err(e) => {
+                    log::error!("{}", e);
+                    self.state.error = Some(e);
                     self.sync();
                     Command::none()
                 }
             },
             GuiMessage::InstallPackage(package) => {
-                if self.installing.is_empty() {
-                    INSTALLING.store(true, Ordering::Relaxed);
+                let (index, name) = (package.index, package.name.clone());
+                if !install::queue(&mut self.installing, package) {
+                    return self.update_message(GuiMessage::ShowNotification(
+                        format!("Already installing {}.", name),
+                        NOTIFICATION_DURATION,
+                    ));
+                }
+                INSTALLING.store(true, Ordering::Relaxed);
+                if let Some(a_package) = self.packages.get_mut(index) {
+                    a_package.state = PackageState::Queued;
                 }
-                self.installing.push(package);
+                Command::none()
+            }
+            GuiMessage::ToggleDownloadsPaused => {
+                self.downloads_paused = !self.downloads_paused;
+                self.pause_downloads.send_replace(self.downloads_paused);
+                log::info!(
+                    "{} downloads.",
+                    if self.downloads_paused {
+                        "Paused"
+                    } else {
+                        "Resumed"
+                    }
+                );
                 Command::none()
             }
             GuiMessage::CancelInstall(package) => {
-                let index = self
+                if let Some((_, cancellation_token)) = self
                     .installing
                     .iter()
-                    .enumerate()
-                    .find(|(_, a_package)| *a_package == &package)
-                    .unwrap()
-                    .0;
-                self.installing.remove(index);
+                    .find(|(a_package, _)| a_package == &package)
+                {
+                    cancellation_token.cancel();
+                }
+                Command::none()
+            }
+            GuiMessage::InstallationStopped(package) => {
+                if let Some(index) = self
+                    .installing
+                    .iter()
+                    .position(|(a_package, _)| a_package == &package)
+                {
+                    self.installing.remove(index);
+                }
                 if self.installing.is_empty() {
                     INSTALLING.store(false, Ordering::Relaxed);
                 }
                 Command::none()
             }
             GuiMessage::PackageInstalled(package) => {
-                let index = self
+                let index = match self
                     .installing
                     .iter()
-                    .enumerate()
-                    .find(|(_, a_package)| *a_package == &package)
-                    .unwrap()
-                    .0;
+                    .position(|(a_package, _)| a_package == &package)
+                {
+                    Some(index) => index,
+                    None => return Command::none(),
+                };
                 self.installing.remove(index);
+                log::info!("Installed {}.", package.name);
+                self.state.integrity.remove(&package.name);
+                self.state.test_launches.remove(&package.name);
+                SettingsService::update(|settings| {
+                    settings.download_stats.total_packages_installed += 1
+                });
+                let notification = GuiMessage::ShowNotification(
+                    if self.state.background_installs.remove(&package.name) {
+                        format!("Installed {} in the background.", package.name)
+                    } else {
+                        format!("Installed {}.", package.name)
+                    },
+                    NOTIFICATION_DURATION,
+                );
+                self.releases.installed.fetch();
+                let default_changed = self.releases.installed.update_default();
+                // An archived build that was just picked shouldn't go right away.
+                let mut keep = self.pinned.clone();
+                keep.insert(package.name.clone());
+                self.releases.installed.remove_old_packages(&keep);
+                let auto_cleanup_days = get_setting().auto_cleanup_days;
+                if let Some(days) = auto_cleanup_days {
+                    self.releases.installed.cleanup_by_age(days, &keep);
+                }
                 self.releases.installed.fetch();
-                self.releases.installed.update_default();
                 self.sync();
                 if self.installing.is_empty() {
                     INSTALLING.store(false, Ordering::Relaxed);
                 }
-                Command::none()
-            }
-            GuiMessage::PackageRemoved(package) => {
-                let default_package_option = get_setting().default_package.clone();
-                if let Some(default_package) = default_package_option {
-                    if default_package == package {
-                        set_setting().default_package = None;
-                        save_settings();
+                let backup = match default_changed {
+                    Some((old_default, new_default))
+                        if get_setting().backup_prefs_on_default_change =>
+                    {
+                        self.update_message(GuiMessage::BackupBlenderPrefs(
+                            old_default,
+                            new_default,
+                        ))
                     }
-                }
+                    _ => Command::none(),
+                };
+                Command::batch([backup, self.update_message(notification)])
+            }
+            GuiMessage::BackupBlenderPrefs(old_default, new_default) => {
+                log::info!(
+                    "Backing up the preferences of {} before switching the default to {}.",
+                    old_default.name,
+                    new_default.name
+                );
                 Command::perform(
-                    Gui::check_availability(false, package),
-                    GuiMessage::CheckAvailability,
+                    backup_blender_prefs(old_default.version),
+                    GuiMessage::BlenderPrefsBackedUp,
                 )
             }
+            GuiMessage::BlenderPrefsBackedUp(result) => match result {
+                Ok(Some(backup_dir)) => {
+                    log::info!("Backed up the preferences to {}.", backup_dir.display());
+                    self.update_message(GuiMessage::ShowNotification(
+                        format!("Backed up the preferences to {}.", backup_dir.display()),
+                        NOTIFICATION_DURATION,
+                    ))
+                }
+                Ok(None) => {
+                    log::info!("There were no preferences to back up.");
+                    Command::none()
+                }
+                Err(e) => {
+                    log::error!("Couldn't back up the preferences: {}", e);
+                    self.state.error = Some(format!("Couldn't back up the preferences: {}", e));
+                    Command::none()
+                }
+            },
+            GuiMessage::PackageRemoved(package) => {
+                log::info!("Removed {}.", package.name);
+                self.state.integrity.remove(&package.name);
+                self.state.test_launches.remove(&package.name);
+                SettingsService::update(|settings| {
+                    settings.download_stats.total_packages_removed += 1
+                });
+                if get_setting().is_any_default_package(&package) {
+                    SettingsService::update(|settings| {
+                        if settings.default_package.as_ref() == Some(&package) {
+                            settings.default_package = None;
+                        }
+                        for profile in &mut settings.profiles {
+                            if profile.default_package.as_ref() == Some(&package) {
+                                profile.default_package = None;
+                            }
+                        }
+                    });
+                }
+                // External packages have nowhere to be checked at.
+                if package.status == PackageStatus::External {
+                    self.sync();
+                    Command::none()
+                } else {
+                    Command::perform(
+                        Gui::check_availability(false, package),
+                        GuiMessage::CheckAvailability,
+                    )
+                }
+            }
+            GuiMessage::ConfirmRemovingDefault(package) => {
+                self.update_message(GuiMessage::ShowConfirmModal(Box::new(ConfirmModal::new(
+                    String::from(
+                        "This is your default package. Uninstalling it will clear the default.",
+                    ),
+                    GuiMessage::PackageMessage((package.index, PackageMessage::RemoveConfirmed)),
+                ))))
+            }
+            GuiMessage::ShowConfirmModal(modal) => {
+                self.state.confirm_modal = Some(*modal);
+                Command::none()
+            }
+            GuiMessage::ConfirmationAnswered(confirmed) => match self.state.confirm_modal.take() {
+                Some(modal) if confirmed => self.update_message(modal.on_confirm),
+                Some(ConfirmModal {
+                    on_cancel: Some(on_cancel),
+                    ..
+                }) => self.update_message(on_cancel),
+                _ => Command::none(),
+            },
             GuiMessage::OpenBlender(package) => {
-                open_blender(package, None);
+                open_blender(package, Vec::new());
                 exit(0);
             }
             GuiMessage::OpenBlenderWithFile(package) => {
-                let file_path = self.file_path.clone().unwrap();
-                let path = PathBuf::from(&file_path);
-                let recent_file = RecentFile::new(path.clone(), package.clone());
-                set_setting().recent_files.insert(path, recent_file);
-                save_settings();
-                open_blender(package, Some(file_path));
+                SettingsService::update(|settings| {
+                    for file_path in &self.file_paths {
+                        let path = PathBuf::from(file_path);
+                        let recent_file = RecentFile::new(path.clone(), package.clone());
+                        settings.recent_files.insert(path, recent_file);
+                    }
+                });
+                open_blender(package, self.file_paths.clone());
                 exit(0);
             }
+            #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+            GuiMessage::TrayEvent(event) => match event {
+                TrayEventKind::Restore => {
+                    self.hidden_to_tray = false;
+                    Command::none()
+                }
+                TrayEventKind::CheckForUpdates => self.update_message(GuiMessage::CheckForUpdates),
+                TrayEventKind::OpenDefaultPackage => {
+                    let default_package = get_setting().active_default_package().cloned();
+                    match default_package {
+                        // Unlike launching from the window, the launcher stays in the tray.
+                        Some(package) => open_blender(package.name, Vec::new()),
+                        None => {
+                            self.hidden_to_tray = false;
+                            self.state.error = Some(String::from("There's no default package."));
+                        }
+                    }
+                    Command::none()
+                }
+                TrayEventKind::Quit => {
+                    self.should_exit = true;
+                    Command::none()
+                }
+            },
+            #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+            GuiMessage::CloseRequested => {
+                if self.tray.is_shown() {
+                    self.hidden_to_tray = true;
+                } else {
+                    self.should_exit = true;
+                }
+                Command::none()
+            }
+            #[cfg(all(target_os = "linux", feature = "dbus"))]
+            GuiMessage::InstallRequested(name) => {
+                match self.packages.iter().find(|package| package.name == name) {
+                    Some(package) if !matches!(package.state, PackageState::Installed) => {
+                        let index = package.index;
+                        self.update_message(GuiMessage::PackageMessage((
+                            index,
+                            PackageMessage::Install,
+                        )))
+                    }
+                    Some(_) => {
+                        log::info!("Asked to install {}, but it's already installed.", name);
+                        Command::none()
+                    }
+                    None => {
+                        log::warn!("Asked to install {}, but no such package was found.", name);
+                        Command::none()
+                    }
+                }
+            }
             GuiMessage::SelectFile => {
-                if let Some(new_file_path) = FileDialog::new()
+                let new_file_paths = FileDialog::new()
                     .add_filter("BLEND archive", &["blend*"])
                     .add_filter("All files", &["*"])
-                    .show_open_single_file()
-                    .unwrap()
-                {
-                    self.file_path = Some(new_file_path.to_str().unwrap().to_string());
+                    .show_open_multiple_file()
+                    .unwrap();
+                if !new_file_paths.is_empty() {
+                    self.file_paths = new_file_paths
+                        .iter()
+                        .map(|file_path| file_path.to_str().unwrap().to_string())
+                        .collect();
                 }
                 Command::none()
             }
+            GuiMessage::RecentFileSelected(file) => {
+                self.file_paths = vec![file];
+                Command::none()
+            }
             GuiMessage::OpenBrowser(url) => {
                 let _ = webbrowser::open(&url);
                 Command::none()
             }
+            GuiMessage::RateLimitTick => {
+                if let Some(retry_after) = self.retry_after {
+                    if Instant::now() >= retry_after {
+                        log::info!("No longer rate limited.");
+                        self.retry_after = None;
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::SpinnerTick => {
+                self.spinner_frame = self.spinner_frame.wrapping_add(1);
+                if self.controls.fetch_progress.is_some() {
+                    self.controls.fetch_progress = Some(FetchProgress::current());
+                }
+                Command::none()
+            }
+            GuiMessage::ScheduledCheckTick => {
+                self.state.elapsed_minutes += 1;
+                if self.state.elapsed_minutes >= get_setting().minutes_between_updates
+                    && CAN_CONNECT.load(Ordering::Relaxed)
+                    && !INSTALLING.load(Ordering::Relaxed)
+                    && !FETCHING.load(Ordering::Relaxed)
+                {
+                    log::info!("Checking for updates on schedule.");
+                    self.update_message(GuiMessage::CheckForUpdates)
+                } else {
+                    Command::none()
+                }
+            }
             GuiMessage::CheckForUpdates => {
+                if self.retry_after.is_some() {
+                    return Command::none();
+                }
+                self.state.elapsed_minutes = 0;
+                self.controls.fetching = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_for_updates(self.releases.take()),
                     GuiMessage::UpdatesChecked,
                 )
             }
+            GuiMessage::InstallAllUpdates => {
+                let updates = self.pending_updates();
+                if updates.is_empty() {
+                    return Command::none();
+                }
+
+                let body = format!(
+                    "About to install {} update{}:\n\n{}",
+                    updates.len(),
+                    if updates.len() > 1 { "s" } else { "" },
+                    updates
+                        .iter()
+                        .map(|package| package.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                );
+                self.update_message(GuiMessage::ShowConfirmModal(Box::new(ConfirmModal {
+                    title: String::from("Install all updates"),
+                    confirm_label: String::from("[#] Install"),
+                    ..ConfirmModal::new(body, GuiMessage::InstallAllUpdatesConfirmed)
+                })))
+            }
+            GuiMessage::InstallAllUpdatesConfirmed => {
+                Command::batch(self.pending_updates().into_iter().map(|package| {
+                    Command::perform(
+                        Gui::check_availability(true, package),
+                        GuiMessage::CheckAvailability,
+                    )
+                }))
+            }
             GuiMessage::UpdatesChecked(tuple) => {
                 self.releases.add_new_packages(tuple);
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchAll => {
                 FETCHING.store(true, Ordering::Relaxed);
+                LOADING_STAGE.store(0, Ordering::Relaxed);
+                self.controls.fetch_progress = Some(FetchProgress::current());
                 Command::perform(
                     Gui::check_all(
                         self.releases.daily_latest.take(),
@@ -428,11 +1024,14 @@ impl Gui {
                 self.releases.stable_latest = stable_latest;
                 self.releases.stable_archive = stable_archive;
                 self.releases.lts = lts;
-                self.sync();
+                self.sync_fetched();
                 FETCHING.store(false, Ordering::Relaxed);
+                self.controls.fetch_progress = None;
+                self.is_loading = false;
                 Command::none()
             }
             GuiMessage::FetchDailyLatest => {
+                self.controls.fetching_daily = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_daily_latest(self.releases.daily_latest.take()),
@@ -441,11 +1040,13 @@ impl Gui {
             }
             GuiMessage::DailyLatestFetched((_, daily_latest)) => {
                 self.releases.daily_latest = daily_latest;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_daily = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchDailyArchive => {
+                self.controls.fetching_daily = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_daily_archive(self.releases.daily_archive.take()),
@@ -454,11 +1055,13 @@ impl Gui {
             }
             GuiMessage::DailyArchiveFetched((_, daily_archive)) => {
                 self.releases.daily_archive = daily_archive;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_daily = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchExperimentalLatest => {
+                self.controls.fetching_experimental = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_experimental_latest(self.releases.experimental_latest.take()),
@@ -467,11 +1070,13 @@ impl Gui {
             }
             GuiMessage::ExperimentalLatestFetched((_, experimental_latest)) => {
                 self.releases.experimental_latest = experimental_latest;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_experimental = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchExperimentalArchive => {
+                self.controls.fetching_experimental = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_experimental_archive(self.releases.experimental_archive.take()),
@@ -480,11 +1085,13 @@ impl Gui {
             }
             GuiMessage::ExperimentalArchiveFetched((_, experimental_archive)) => {
                 self.releases.experimental_archive = experimental_archive;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_experimental = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchPatchLatest => {
+                self.controls.fetching_patch = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_patch_latest(self.releases.patch_latest.take()),
@@ -493,11 +1100,13 @@ impl Gui {
             }
             GuiMessage::PatchLatestFetched((_, patch_latest)) => {
                 self.releases.patch_latest = patch_latest;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_patch = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchPatchArchive => {
+                self.controls.fetching_patch = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_patch_archive(self.releases.patch_archive.take()),
@@ -506,11 +1115,13 @@ impl Gui {
             }
             GuiMessage::PatchArchiveFetched((_, patch_archive)) => {
                 self.releases.patch_archive = patch_archive;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_patch = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchStableLatest => {
+                self.controls.fetching_stable = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_stable_latest(self.releases.stable_latest.take()),
@@ -519,11 +1130,13 @@ impl Gui {
             }
             GuiMessage::StableLatestFetched((_, stable_latest)) => {
                 self.releases.stable_latest = stable_latest;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_stable = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchStableArchive => {
+                self.controls.fetching_stable = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_stable_archive(self.releases.stable_archive.take()),
@@ -532,11 +1145,13 @@ impl Gui {
             }
             GuiMessage::StableArchiveFetched((_, stable_archive)) => {
                 self.releases.stable_archive = stable_archive;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_stable = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FetchLts => {
+                self.controls.fetching_lts = true;
                 FETCHING.store(true, Ordering::Relaxed);
                 Command::perform(
                     Gui::check_lts(self.releases.lts.take()),
@@ -545,102 +1160,187 @@ impl Gui {
             }
             GuiMessage::LtsFetched((_, lts)) => {
                 self.releases.lts = lts;
-                self.sync();
+                self.sync_fetched();
+                self.controls.fetching_lts = false;
                 FETCHING.store(false, Ordering::Relaxed);
                 Command::none()
             }
             GuiMessage::FilterUpdatesChanged(change) => {
-                set_setting().filters.updates = change;
-                save_settings();
+                SettingsService::update(|settings| settings.filters.updates = change);
                 Command::none()
             }
 
-            GuiMessage::FilterBookmarksChanged(change) => {
-                set_setting().filters.bookmarks = change;
-                save_settings();
+            GuiMessage::FilterFavouritesChanged(change) => {
+                SettingsService::update(|settings| settings.filters.favourites = change);
                 Command::none()
             }
             GuiMessage::FilterInstalledChanged(change) => {
-                set_setting().filters.installed = change;
-                save_settings();
+                SettingsService::update(|settings| settings.filters.installed = change);
                 Command::none()
             }
             GuiMessage::FilterAllChanged(change) => {
-                set_setting().filters.all = change;
-                set_setting().filters.daily_latest = change;
-                set_setting().filters.daily_archive = change;
-                set_setting().filters.experimental_latest = change;
-                set_setting().filters.experimental_archive = change;
-                set_setting().filters.patch_latest = change;
-                set_setting().filters.patch_archive = change;
-                set_setting().filters.stable_latest = change;
-                set_setting().filters.stable_archive = change;
-                set_setting().filters.lts = change;
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.all = change;
+                    settings.filters.daily_latest = change;
+                    settings.filters.daily_archive = change;
+                    settings.filters.experimental_latest = change;
+                    settings.filters.experimental_archive = change;
+                    settings.filters.patch_latest = change;
+                    settings.filters.patch_archive = change;
+                    settings.filters.stable_latest = change;
+                    settings.filters.stable_archive = change;
+                    settings.filters.lts = change;
+                });
                 Command::none()
             }
             GuiMessage::FilterDailyLatestChanged(change) => {
-                set_setting().filters.daily_latest = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.daily_latest = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterDailyArchiveChanged(change) => {
-                set_setting().filters.daily_archive = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.daily_archive = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterExperimentalLatestChanged(change) => {
-                set_setting().filters.experimental_latest = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.experimental_latest = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterExperimentalArchiveChanged(change) => {
-                set_setting().filters.experimental_archive = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.experimental_archive = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterPatchLatestChanged(change) => {
-                set_setting().filters.patch_latest = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.patch_latest = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterPatchArchiveChanged(change) => {
-                set_setting().filters.patch_archive = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.patch_archive = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterStableLatestChanged(change) => {
-                set_setting().filters.stable_latest = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.stable_latest = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterStableArchiveChanged(change) => {
-                set_setting().filters.stable_archive = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.stable_archive = change;
+                    settings.filters.refresh_all();
+                });
                 Command::none()
             }
             GuiMessage::FilterLtsChanged(change) => {
-                set_setting().filters.lts = change;
-                set_setting().filters.refresh_all();
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.filters.lts = change;
+                    settings.filters.refresh_all();
+                });
+                Command::none()
+            }
+            GuiMessage::FilterMinVersionChanged(min_version) => {
+                self.controls.min_version = min_version;
+                self.apply_version_range();
+                Command::none()
+            }
+            GuiMessage::FilterMaxVersionChanged(max_version) => {
+                self.controls.max_version = max_version;
+                self.apply_version_range();
+                Command::none()
+            }
+            GuiMessage::FilterDateFromChanged(date_from) => {
+                self.controls.date_from = date_from;
+                self.apply_date_range();
+                Command::none()
+            }
+            GuiMessage::FilterDateToChanged(date_to) => {
+                self.controls.date_to = date_to;
+                self.apply_date_range();
+                Command::none()
+            }
+            GuiMessage::DateRangePresetPicked(preset) => {
+                let format = |date: Option<NaiveDate>| {
+                    date.map(|date| date.format(DATE_FORMAT).to_string())
+                        .unwrap_or_default()
+                };
+                let (date_from, date_to) = preset.range();
+                self.controls.date_from = format(date_from);
+                self.controls.date_to = format(date_to);
+                self.apply_date_range();
                 Command::none()
             }
             GuiMessage::SortingChanged(sort_by) => {
-                set_setting().sort_by = sort_by;
-                save_settings();
+                SettingsService::update(|settings| settings.sort_by = sort_by);
+                Gui::measure_missing_sizes(&self.packages, &self.state.package_sizes)
+            }
+            GuiMessage::SizesMeasured(sizes) => {
+                self.state.package_sizes.extend(sizes);
+                Command::none()
+            }
+            GuiMessage::ExternalScanned(external) => {
+                self.state.scanning_external = false;
+                // What was installed meanwhile may have been taken for an external package.
+                if self.state.external_scan_wanted {
+                    return Command::none();
+                }
+                self.releases.external = external;
+                self.refresh_packages();
+                Command::none()
+            }
+            GuiMessage::CheckBuildHealth(generation) => {
+                if generation != self.state.build_health_generation {
+                    return Command::none();
+                }
+
+                let wanted = mem::take(&mut self.state.build_health_wanted);
+                let packages: Vec<Package> = self
+                    .packages
+                    .iter()
+                    .filter(|package| wanted.contains(&package.name))
+                    .cloned()
+                    .collect();
+                self.state.build_health_pending.extend(wanted);
+                Command::perform(
+                    Gui::fetch_build_health(packages),
+                    GuiMessage::BuildHealthFetched,
+                )
+            }
+            GuiMessage::BuildHealthFetched(health) => {
+                for package in &mut self.packages {
+                    if let Some(package_health) = health.get(&package.name) {
+                        package.build_health = Some(*package_health);
+                    }
+                }
+                for name in health.keys() {
+                    self.state.build_health_pending.remove(name);
+                }
+                self.state.build_health.extend(health);
+                Command::none()
+            }
+            GuiMessage::SearchChanged(search) => {
+                self.controls.search = search;
                 Command::none()
             }
             GuiMessage::TabChanged(tab) => {
-                set_setting().tab = tab;
-                save_settings();
+                SettingsService::update(|settings| settings.tab = tab);
                 if tab == Tab::Settings {
                     Command::perform(Gui::calculate_disk_space(), GuiMessage::CalculateDiskSpace)
                 } else {
@@ -652,148 +1352,567 @@ impl Gui {
                 Command::none()
             }
             GuiMessage::BypassLauncher(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().bypass_launcher = true,
-                    Choice::Disable => set_setting().bypass_launcher = false,
-                }
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.bypass_launcher = choice == Choice::Enable
+                });
                 Command::none()
             }
             GuiMessage::ModifierKey(modifier_key) => {
-                set_setting().modifier_key = modifier_key;
-                save_settings();
+                SettingsService::update(|settings| settings.modifier_key = modifier_key);
                 Command::none()
             }
             GuiMessage::UseLatestAsDefault(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().use_latest_as_default = true,
-                    Choice::Disable => set_setting().use_latest_as_default = false,
-                }
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.use_latest_as_default = choice == Choice::Enable
+                });
                 Command::none()
             }
-            GuiMessage::CheckUpdatesAtLaunch(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().check_updates_at_launch = true,
-                    Choice::Disable => set_setting().check_updates_at_launch = false,
-                }
-                save_settings();
+            GuiMessage::BackupPrefsOnDefaultChange(choice) => {
+                SettingsService::update(|settings| {
+                    settings.backup_prefs_on_default_change = choice == Choice::Enable
+                });
                 Command::none()
             }
-            GuiMessage::MinutesBetweenUpdatesChanged(change) => {
-                if change.is_positive() {
-                    let mut current = get_setting().minutes_between_updates;
-                    current += change as u64;
-                    if current > 1440 {
-                        set_setting().minutes_between_updates = 1440;
-                    } else {
-                        set_setting().minutes_between_updates = current;
-                    }
-                } else {
-                    let current = get_setting().minutes_between_updates;
-                    set_setting().minutes_between_updates =
-                        current.saturating_sub(change.abs() as u64);
-                }
-                save_settings();
+            GuiMessage::HidePreRelease(choice) => {
+                SettingsService::update(|settings| {
+                    settings.hide_pre_release = choice == Choice::Enable
+                });
                 Command::none()
             }
-            GuiMessage::UpdateDailyLatest(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().update_daily_latest = true,
-                    Choice::Disable => set_setting().update_daily_latest = false,
-                }
-                save_settings();
-                self.sync();
+            GuiMessage::ResumeDownloads(choice) => {
+                SettingsService::update(|settings| {
+                    settings.resume_downloads = choice == Choice::Enable
+                });
                 Command::none()
             }
-            GuiMessage::UpdateExperimentalLatest(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().update_experimental_latest = true,
-                    Choice::Disable => set_setting().update_experimental_latest = false,
-                }
-                save_settings();
-                self.sync();
+            GuiMessage::ProxyUrlChanged(proxy_url) => {
+                SettingsService::update(|settings| {
+                    settings.proxy_url = match proxy_url.trim() {
+                        "" => None,
+                        proxy_url => Some(proxy_url.to_string()),
+                    };
+                });
+                self.state.proxy_url = proxy_url;
                 Command::none()
             }
-            GuiMessage::UpdatePatchLatest(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().update_patch_latest = true,
-                    Choice::Disable => set_setting().update_patch_latest = false,
-                }
-                save_settings();
-                self.sync();
+            GuiMessage::NewMirrorChanged(mirror) => {
+                self.state.new_mirror = mirror;
                 Command::none()
             }
-            GuiMessage::UpdateStableLatest(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().update_stable_latest = true,
-                    Choice::Disable => set_setting().update_stable_latest = false,
+            GuiMessage::MirrorAdded => {
+                let mirror = self.state.new_mirror.trim().to_string();
+                if mirror.is_empty() || get_setting().mirror_urls.contains(&mirror) {
+                    return Command::none();
                 }
-                save_settings();
-                self.sync();
+                if let Err(e) = Url::parse(&mirror) {
+                    self.state.error = Some(format!("Invalid mirror URL '{}': {}", mirror, e));
+                    return Command::none();
+                }
+
+                SettingsService::update(|settings| settings.mirror_urls.push(mirror));
+                self.state.new_mirror.clear();
                 Command::none()
             }
-            GuiMessage::UpdateLts(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().update_lts = true,
-                    Choice::Disable => set_setting().update_lts = false,
+            GuiMessage::PickLocalBuild => match FileDialog::new().show_open_single_dir() {
+                Ok(Some(path)) => self.update_message(GuiMessage::AddLocalBuild(path)),
+                Ok(None) => Command::none(),
+                Err(e) => {
+                    log::error!("Couldn't show the file dialog: {}", e);
+                    Command::none()
+                }
+            },
+            GuiMessage::AddLocalBuild(path) => {
+                if get_setting()
+                    .local_builds
+                    .iter()
+                    .any(|local_build| local_build.local_path.as_ref() == Some(&path))
+                {
+                    self.state.error = Some(format!("{} was already added.", path.display()));
+                    return Command::none();
                 }
-                save_settings();
+                Command::perform(Gui::local_build(path), GuiMessage::LocalBuildAdded)
+            }
+            GuiMessage::LocalBuildAdded(result) => match result {
+                Ok(package) => {
+                    if self
+                        .releases
+                        .installed
+                        .iter()
+                        .any(|installed| installed.name == package.name)
+                    {
+                        self.state.error =
+                            Some(format!("There's already a package named {}.", package.name));
+                        return Command::none();
+                    }
+                    log::info!(
+                        "Added the local build {} from {}.",
+                        package.name,
+                        package.local_path.as_ref().unwrap().display()
+                    );
+                    let notification = format!("Added the local build {}.", package.name);
+                    SettingsService::update(|settings| settings.local_builds.push(package));
+                    self.sync();
+                    self.update_message(GuiMessage::ShowNotification(
+                        notification,
+                        NOTIFICATION_DURATION,
+                    ))
+                }
+                Err(e) => {
+                    log::error!("Couldn't add the local build: {}", e);
+                    self.state.error = Some(format!("Couldn't add the local build: {}.", e));
+                    Command::none()
+                }
+            },
+            GuiMessage::ForgetLocalBuild(name) => {
+                SettingsService::update(|settings| {
+                    settings
+                        .local_builds
+                        .retain(|local_build| local_build.name != name);
+                });
+                log::info!("Forgot the local build {}.", name);
                 self.sync();
                 Command::none()
             }
-            GuiMessage::ThemeChanged(theme) => {
-                set_setting().theme = theme;
-                save_settings();
+            GuiMessage::CustomPackageUrlChanged(url) => {
+                self.state.custom_package_url = url;
                 Command::none()
             }
-            GuiMessage::ChangeLocation(location) => {
-                match location {
-                    Location::Databases => {
-                        if let Some(directory) = FileDialog::new().show_open_single_dir().unwrap() {
-                            set_setting().databases_dir = directory;
-                            save_settings();
-                        }
-                    }
-                    Location::Packages => {
-                        if let Some(directory) = FileDialog::new().show_open_single_dir().unwrap() {
-                            set_setting().packages_dir = directory;
-                            save_settings();
-                            self.sync();
-                        }
-                    }
-                    Location::Cache => {
-                        if let Some(directory) = FileDialog::new().show_open_single_dir().unwrap() {
-                            set_setting().cache_dir = directory;
-                            save_settings();
-                        }
-                    }
-                }
+            GuiMessage::CustomPackageNameChanged(name) => {
+                self.state.custom_package_name = name;
                 Command::none()
             }
-            GuiMessage::ResetLocation(location) => {
-                match location {
-                    Location::Databases => {
-                        set_setting().databases_dir = PROJECT_DIRS.config_dir().to_path_buf();
-                        save_settings();
+            GuiMessage::CustomPackageVersionChanged(version) => {
+                self.state.custom_package_version = version;
+                Command::none()
+            }
+            GuiMessage::AddCustomPackage => {
+                if self.state.checking_custom_package {
+                    return Command::none();
+                }
+                let package = match Package::from_url(
+                    self.state.custom_package_url.trim(),
+                    &self.state.custom_package_name,
+                    &self.state.custom_package_version,
+                ) {
+                    Ok(package) => package,
+                    Err(e) => {
+                        self.state.error = Some(format!("Couldn't add the custom package: {}.", e));
+                        return Command::none();
                     }
-                    Location::Packages => {
-                        set_setting().packages_dir = PROJECT_DIRS.data_local_dir().to_path_buf();
-                        save_settings();
+                };
+                if self
+                    .packages
+                    .iter()
+                    .any(|listed| listed.name == package.name || listed.url == package.url)
+                {
+                    self.state.error = Some(format!(
+                        "{} is already listed, or a package with that name is.",
+                        package.url
+                    ));
+                    return Command::none();
+                }
+                self.state.checking_custom_package = true;
+                Command::perform(
+                    Gui::check_custom_package(package),
+                    GuiMessage::CustomPackageChecked,
+                )
+            }
+            GuiMessage::CustomPackageChecked(result) => {
+                self.state.checking_custom_package = false;
+                match result {
+                    Ok(package) => {
+                        log::info!(
+                            "Added the custom package {} from {}.",
+                            package.name,
+                            package.url
+                        );
+                        let notification = format!("Added the custom package {}.", package.name);
+                        SettingsService::update(|settings| settings.custom_packages.push(package));
+                        self.state.custom_package_url.clear();
+                        self.state.custom_package_name.clear();
+                        self.state.custom_package_version.clear();
                         self.sync();
+                        self.update_message(GuiMessage::ShowNotification(
+                            notification,
+                            NOTIFICATION_DURATION,
+                        ))
                     }
-                    Location::Cache => {
-                        set_setting().cache_dir = PROJECT_DIRS.cache_dir().to_path_buf();
-                        save_settings();
+                    Err(e) => {
+                        log::error!("Couldn't add the custom package: {}", e);
+                        self.state.error = Some(format!("Couldn't add the custom package: {}.", e));
+                        Command::none()
                     }
                 }
+            }
+            GuiMessage::ForgetCustomPackage(url) => {
+                // Once it's off the list, nothing would show the installed copy anymore.
+                if let Some(package) = self.releases.custom.iter().find(|package| {
+                    package.url == url && matches!(package.state, PackageState::Installed)
+                }) {
+                    self.state.error = Some(format!(
+                        "{} is installed, so it has to be uninstalled before it's forgotten.",
+                        package.name
+                    ));
+                    return Command::none();
+                }
+                SettingsService::update(|settings| {
+                    settings
+                        .custom_packages
+                        .retain(|package| package.url != url);
+                });
+                log::info!("Forgot the custom package from {}.", url);
+                self.sync();
                 Command::none()
             }
-            GuiMessage::RemoveDatabases(build_type) => {
-                match build_type {
-                    BuildTypeSettings::All => {
-                        remove_dir_all(&get_setting().databases_dir).unwrap();
-                        create_dir_all(&get_setting().databases_dir).unwrap();
+            GuiMessage::LocalBuildNotRemovable(package) => {
+                self.state.error = Some(format!(
+                    "{} is a local build, so it isn't uninstalled. It can be removed from the \
+                     list on the Settings tab, which leaves its files alone.",
+                    package.name
+                ));
+                Command::none()
+            }
+            GuiMessage::MirrorRemoved(index) => {
+                SettingsService::update(|settings| {
+                    if index < settings.mirror_urls.len() {
+                        let mirror = settings.mirror_urls.remove(index);
+                        self.state.mirror_tests.remove(&mirror);
+                    }
+                });
+                Command::none()
+            }
+            GuiMessage::TestMirror(mirror) => {
+                self.state
+                    .mirror_tests
+                    .insert(mirror.clone(), String::from("Testing..."));
+                Command::perform(test_mirror(mirror), GuiMessage::MirrorTested)
+            }
+            GuiMessage::MirrorTested((mirror, result)) => {
+                let result = match result {
+                    Ok(latency) => format!("{} ms", latency.as_millis()),
+                    Err(e) => format!("Failed: {}", e),
+                };
+                self.state.mirror_tests.insert(mirror, result);
+                Command::none()
+            }
+            GuiMessage::BuildbotApiUrlChanged(api_url) => {
+                SettingsService::update(|settings| {
+                    settings.buildbot_api_url = api_url.trim().to_string()
+                });
+                self.state.buildbot_api_url = api_url;
+                self.state.build_health.clear();
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::NamingTemplateChanged(naming_template) => {
+                SettingsService::update(|settings| {
+                    settings.naming_template = match naming_template.trim() {
+                        "" => None,
+                        naming_template => Some(naming_template.to_string()),
+                    };
+                });
+                self.state.naming_template = naming_template;
+                Command::none()
+            }
+            GuiMessage::CopyInfoTemplateChanged(copy_info_template) => {
+                SettingsService::update(|settings| {
+                    settings.copy_info_template = copy_info_template.clone()
+                });
+                self.state.copy_info_template = copy_info_template;
+                Command::none()
+            }
+            GuiMessage::ProfileSelected(name) => {
+                SettingsService::update(|settings| {
+                    settings.active_profile = (name != NO_PROFILE).then_some(name)
+                });
+                Command::none()
+            }
+            GuiMessage::NewProfileChanged(name) => {
+                self.state.new_profile = name;
+                Command::none()
+            }
+            GuiMessage::ProfileCreated => {
+                let name = self.state.new_profile.trim().to_string();
+                let taken = name == NO_PROFILE
+                    || get_setting()
+                        .profiles
+                        .iter()
+                        .any(|profile| profile.name == name);
+                if name.is_empty() || taken {
+                    return Command::none();
+                }
+
+                SettingsService::update(|settings| {
+                    settings.profiles.push(LaunchProfile {
+                        name,
+                        ..Default::default()
+                    });
+                });
+                self.state.new_profile.clear();
+                self.state.profile_drafts = GuiState::profile_drafts();
+                Command::none()
+            }
+            GuiMessage::ProfileDeleted(index) => {
+                SettingsService::update(|settings| {
+                    if index < settings.profiles.len() {
+                        let profile = settings.profiles.remove(index);
+                        if settings.active_profile.as_ref() == Some(&profile.name) {
+                            settings.active_profile = None;
+                        }
+                    }
+                });
+                self.state.profile_drafts = GuiState::profile_drafts();
+                Command::none()
+            }
+            GuiMessage::ProfileArgsChanged(index, args) => {
+                SettingsService::update(|settings| {
+                    if let Some(profile) = settings.profiles.get_mut(index) {
+                        profile.launch_args = LaunchProfile::parse_args(&args);
+                    }
+                });
+                if let Some((args_draft, _)) = self.state.profile_drafts.get_mut(index) {
+                    *args_draft = args;
+                }
+                Command::none()
+            }
+            GuiMessage::ProfileEnvChanged(index, env) => {
+                SettingsService::update(|settings| {
+                    if let Some(profile) = settings.profiles.get_mut(index) {
+                        profile.env_vars = LaunchProfile::parse_env(&env);
+                    }
+                });
+                if let Some((_, env_draft)) = self.state.profile_drafts.get_mut(index) {
+                    *env_draft = env;
+                }
+                Command::none()
+            }
+            GuiMessage::FontFamilyChanged(font_family) => {
+                SettingsService::update(|settings| settings.font_family = font_family);
+                Command::none()
+            }
+            GuiMessage::CustomFontChanged(name) => {
+                SettingsService::update(|settings| {
+                    settings.font_family = FontFamily::Custom(name.trim().to_string())
+                });
+                self.state.custom_font = name;
+                Command::none()
+            }
+            GuiMessage::ConnectTimeoutChanged(secs) => {
+                SettingsService::update(|settings| settings.connect_timeout_secs = secs as u64);
+                Command::none()
+            }
+            GuiMessage::ReadTimeoutChanged(secs) => {
+                SettingsService::update(|settings| settings.read_timeout_secs = secs as u64);
+                Command::none()
+            }
+            GuiMessage::ProxyBypassChanged(proxy_bypass) => {
+                SettingsService::update(|settings| {
+                    settings.proxy_bypass = proxy_bypass
+                        .split(',')
+                        .map(|host| host.trim().to_string())
+                        .filter(|host| !host.is_empty())
+                        .collect();
+                });
+                self.state.proxy_bypass = proxy_bypass;
+                Command::none()
+            }
+            GuiMessage::CheckUpdatesAtLaunch(choice) => {
+                SettingsService::update(|settings| {
+                    settings.check_updates_at_launch = choice == Choice::Enable
+                });
+                Command::none()
+            }
+            GuiMessage::MinutesBetweenUpdatesChanged(change) => {
+                SettingsService::update(|settings| {
+                    let current = settings.minutes_between_updates;
+                    settings.minutes_between_updates = if change.is_positive() {
+                        (current + change as u64).min(1440)
+                    } else {
+                        current.saturating_sub(change.abs() as u64)
+                    };
+                });
+                Command::none()
+            }
+            GuiMessage::MaxConcurrentDownloadsChanged(max) => {
+                let new = max as usize;
+                self.download_slots
+                    .resize(get_setting().max_concurrent_downloads, new);
+                SettingsService::update(|settings| settings.max_concurrent_downloads = new);
+                Command::none()
+            }
+            GuiMessage::UpdateChannelChanged(update_channel) => {
+                SettingsService::update(|settings| settings.update_channel = update_channel);
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::UpdateDailyLatest(choice) => {
+                SettingsService::update(|settings| {
+                    settings.update_daily_latest = choice == Choice::Enable
+                });
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::UpdateExperimentalLatest(choice) => {
+                SettingsService::update(|settings| {
+                    settings.update_experimental_latest = choice == Choice::Enable
+                });
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::UpdatePatchLatest(choice) => {
+                SettingsService::update(|settings| {
+                    settings.update_patch_latest = choice == Choice::Enable
+                });
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::UpdateStableLatest(choice) => {
+                SettingsService::update(|settings| {
+                    settings.update_stable_latest = choice == Choice::Enable
+                });
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::UpdateLts(choice) => {
+                SettingsService::update(|settings| settings.update_lts = choice == Choice::Enable);
+                self.sync();
+                Command::none()
+            }
+            GuiMessage::AutoInstallUpdates(choice) => {
+                SettingsService::update(|settings| {
+                    settings.auto_install_updates = choice == Choice::Enable
+                });
+                Command::none()
+            }
+            GuiMessage::AutoInstallDaily(enabled) => {
+                SettingsService::update(|settings| settings.auto_install_daily = enabled);
+                Command::none()
+            }
+            GuiMessage::AutoInstallExperimental(enabled) => {
+                SettingsService::update(|settings| settings.auto_install_experimental = enabled);
+                Command::none()
+            }
+            GuiMessage::AutoInstallPatch(enabled) => {
+                SettingsService::update(|settings| settings.auto_install_patch = enabled);
+                Command::none()
+            }
+            GuiMessage::AutoInstallStable(enabled) => {
+                SettingsService::update(|settings| settings.auto_install_stable = enabled);
+                Command::none()
+            }
+            GuiMessage::AutoInstallLts(enabled) => {
+                SettingsService::update(|settings| settings.auto_install_lts = enabled);
+                Command::none()
+            }
+            GuiMessage::KeepCountDaily(keep_count) => {
+                SettingsService::update(|settings| settings.keep_count_daily = keep_count.0);
+                Command::none()
+            }
+            GuiMessage::KeepCountExperimental(keep_count) => {
+                SettingsService::update(|settings| settings.keep_count_experimental = keep_count.0);
+                Command::none()
+            }
+            GuiMessage::KeepCountPatch(keep_count) => {
+                SettingsService::update(|settings| settings.keep_count_patch = keep_count.0);
+                Command::none()
+            }
+            GuiMessage::KeepCountStable(keep_count) => {
+                SettingsService::update(|settings| settings.keep_count_stable = keep_count.0);
+                Command::none()
+            }
+            GuiMessage::KeepCountLts(keep_count) => {
+                SettingsService::update(|settings| settings.keep_count_lts = keep_count.0);
+                Command::none()
+            }
+            GuiMessage::AutoCleanupDaysChanged(days) => {
+                SettingsService::update(|settings| {
+                    settings.auto_cleanup_days = (days > 0).then_some(days as u64)
+                });
+                Command::none()
+            }
+            GuiMessage::ResetStats => {
+                SettingsService::update(|settings| {
+                    settings.download_stats = DownloadStats::default()
+                });
+                Command::none()
+            }
+            GuiMessage::ThemeChanged(theme) => {
+                SettingsService::update(|settings| settings.theme = theme);
+                Command::none()
+            }
+            GuiMessage::ChangeLocation(location) => {
+                match location {
+                    Location::Databases => {
+                        if let Some(directory) = FileDialog::new().show_open_single_dir().unwrap() {
+                            SettingsService::update(|settings| settings.databases_dir = directory);
+                        }
+                    }
+                    Location::Packages => {
+                        if let Some(directory) = FileDialog::new().show_open_single_dir().unwrap() {
+                            self.remember_packages_dir(&directory);
+                            SettingsService::update(|settings| settings.packages_dir = directory);
+                            self.sync();
+                        }
+                    }
+                    Location::Cache => {
+                        if let Some(directory) = FileDialog::new().show_open_single_dir().unwrap() {
+                            SettingsService::update(|settings| settings.cache_dir = directory);
+                        }
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::ResetLocation(location) => {
+                match location {
+                    Location::Databases => {
+                        SettingsService::update(|settings| {
+                            settings.databases_dir = PROJECT_DIRS.config_dir().to_path_buf()
+                        });
+                    }
+                    Location::Packages => {
+                        self.remember_packages_dir(PROJECT_DIRS.data_local_dir());
+                        SettingsService::update(|settings| {
+                            settings.packages_dir = PROJECT_DIRS.data_local_dir().to_path_buf()
+                        });
+                        self.sync();
+                    }
+                    Location::Cache => {
+                        SettingsService::update(|settings| {
+                            settings.cache_dir = PROJECT_DIRS.cache_dir().to_path_buf()
+                        });
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::MigratePackages(previous_packages_dir) => {
+                self.state.move_packages_error = None;
+                self.state.migrating_packages = match read_dir(&previous_packages_dir) {
+                    Ok(entries) => entries
+                        .filter_map(|entry| entry.ok())
+                        .map(|entry| entry.path())
+                        .filter(|path| path.join("package_info.ron").exists())
+                        .collect(),
+                    Err(e) => {
+                        self.state.move_packages_error = Some(e.to_string());
+                        Vec::new()
+                    }
+                };
+                self.state.migration_total = self.state.migrating_packages.len();
+                self.migrate_next_package()
+            }
+            GuiMessage::PackageMigrated(result) => {
+                if let Err(e) = result {
+                    // Whatever wasn't moved yet stays where it was.
+                    self.state.move_packages_error = Some(e);
+                    self.state.migrating_packages.clear();
+                }
+                self.migrate_next_package()
+            }
+            GuiMessage::RemoveDatabases(build_type) => {
+                match build_type {
+                    BuildTypeSettings::All => {
+                        remove_dir_all(&get_setting().databases_dir).unwrap();
+                        create_dir_all(&get_setting().databases_dir).unwrap();
                     }
                     BuildTypeSettings::DailyLatest => {
                         self.releases.daily_latest.remove_db();
@@ -868,27 +1987,29 @@ impl Gui {
                 Command::perform(Gui::calculate_disk_space(), GuiMessage::CalculateDiskSpace)
             }
             GuiMessage::SelfUpdater(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().self_updater = true,
-                    Choice::Disable => set_setting().self_updater = false,
-                }
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.self_updater = choice == Choice::Enable
+                });
                 Command::none()
             }
             GuiMessage::CheckSelfUpdatesAtLaunch(choice) => {
-                match choice {
-                    Choice::Enable => set_setting().check_self_updates_at_launch = true,
-                    Choice::Disable => set_setting().check_self_updates_at_launch = false,
-                }
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.check_self_updates_at_launch = choice == Choice::Enable
+                });
                 Command::none()
             }
             GuiMessage::FetchSelfReleases => {
                 self.state.fetching_releases = true;
                 Command::perform(Gui::fetch_self_releases(), GuiMessage::PopulateSelfReleases)
             }
-            GuiMessage::PopulateSelfReleases(self_releases) => {
-                self.self_releases = self_releases;
+            GuiMessage::PopulateSelfReleases(result) => {
+                match result {
+                    Ok(self_releases) => self.self_releases = self_releases,
+                    Err(e) => {
+                        log::error!("{}", e);
+                        self.state.error = Some(e);
+                    }
+                }
                 if let Some(s_releases) = &self.self_releases {
                     self.state.release_versions = s_releases
                         .iter()
@@ -912,9 +2033,693 @@ impl Gui {
                     GuiMessage::VersionChanged,
                 )
             }
-            GuiMessage::VersionChanged(()) => {
+            GuiMessage::VersionChanged(result) => {
                 self.state.installing_release = false;
-                self.state.installed_release = true;
+                match result {
+                    Ok(()) => self.state.installed_release = true,
+                    Err(e) => {
+                        log::error!("{}", e);
+                        self.state.error = Some(e);
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::DismissError => {
+                self.state.error = None;
+                Command::none()
+            }
+            GuiMessage::LogsCopied => clipboard::write(get_logs().join("\n")),
+            GuiMessage::LogsCleared => {
+                clear_logs();
+                Command::none()
+            }
+            GuiMessage::KeyPressed(combo, captured) => {
+                if let Some(action) = self.state.rebinding.take() {
+                    if combo != KeyCombo::escape() {
+                        SettingsService::update(|settings| {
+                            settings.keybindings.insert(action, combo)
+                        });
+                    }
+                    return Command::none();
+                }
+
+                if self.quick_switch_mode {
+                    if combo == KeyCombo::escape() {
+                        return self.update_message(GuiMessage::QuickSwitchDismissed);
+                    }
+                    if combo.ctrl || combo.alt || combo.shift {
+                        return Command::none();
+                    }
+                    let name = key_index(&combo.key).and_then(|index| {
+                        quick_switch_packages(&self.releases.installed)
+                            .get(index)
+                            .map(|package| package.name.clone())
+                    });
+                    return match name {
+                        Some(name) => self.update_message(GuiMessage::QuickSwitchLaunch(name)),
+                        None => Command::none(),
+                    };
+                }
+
+                if self.compact_mode {
+                    return self.compact_key_pressed(&combo);
+                }
+
+                if self.state.comparing.is_some() && combo == KeyCombo::escape() {
+                    return self.update_message(GuiMessage::CloseComparison);
+                }
+
+                if self.state.changelog.is_some() && combo == KeyCombo::escape() {
+                    return self.update_message(GuiMessage::CloseChangelog);
+                }
+
+                // Plain keys typed into a text input shouldn't trigger anything.
+                if captured && !combo.ctrl && !combo.alt && combo != KeyCombo::escape() {
+                    return Command::none();
+                }
+
+                let action = get_setting().keybindings.action_for(&combo);
+                match action {
+                    Some(KeyAction::CheckForUpdates) => {
+                        if CAN_CONNECT.load(Ordering::Relaxed)
+                            && !INSTALLING.load(Ordering::Relaxed)
+                            && !FETCHING.load(Ordering::Relaxed)
+                        {
+                            self.update_message(GuiMessage::CheckForUpdates)
+                        } else {
+                            Command::none()
+                        }
+                    }
+                    // TODO: Focus the search box too once iced exposes a way to do it.
+                    Some(KeyAction::FocusSearch) => {
+                        self.update_message(GuiMessage::TabChanged(Tab::Packages))
+                    }
+                    Some(KeyAction::OpenSettings) => {
+                        self.update_message(GuiMessage::TabChanged(Tab::Settings))
+                    }
+                    Some(KeyAction::ClearSearch) => {
+                        self.update_message(GuiMessage::SearchChanged(String::new()))
+                    }
+                    None => self.package_key_pressed(&combo),
+                }
+            }
+            GuiMessage::RebindKey(action) => {
+                self.state.rebinding = Some(action);
+                Command::none()
+            }
+            GuiMessage::ShowNotification(message, duration) => {
+                self.notifications.push(Notification {
+                    message,
+                    expires_at: Instant::now() + duration,
+                });
+                Command::none()
+            }
+            GuiMessage::PruneNotifications => {
+                let now = Instant::now();
+                self.notifications
+                    .retain(|notification| notification.expires_at > now);
+                Command::none()
+            }
+            GuiMessage::NewGroupChanged(name) => {
+                self.controls.new_group = name;
+                Command::none()
+            }
+            GuiMessage::GroupCreated => {
+                let name = self.controls.new_group.trim().to_string();
+                if !name.is_empty()
+                    && !get_setting()
+                        .package_groups
+                        .iter()
+                        .any(|group| group.name == name)
+                {
+                    SettingsService::update(|settings| {
+                        settings.package_groups.push(PackageGroup {
+                            name,
+                            packages: Vec::new(),
+                        });
+                    });
+                }
+                self.controls.new_group.clear();
+                Command::none()
+            }
+            GuiMessage::GroupRenameStarted(name) => {
+                self.controls.renaming_group = Some((name.clone(), name));
+                Command::none()
+            }
+            GuiMessage::GroupRenameChanged(draft) => {
+                if let Some((_, current_draft)) = &mut self.controls.renaming_group {
+                    *current_draft = draft;
+                }
+                Command::none()
+            }
+            GuiMessage::GroupRenamed => {
+                if let Some((name, draft)) = self.controls.renaming_group.take() {
+                    let draft = draft.trim().to_string();
+                    let taken = get_setting()
+                        .package_groups
+                        .iter()
+                        .any(|group| group.name == draft);
+                    if !draft.is_empty() && !taken {
+                        SettingsService::update(|settings| {
+                            if let Some(group) = settings
+                                .package_groups
+                                .iter_mut()
+                                .find(|group| group.name == name)
+                            {
+                                group.name = draft.clone();
+                            }
+                            if settings.filters.groups.remove(&name) {
+                                settings.filters.groups.insert(draft);
+                            }
+                        });
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::GroupDeleted(name) => {
+                SettingsService::update(|settings| {
+                    settings.package_groups.retain(|group| group.name != name);
+                    settings.filters.groups.remove(&name);
+                });
+                Command::none()
+            }
+            GuiMessage::GroupFilterChanged(name, active) => {
+                SettingsService::update(|settings| {
+                    if active {
+                        settings.filters.groups.insert(name);
+                    } else {
+                        settings.filters.groups.remove(&name);
+                    }
+                });
+                Command::none()
+            }
+            GuiMessage::TagFilterChanged(tag, active) => {
+                SettingsService::update(|settings| {
+                    if active {
+                        settings.filters.tags.insert(tag);
+                    } else {
+                        settings.filters.tags.remove(&tag);
+                    }
+                });
+                Command::none()
+            }
+            GuiMessage::GroupModeToggled(group_mode) => {
+                self.controls.group_mode = group_mode;
+                self.controls.group_selection.clear();
+                Command::none()
+            }
+            GuiMessage::GroupSelectionChanged(package, selected) => {
+                self.controls
+                    .group_selection
+                    .retain(|name| *name != package.name);
+                if selected {
+                    self.controls.group_selection.push(package.name);
+                }
+                Command::none()
+            }
+            GuiMessage::AddToGroup(name) => {
+                SettingsService::update(|settings| {
+                    if let Some(group) = settings
+                        .package_groups
+                        .iter_mut()
+                        .find(|group| group.name == name)
+                    {
+                        for package in &self.controls.group_selection {
+                            if !group.packages.contains(package) {
+                                group.packages.push(package.clone());
+                            }
+                        }
+                    }
+                });
+                self.controls.group_selection.clear();
+                Command::none()
+            }
+            GuiMessage::RemoveFromGroup(name) => {
+                SettingsService::update(|settings| {
+                    if let Some(group) = settings
+                        .package_groups
+                        .iter_mut()
+                        .find(|group| group.name == name)
+                    {
+                        group
+                            .packages
+                            .retain(|package| !self.controls.group_selection.contains(package));
+                    }
+                });
+                self.controls.group_selection.clear();
+                Command::none()
+            }
+            GuiMessage::QuickSwitchLaunch(name) => {
+                if self.file_paths.is_empty() {
+                    self.update_message(GuiMessage::OpenBlender(name))
+                } else {
+                    self.update_message(GuiMessage::OpenBlenderWithFile(name))
+                }
+            }
+            GuiMessage::QuickSwitchDismissed => {
+                self.quick_switch_mode = false;
+                Command::none()
+            }
+            GuiMessage::LeaveCompactMode => {
+                self.compact_mode = false;
+                self.focused_package_index = None;
+                window::resize(get_setting().window_width, get_setting().window_height)
+            }
+            GuiMessage::MultiSelectToggled => {
+                self.controls.multi_select = !self.controls.multi_select;
+                self.selected_packages.clear();
+                Command::none()
+            }
+            GuiMessage::PackageSelectionChanged(package, selected) => {
+                if selected {
+                    self.selected_packages.insert(package.name);
+                } else {
+                    self.selected_packages.remove(&package.name);
+                }
+                Command::none()
+            }
+            GuiMessage::SelectAllPackages(selected) => {
+                if selected {
+                    let shown = shown_packages(
+                        &self.packages,
+                        &self.controls.search,
+                        &self.pinned,
+                        &self.state.package_sizes,
+                    );
+                    self.selected_packages
+                        .extend(shown.into_iter().map(|package| package.name.clone()));
+                } else {
+                    self.selected_packages.clear();
+                }
+                Command::none()
+            }
+            GuiMessage::InstallSelected => Command::batch(
+                self.packages
+                    .iter()
+                    .filter(|package| {
+                        self.selected_packages.contains(&package.name)
+                            && matches!(
+                                package.state,
+                                PackageState::Fetched | PackageState::Errored { .. }
+                            )
+                    })
+                    .map(|package| {
+                        Command::perform(
+                            Gui::check_availability(true, package.clone()),
+                            GuiMessage::CheckAvailability,
+                        )
+                    })
+                    .collect::<Vec<_>>(),
+            ),
+            GuiMessage::ConfirmRemovingSelected => {
+                let names: Vec<&str> = self
+                    .packages
+                    .iter()
+                    .filter(|package| {
+                        self.selected_packages.contains(&package.name)
+                            && matches!(package.state, PackageState::Installed)
+                    })
+                    .map(|package| package.name.as_str())
+                    .collect();
+                if names.is_empty() {
+                    return Command::none();
+                }
+                self.update_message(GuiMessage::ShowConfirmModal(Box::new(ConfirmModal::new(
+                    format!(
+                        "Uninstall {} package{}?\n\n{}",
+                        names.len(),
+                        if names.len() == 1 { "" } else { "s" },
+                        names.join("\n")
+                    ),
+                    GuiMessage::RemoveSelected,
+                ))))
+            }
+            GuiMessage::RemoveSelected => {
+                let indexes: Vec<usize> = self
+                    .packages
+                    .iter()
+                    .filter(|package| {
+                        self.selected_packages.contains(&package.name)
+                            && matches!(package.state, PackageState::Installed)
+                    })
+                    .map(|package| package.index)
+                    .collect();
+                self.selected_packages.clear();
+                Command::batch(
+                    indexes
+                        .into_iter()
+                        .map(|index| {
+                            self.update_message(GuiMessage::PackageMessage((
+                                index,
+                                PackageMessage::RemoveConfirmed,
+                            )))
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            }
+            GuiMessage::ExportSelectedPackages => {
+                let selected: Vec<Package> = self
+                    .packages
+                    .iter()
+                    .filter(|package| self.selected_packages.contains(&package.name))
+                    .cloned()
+                    .collect();
+                self.export_packages(&selected)
+            }
+            GuiMessage::CompareModeToggled(compare_mode) => {
+                self.controls.compare_mode = compare_mode;
+                self.controls.compare_selection.clear();
+                Command::none()
+            }
+            GuiMessage::CompareSelectionChanged(package, selected) => {
+                let selection = &mut self.controls.compare_selection;
+                selection.retain(|name| *name != package.name);
+                if selected {
+                    // Ticking a third package replaces the oldest pick.
+                    if selection.len() == 2 {
+                        selection.remove(0);
+                    }
+                    selection.push(package.name);
+                }
+
+                let selected: Vec<Package> = self
+                    .controls
+                    .compare_selection
+                    .iter()
+                    .filter_map(|name| {
+                        self.packages
+                            .iter()
+                            .find(|package| package.name == *name)
+                            .cloned()
+                    })
+                    .collect();
+                match <[Package; 2]>::try_from(selected) {
+                    Ok([first, second]) => {
+                        self.update_message(GuiMessage::ComparePackages(first, second))
+                    }
+                    Err(_) => Command::none(),
+                }
+            }
+            GuiMessage::ComparePackages(first, second) => {
+                self.state.comparing = Some((first, second));
+                Command::none()
+            }
+            GuiMessage::CloseComparison => {
+                self.state.comparing = None;
+                Command::none()
+            }
+            GuiMessage::ChangelogRequested(package) => {
+                // The newest installed build it updates is the one to compare against.
+                match self
+                    .releases
+                    .installed
+                    .iter()
+                    .filter(|installed_package| package.updates(installed_package))
+                    .max_by_key(|installed_package| installed_package.date)
+                {
+                    Some(installed_package) => self.update_message(GuiMessage::ShowChangelog(
+                        installed_package.clone(),
+                        package,
+                    )),
+                    None => {
+                        self.state.error = Some(format!(
+                            "Couldn't find the installed package {} updates.",
+                            package.name
+                        ));
+                        Command::none()
+                    }
+                }
+            }
+            GuiMessage::ShowChangelog(old, new) => {
+                let name = new.name.clone();
+                let old_commit = old.commit_hash().to_string();
+                let new_commit = new.commit_hash().to_string();
+                self.state.changelog = Some((old, new));
+                self.state.changelog_commits = None;
+                Command::perform(fetch_commits(old_commit, new_commit), move |commits| {
+                    GuiMessage::ChangelogFetched(name.clone(), commits)
+                })
+            }
+            GuiMessage::ChangelogFetched(name, commits) => {
+                // Dropped if the changelog was closed or another one opened in the meantime.
+                if let Some((_, new)) = &self.state.changelog {
+                    if new.name == name {
+                        self.state.changelog_commits = Some(commits);
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::CloseChangelog => {
+                self.state.changelog = None;
+                self.state.changelog_commits = None;
+                Command::none()
+            }
+            GuiMessage::RegisterFileAssociation => match file_association::register() {
+                Ok(()) => {
+                    log::info!("Registered Ablavema to open .blend files.");
+                    self.state.file_association_registered = true;
+                    Command::none()
+                }
+                Err(e) => {
+                    self.state.error = Some(format!(
+                        "Couldn't register Ablavema for .blend files: {}",
+                        e
+                    ));
+                    Command::none()
+                }
+            },
+            GuiMessage::UnregisterFileAssociation => match file_association::unregister() {
+                Ok(()) => {
+                    log::info!("Unregistered Ablavema from opening .blend files.");
+                    self.state.file_association_registered = false;
+                    Command::none()
+                }
+                Err(e) => {
+                    self.state.error = Some(format!(
+                        "Couldn't unregister Ablavema from .blend files: {}",
+                        e
+                    ));
+                    Command::none()
+                }
+            },
+            GuiMessage::ExportSettings => {
+                let path = match FileDialog::new()
+                    .set_filename("ablavema_settings.json")
+                    .add_filter("JSON", &["json"])
+                    .show_save_single_file()
+                {
+                    Ok(Some(path)) => path,
+                    Ok(None) => return Command::none(),
+                    Err(e) => {
+                        log::error!("Couldn't show the file dialog: {}", e);
+                        return Command::none();
+                    }
+                };
+                let result = get_setting().export(&path);
+                match result {
+                    Ok(()) => {
+                        log::info!("Exported settings to {}.", path.display());
+                        self.update_message(GuiMessage::ShowNotification(
+                            String::from("Exported settings."),
+                            NOTIFICATION_DURATION,
+                        ))
+                    }
+                    Err(e) => {
+                        self.state.error = Some(format!(
+                            "Couldn't export the settings to {}: {}",
+                            path.display(),
+                            e
+                        ));
+                        Command::none()
+                    }
+                }
+            }
+            GuiMessage::ImportSettings => {
+                let path = match FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .show_open_single_file()
+                {
+                    Ok(Some(path)) => path,
+                    Ok(None) => return Command::none(),
+                    Err(e) => {
+                        log::error!("Couldn't show the file dialog: {}", e);
+                        return Command::none();
+                    }
+                };
+                let result = get_setting().import(&path);
+                match result {
+                    Ok((settings, skipped)) => {
+                        if !skipped.is_empty() {
+                            log::warn!(
+                                "Left out of the imported settings: {}.",
+                                skipped.join(", ")
+                            );
+                        }
+                        self.download_slots.resize(
+                            get_setting().max_concurrent_downloads,
+                            settings.max_concurrent_downloads,
+                        );
+                        SettingsService::update(|current| *current = settings);
+                        log::info!("Imported settings from {}.", path.display());
+                        self.reload_settings_fields();
+                        self.sync();
+                        self.update_message(GuiMessage::ShowNotification(
+                            String::from("Imported settings."),
+                            NOTIFICATION_DURATION,
+                        ))
+                    }
+                    Err(e) => {
+                        self.state.error = Some(format!(
+                            "Couldn't import the settings from {}: {}",
+                            path.display(),
+                            e
+                        ));
+                        Command::none()
+                    }
+                }
+            }
+            GuiMessage::ResetPackageDataToggled(reset_package_data) => {
+                self.state.reset_package_data = reset_package_data;
+                Command::none()
+            }
+            GuiMessage::ConfirmResetSettings => {
+                self.update_message(GuiMessage::ShowConfirmModal(Box::new(ConfirmModal::new(
+                    String::from(if self.state.reset_package_data {
+                        "Every setting will be restored to its default, and the default package, \
+                        favourites and recent files will be cleared."
+                    } else {
+                        "Every setting will be restored to its default."
+                    }),
+                    GuiMessage::ResetSettings,
+                ))))
+            }
+            GuiMessage::ResetSettings => {
+                let mut settings = Settings::default();
+                self.download_slots.resize(
+                    get_setting().max_concurrent_downloads,
+                    settings.max_concurrent_downloads,
+                );
+                let reset_package_data = self.state.reset_package_data;
+                SettingsService::update(|current| {
+                    // The locations belong to this machine, and its packages are still there.
+                    mem::swap(&mut settings.databases_dir, &mut current.databases_dir);
+                    mem::swap(&mut settings.packages_dir, &mut current.packages_dir);
+                    mem::swap(&mut settings.cache_dir, &mut current.cache_dir);
+                    mem::swap(&mut settings.local_builds, &mut current.local_builds);
+                    mem::swap(&mut settings.custom_packages, &mut current.custom_packages);
+                    settings.first_run = false;
+                    if !reset_package_data {
+                        mem::swap(&mut settings.default_package, &mut current.default_package);
+                        mem::swap(
+                            &mut settings.favourite_packages,
+                            &mut current.favourite_packages,
+                        );
+                        mem::swap(&mut settings.recent_files, &mut current.recent_files);
+                    }
+                    *current = settings;
+                });
+                log::info!("Restored the default settings.");
+
+                self.reload_settings_fields();
+                self.state.reset_package_data = false;
+                self.recent_files = get_setting().recent_files.to_vec();
+                self.sync();
+                self.update_message(GuiMessage::ShowNotification(
+                    String::from("Restored the default settings."),
+                    NOTIFICATION_DURATION,
+                ))
+            }
+            GuiMessage::ExportPackageList => {
+                let installed = self.releases.installed.to_vec();
+                self.export_packages(&installed)
+            }
+            GuiMessage::ImportPackageList => {
+                let path = match FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .show_open_single_file()
+                {
+                    Ok(Some(path)) => path,
+                    Ok(None) => return Command::none(),
+                    Err(e) => {
+                        log::error!("Couldn't show the file dialog: {}", e);
+                        return Command::none();
+                    }
+                };
+                let entries = match import_package_list(&path) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        self.state.error = Some(format!(
+                            "Couldn't import the package list from {}: {}",
+                            path.display(),
+                            e
+                        ));
+                        return Command::none();
+                    }
+                };
+
+                let mut to_install = Vec::new();
+                let mut already_installed = 0;
+                let mut not_found = Vec::new();
+                for entry in entries {
+                    if self
+                        .releases
+                        .installed
+                        .iter()
+                        .any(|package| package.name == entry.name)
+                    {
+                        already_installed += 1;
+                        continue;
+                    }
+                    match self.packages.iter().find(|package| {
+                        package.name == entry.name
+                            || (!entry.url.is_empty() && package.url == entry.url)
+                    }) {
+                        Some(package) => {
+                            if matches!(package.state, PackageState::Fetched) {
+                                to_install.push(package.index);
+                            }
+                        }
+                        None => not_found.push(entry.name),
+                    }
+                }
+
+                log::info!(
+                    "Importing {}: {} to install, {} already installed, {} not found.",
+                    path.display(),
+                    to_install.len(),
+                    already_installed,
+                    not_found.len()
+                );
+                if !not_found.is_empty() {
+                    self.state.error = Some(format!(
+                        "Couldn't find these packages among the fetched ones: {}",
+                        not_found.join(", ")
+                    ));
+                }
+
+                let notification = format!(
+                    "Installing {} package(s), {} already installed.",
+                    to_install.len(),
+                    already_installed
+                );
+                let mut commands: Vec<Command<GuiMessage>> = to_install
+                    .into_iter()
+                    .map(|index| {
+                        self.update_message(GuiMessage::PackageMessage((
+                            index,
+                            PackageMessage::Install,
+                        )))
+                    })
+                    .collect();
+                commands.push(self.update_message(GuiMessage::ShowNotification(
+                    notification,
+                    NOTIFICATION_DURATION,
+                )));
+                Command::batch(commands)
+            }
+            GuiMessage::ResetKeybindings => {
+                self.state.rebinding = None;
+                SettingsService::update(|settings| settings.keybindings = Keybindings::default());
                 Command::none()
             }
             GuiMessage::CheckConnection => {
@@ -923,8 +2728,361 @@ impl Gui {
             }
             GuiMessage::ConnectionChecked(()) => {
                 self.controls.checking_connection = false;
+                self.update_message(GuiMessage::ConnectionStatusChanged(
+                    CAN_CONNECT.load(Ordering::Relaxed),
+                ))
+            }
+            GuiMessage::PollConnection => {
+                if self.controls.checking_connection {
+                    Command::none()
+                } else {
+                    Command::perform(Gui::poll_connection(), GuiMessage::ConnectionStatusChanged)
+                }
+            }
+            GuiMessage::ConnectionStatusChanged(connection_ok) => {
+                if connection_ok != self.connection_ok {
+                    if connection_ok {
+                        log::info!("Connection restored.");
+                    } else {
+                        log::warn!("Connection lost.");
+                    }
+                    self.connection_ok = connection_ok;
+                }
                 Command::none()
             }
+            // The compact window isn't the size to open the whole launcher with.
+            GuiMessage::PackagesScrolled(offset) => {
+                self.state.packages_scroll_offset = offset;
+                if self.state.new_since_last_session.is_empty() {
+                    return Command::none();
+                }
+                let shown: Vec<usize> = shown_packages(
+                    &self.packages,
+                    &self.controls.search,
+                    &self.pinned,
+                    &self.state.package_sizes,
+                )
+                .into_iter()
+                .map(|package| package.index)
+                .collect();
+                // The list only tells how far down it is, so what's in view is estimated from the
+                // height of the window.
+                let in_view = (get_setting().window_height / APPROXIMATE_PACKAGE_HEIGHT) as usize;
+                let hidden = shown.len().saturating_sub(in_view);
+                let seen = (offset * hidden as f32).round() as usize + in_view;
+                for index in shown.into_iter().take(seen) {
+                    let package = &mut self.packages[index];
+                    if package.is_new_since_last_session {
+                        package.is_new_since_last_session = false;
+                        self.state.new_since_last_session.remove(&package.name);
+                    }
+                }
+                Command::none()
+            }
+            GuiMessage::SettingsScrolled(offset) => {
+                self.state.settings_scroll_offset = offset;
+                Command::none()
+            }
+            GuiMessage::WindowResized(_, _) if self.compact_mode => Command::none(),
+            GuiMessage::WindowResized(width, height) => {
+                SettingsService::update_unsaved(|settings| {
+                    settings.window_width = width;
+                    settings.window_height = height;
+                });
+                self.state.window_changed = Some(Instant::now());
+                Command::perform(
+                    Gui::debounce(self.state.window_changed.unwrap()),
+                    GuiMessage::SaveWindowGeometry,
+                )
+            }
+            GuiMessage::FileHovered => {
+                self.state.files_hovered = true;
+                Command::none()
+            }
+            GuiMessage::FileDropped(path) => {
+                let is_blend = matches!(
+                    path.extension().and_then(|extension| extension.to_str()),
+                    Some(extension) if extension.starts_with("blend")
+                );
+                if is_blend {
+                    if self.state.files_hovered {
+                        self.file_paths.clear();
+                        self.state.files_hovered = false;
+                    }
+                    self.file_paths.push(path.to_string_lossy().to_string());
+                }
+                Command::none()
+            }
+            GuiMessage::FilesHoveredLeft => {
+                self.state.files_hovered = false;
+                Command::none()
+            }
+            GuiMessage::WindowMoved(x, y) => {
+                let monitor = monitor_at(&self.state.monitors, x, y);
+                SettingsService::update_unsaved(|settings| {
+                    settings.window_x = Some(x);
+                    settings.window_y = Some(y);
+                    if let Some(monitor) = monitor {
+                        settings.window_monitor = monitor;
+                    }
+                });
+                self.state.window_changed = Some(Instant::now());
+                Command::perform(
+                    Gui::debounce(self.state.window_changed.unwrap()),
+                    GuiMessage::SaveWindowGeometry,
+                )
+            }
+            GuiMessage::SaveWindowGeometry(instant) => {
+                // Only the last of a burst of resize or move events gets saved.
+                if self.state.window_changed == Some(instant) {
+                    self.state.window_changed = None;
+                    SettingsService::save();
+                }
+                Command::none()
+            }
+            GuiMessage::LauncherUpdateAvailable(release) => {
+                self.state.launcher_release = release;
+                Command::none()
+            }
+            GuiMessage::UpdateLauncher => {
+                self.state.updating_launcher = true;
+                self.state.launcher_update_error = None;
+                Command::perform(
+                    LauncherUpdate::install(self.state.launcher_release.clone().unwrap()),
+                    GuiMessage::LauncherUpdated,
+                )
+            }
+            GuiMessage::LauncherUpdated(result) => {
+                self.state.updating_launcher = false;
+                match result {
+                    Ok(()) => LauncherUpdate::restart(),
+                    Err(e) => self.state.launcher_update_error = Some(e),
+                }
+                Command::none()
+            }
+        }
+    }
+
+    /// Stores the version range typed into the sidebar if it's valid, or shows why it isn't and
+    /// keeps the last valid one.
+    fn apply_version_range(&mut self) {
+        match Filters::validate_version_range(
+            &self.controls.min_version,
+            &self.controls.max_version,
+        ) {
+            Ok((min_version, max_version)) => {
+                self.controls.version_range_error = None;
+                SettingsService::update(|settings| {
+                    settings.filters.min_version = min_version;
+                    settings.filters.max_version = max_version;
+                });
+            }
+            Err(e) => self.controls.version_range_error = Some(e),
+        }
+    }
+
+    /// Stores the date range typed into the sidebar if it's valid, the same way as the version
+    /// range.
+    fn apply_date_range(&mut self) {
+        match Filters::validate_date_range(&self.controls.date_from, &self.controls.date_to) {
+            Ok((date_from, date_to)) => {
+                self.controls.date_range_error = None;
+                SettingsService::update(|settings| {
+                    settings.filters.date_from = date_from;
+                    settings.filters.date_to = date_to;
+                });
+            }
+            Err(e) => self.controls.date_range_error = Some(e),
+        }
+    }
+
+    /// The updates that can be installed, leaving out the ones already on their way.
+    fn pending_updates(&self) -> Vec<Package> {
+        self.packages
+            .iter()
+            .filter(|package| {
+                package.status == PackageStatus::Update
+                    && matches!(
+                        package.state,
+                        PackageState::Fetched | PackageState::Errored { .. }
+                    )
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Asks where to save the list of the given packages and saves it there.
+    fn export_packages(&mut self, packages: &[Package]) -> Command<GuiMessage> {
+        let path = match FileDialog::new()
+            .set_filename("ablavema_packages.json")
+            .add_filter("JSON", &["json"])
+            .show_save_single_file()
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => return Command::none(),
+            Err(e) => {
+                log::error!("Couldn't show the file dialog: {}", e);
+                return Command::none();
+            }
+        };
+        match export_package_list(packages, &path) {
+            Ok(()) => {
+                log::info!(
+                    "Exported {} package(s) to {}.",
+                    packages.len(),
+                    path.display()
+                );
+                self.update_message(GuiMessage::ShowNotification(
+                    String::from("Exported the package list."),
+                    NOTIFICATION_DURATION,
+                ))
+            }
+            Err(e) => {
+                self.state.error = Some(format!(
+                    "Couldn't export the package list to {}: {}",
+                    path.display(),
+                    e
+                ));
+                Command::none()
+            }
+        }
+    }
+
+    /// Answers the confirmation asked while in compact mode, or leaves it. Anything else is
+    /// handled like in the packages tab, only on the installed packages.
+    fn compact_key_pressed(&mut self, combo: &KeyCombo) -> Command<GuiMessage> {
+        if combo.ctrl || combo.alt || combo.shift {
+            return Command::none();
+        }
+
+        if self.state.confirm_modal.is_some() {
+            return match combo.key.as_str() {
+                "Y" | "Enter" => self.update_message(GuiMessage::ConfirmationAnswered(true)),
+                "N" | "Escape" => self.update_message(GuiMessage::ConfirmationAnswered(false)),
+                _ => Command::none(),
+            };
+        }
+
+        if *combo == KeyCombo::escape() {
+            return self.update_message(GuiMessage::LeaveCompactMode);
+        }
+
+        self.package_key_pressed(combo)
+    }
+
+    /// Moves through the shown packages with the arrow keys and acts on the focused one, which
+    /// has to be installed for anything but moving.
+    fn package_key_pressed(&mut self, combo: &KeyCombo) -> Command<GuiMessage> {
+        if (get_setting().tab != Tab::Packages && !self.compact_mode)
+            || self.is_loading
+            || self.state.confirm_modal.is_some()
+            || combo.ctrl
+            || combo.alt
+            || combo.shift
+        {
+            return Command::none();
+        }
+
+        let shown: Vec<usize> = if self.compact_mode {
+            compact_packages(&self.packages, &self.state.package_sizes)
+        } else {
+            shown_packages(
+                &self.packages,
+                &self.controls.search,
+                &self.pinned,
+                &self.state.package_sizes,
+            )
+        }
+        .into_iter()
+        .map(|package| package.index)
+        .collect();
+        let last = match shown.len().checked_sub(1) {
+            Some(last) => last,
+            None => {
+                self.focused_package_index = None;
+                return Command::none();
+            }
+        };
+        // The list may have shrunk since the focus was last moved.
+        let focused = self.focused_package_index.map(|focused| focused.min(last));
+
+        let index = match (combo.key.as_str(), focused) {
+            ("Up", _) => {
+                self.focused_package_index =
+                    Some(focused.map_or(last, |focused| focused.saturating_sub(1)));
+                return Command::none();
+            }
+            ("Down", _) => {
+                self.focused_package_index =
+                    Some(focused.map_or(0, |focused| (focused + 1).min(last)));
+                return Command::none();
+            }
+            (_, Some(focused)) => shown[focused],
+            (_, None) => return Command::none(),
+        };
+        let package = &self.packages[index];
+        if !matches!(package.state, PackageState::Installed) {
+            return Command::none();
+        }
+        let is_default_package = get_setting().active_default_package() == Some(package);
+
+        match combo.key.as_str() {
+            "Enter" => self.update_message(GuiMessage::PackageMessage((
+                index,
+                PackageMessage::OpenBlender,
+            ))),
+            "Space" => self.update_message(GuiMessage::PackageMessage((
+                index,
+                if is_default_package {
+                    PackageMessage::UnsetDefault
+                } else {
+                    PackageMessage::SetDefault
+                },
+            ))),
+            "Delete" => {
+                self.update_message(GuiMessage::ShowConfirmModal(Box::new(ConfirmModal::new(
+                    if get_setting().is_any_default_package(package) {
+                        String::from(
+                            "This is your default package. Uninstalling it will clear the default.",
+                        )
+                    } else {
+                        format!("Uninstall {}?", package.name)
+                    },
+                    GuiMessage::PackageMessage((index, PackageMessage::RemoveConfirmed)),
+                ))))
+            }
+            _ => Command::none(),
+        }
+    }
+
+    /// Refills the text fields and filters taken from the settings after they were all replaced
+    /// at once, leaving everything else from this session as it was.
+    fn reload_settings_fields(&mut self) {
+        self.state.proxy_url = get_setting().proxy_url.clone().unwrap_or_default();
+        self.state.proxy_bypass = get_setting().proxy_bypass.join(", ");
+        self.state.buildbot_api_url = get_setting().buildbot_api_url.clone();
+        self.state.naming_template = get_setting().naming_template.clone().unwrap_or_default();
+        self.state.copy_info_template = get_setting().copy_info_template.clone();
+        self.state.profile_drafts = GuiState::profile_drafts();
+        self.state.custom_font = match &get_setting().font_family {
+            FontFamily::Custom(name) => name.clone(),
+            _ => String::new(),
+        };
+        let controls = Controls::new();
+        self.controls.min_version = controls.min_version;
+        self.controls.max_version = controls.max_version;
+        self.controls.version_range_error = None;
+        self.controls.date_from = controls.date_from;
+        self.controls.date_to = controls.date_to;
+        self.controls.date_range_error = None;
+    }
+
+    /// Keeps track of the current packages location when moving to another one, as long as
+    /// there's something installed in it.
+    fn remember_packages_dir(&mut self, new_packages_dir: &Path) {
+        if get_setting().packages_dir != new_packages_dir && !self.releases.installed.is_empty() {
+            self.state.previous_packages_dir = Some(get_setting().packages_dir.clone());
+            self.state.move_packages_error = None;
         }
     }
 }
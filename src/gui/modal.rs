@@ -0,0 +1,79 @@
+use super::message::GuiMessage;
+use crate::settings::{get_setting, TEXT_SIZE};
+use iced::{
+    alignment::Horizontal,
+    pure::{
+        widget::{Button, Column, Container, Row, Text},
+        Element,
+    },
+    Alignment, Length,
+};
+
+/// A question covering the current tab until it's answered, along with the messages sent
+/// once it is.
+#[derive(Clone, Debug)]
+pub struct ConfirmModal {
+    pub title: String,
+    pub body: String,
+    pub confirm_label: String,
+    pub cancel_label: String,
+    pub on_confirm: GuiMessage,
+    /// Only needed when something has to happen on cancelling too.
+    pub on_cancel: Option<GuiMessage>,
+}
+
+impl ConfirmModal {
+    /// Asks whether to go ahead, with the usual title and buttons.
+    pub fn new(body: impl Into<String>, on_confirm: GuiMessage) -> Self {
+        Self {
+            title: String::from("Are you sure?"),
+            body: body.into(),
+            confirm_label: String::from("[Y] Confirm"),
+            cancel_label: String::from("[N] Cancel"),
+            on_confirm,
+            on_cancel: None,
+        }
+    }
+}
+
+pub fn confirm_modal_view(modal: &ConfirmModal) -> Element<'_, GuiMessage> {
+    let button = |label, confirmed| {
+        Button::new(Text::new(label).horizontal_alignment(Horizontal::Center))
+            .width(Length::Units(150))
+            .on_press(GuiMessage::ConfirmationAnswered(confirmed))
+            .style(get_setting().theme.tab_button())
+    };
+
+    Container::new(
+        Container::new(
+            Column::new()
+                .spacing(20)
+                .padding(20)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(&modal.title)
+                        .size(TEXT_SIZE * 2)
+                        .color(get_setting().theme.highlight_text()),
+                )
+                .push(
+                    Text::new(&modal.body)
+                        .width(Length::Fill)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(button(&modal.confirm_label, true))
+                        .push(button(&modal.cancel_label, false)),
+                ),
+        )
+        .max_width(500)
+        .style(get_setting().theme.dialog_container()),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x()
+    .center_y()
+    .style(get_setting().theme.backdrop_container())
+    .into()
+}
@@ -0,0 +1,156 @@
+//! Bringing the window back to the monitor it was last on.
+//!
+//! Iced doesn't tell which monitors there are, so winit is asked directly with an event loop of
+//! our own before Iced makes its one. That's not done on macOS, where the event loop sets up the
+//! application itself and a second one would replace the first one's delegate; there the saved
+//! position, being in desktop coordinates, is all that brings the window back.
+use iced::window::Position;
+
+/// Where a monitor is on the desktop, in the logical coordinates Iced reports window moves in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Monitor {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+    pub is_primary: bool,
+}
+
+impl Monitor {
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+
+    /// The position that centers a window of the given size on this monitor.
+    fn centered(&self, (width, height): (u32, u32)) -> Position {
+        Position::Specific(
+            self.x + (self.width - width as i32).max(0) / 2,
+            self.y + (self.height - height as i32).max(0) / 2,
+        )
+    }
+}
+
+/// The monitors in the order winit lists them, which is what `Settings::window_monitor` indexes.
+/// Empty when they can't be asked for.
+#[cfg(not(target_os = "macos"))]
+pub fn available_monitors() -> Vec<Monitor> {
+    use winit::event_loop::EventLoop;
+
+    let event_loop = EventLoop::new();
+    let primary = event_loop.primary_monitor();
+    event_loop
+        .available_monitors()
+        .map(|monitor| {
+            let position = monitor.position().to_logical::<i32>(monitor.scale_factor());
+            let size = monitor.size().to_logical::<i32>(monitor.scale_factor());
+            Monitor {
+                x: position.x,
+                y: position.y,
+                width: size.width,
+                height: size.height,
+                is_primary: primary.as_ref() == Some(&monitor),
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "macos")]
+pub fn available_monitors() -> Vec<Monitor> {
+    Vec::new()
+}
+
+/// Index of the monitor the window is on, going by its top left corner.
+pub fn monitor_at(monitors: &[Monitor], x: i32, y: i32) -> Option<usize> {
+    monitors.iter().position(|monitor| monitor.contains(x, y))
+}
+
+/// Where to open a window of the given size.
+///
+/// The saved position is used as long as it's on the saved monitor. If that monitor is still
+/// around but the position isn't on it, because the monitors were rearranged, the window is
+/// centered on it. If it's gone, the window is centered on the primary monitor. Without a list of
+/// monitors the saved position is used as is.
+pub fn window_position(
+    monitors: &[Monitor],
+    saved_monitor: usize,
+    saved_position: Option<(i32, i32)>,
+    size: (u32, u32),
+) -> Position {
+    let (x, y) = match saved_position {
+        Some(position) => position,
+        None => return Position::Default,
+    };
+    if monitors.is_empty() {
+        return Position::Specific(x, y);
+    }
+    match monitors.get(saved_monitor) {
+        Some(monitor) if monitor.contains(x, y) => Position::Specific(x, y),
+        Some(monitor) => monitor.centered(size),
+        None => match monitors.iter().find(|monitor| monitor.is_primary) {
+            Some(primary) => primary.centered(size),
+            None => Position::Default,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MONITORS: [Monitor; 2] = [
+        Monitor {
+            x: 0,
+            y: 0,
+            width: 1920,
+            height: 1080,
+            is_primary: true,
+        },
+        Monitor {
+            x: 1920,
+            y: 0,
+            width: 1280,
+            height: 1024,
+            is_primary: false,
+        },
+    ];
+
+    #[test]
+    fn saved_position_on_saved_monitor() {
+        assert_eq!(
+            window_position(&MONITORS, 1, Some((2000, 100)), (680, 620)),
+            Position::Specific(2000, 100)
+        );
+    }
+
+    #[test]
+    fn saved_position_off_saved_monitor_centers_on_it() {
+        assert_eq!(
+            window_position(&MONITORS, 1, Some((100, 100)), (680, 620)),
+            Position::Specific(1920 + 300, 202)
+        );
+    }
+
+    #[test]
+    fn missing_monitor_falls_back_to_primary() {
+        assert_eq!(
+            window_position(&MONITORS[..1], 1, Some((2000, 100)), (680, 620)),
+            Position::Specific(620, 230)
+        );
+    }
+
+    #[test]
+    fn no_monitors_keeps_saved_position() {
+        assert_eq!(
+            window_position(&[], 1, Some((2000, 100)), (680, 620)),
+            Position::Specific(2000, 100)
+        );
+        assert_eq!(window_position(&[], 0, None, (680, 620)), Position::Default);
+    }
+
+    #[test]
+    fn monitor_at_top_left_corner() {
+        assert_eq!(monitor_at(&MONITORS, 1919, 0), Some(0));
+        assert_eq!(monitor_at(&MONITORS, 1920, 0), Some(1));
+        assert_eq!(monitor_at(&MONITORS, 1920, 1024), None);
+    }
+}
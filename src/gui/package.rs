@@ -1,29 +1,74 @@
 use super::{install::Progress, Gui, GuiMessage};
 use crate::{
-    package::{Package, PackageState, PackageStatus},
-    settings::{get_setting, save_settings, set_setting, CAN_CONNECT, FETCHING, TEXT_SIZE},
+    helpers::{
+        format_relative_date, format_size, open_blender_sandboxed, open_folder, sandbox_dir,
+        version_label,
+    },
+    package::{BuildHealth, IntegrityStatus, Package, PackageState, PackageStatus},
+    settings::{get_setting, SettingsService, CAN_CONNECT, FETCHING, TEXT_SIZE},
 };
 use iced::{
     alignment::Horizontal,
     pure::{
-        widget::{Button, Column, Container, Row, Text},
+        widget::{
+            tooltip::Position, Button, Checkbox, Column, Container, Row, Text, TextInput, Tooltip,
+        },
         Element,
     },
-    Alignment, Command, Length, ProgressBar,
+    Alignment, Command, Length, ProgressBar, Space,
 };
 use std::sync::atomic::Ordering;
 
+/// Characters of a note shown before it gets truncated.
+const NOTE_LENGTH: usize = 60;
+/// Tags used before offered at once while tagging a package.
+const TAG_SUGGESTIONS: usize = 3;
+/// Roughly how tall a package is in the list, for telling which ones were scrolled into view.
+pub const APPROXIMATE_PACKAGE_HEIGHT: u32 = 100;
+
 #[derive(Clone, Debug)]
 pub enum PackageMessage {
     Install,
     InstallationProgress(Progress),
-    Cancel,
+    CancelInstall,
+    Retry,
     Remove,
+    RemoveConfirmed,
     OpenBlender,
     OpenBlenderWithFile,
     SetDefault,
     UnsetDefault,
-    Bookmark,
+    ToggleFavourite,
+    EditNote,
+    NoteChanged(String),
+    NoteSaved,
+    EditTags,
+    TagInputChanged(String),
+    TagAdded(String),
+    TagRemoved(usize),
+    TagsSaved,
+    OpenReleaseNotes,
+    CompareToggled(bool),
+    ShowChangelog,
+    GroupSelectionToggled(bool),
+    SelectionToggled(bool),
+    EditArgs,
+    ArgsChanged(String),
+    ArgsSaved,
+    VerifyIntegrity,
+    TestLaunch,
+    TogglePin,
+    OpenFolder,
+    CopyInfo,
+    OpenSandboxed,
+    EditEnv,
+    EnvKeyChanged(usize, String),
+    EnvValueChanged(usize, String),
+    EnvAdded,
+    EnvRemoved(usize),
+    /// Moves the entry one place up.
+    EnvMovedUp(usize),
+    EnvSaved,
 }
 
 impl Package {
@@ -34,20 +79,52 @@ impl Package {
                 GuiMessage::CheckAvailability,
             ),
             PackageMessage::InstallationProgress(progress) => match progress {
-                Progress::Started => {
-                    self.state = PackageState::Downloading { progress: 0.0 };
+                Progress::Started(download_size) => {
+                    self.download_size = download_size;
+                    self.state = PackageState::Downloading {
+                        progress: 0.0,
+                        downloaded: 0,
+                        bytes_per_sec: 0.0,
+                        eta_secs: None,
+                    };
                     Command::none()
                 }
-                Progress::DownloadProgress(progress) => {
+                Progress::DownloadProgress {
+                    progress,
+                    downloaded,
+                    bytes_per_sec,
+                    eta_secs,
+                    chunk_size,
+                } => {
+                    // Only kept in memory here, it's saved along with the installation result.
+                    SettingsService::update_unsaved(|settings| {
+                        settings.download_stats.total_bytes_downloaded += chunk_size
+                    });
                     if let PackageState::Downloading { .. } = self.state {
-                        self.state = PackageState::Downloading { progress };
+                        self.state = PackageState::Downloading {
+                            progress,
+                            downloaded,
+                            bytes_per_sec,
+                            eta_secs,
+                        };
                     }
                     Command::none()
                 }
                 Progress::FinishedDownloading => {
+                    self.state = PackageState::Verifying;
+                    Command::none()
+                }
+                Progress::ChecksumVerified | Progress::ChecksumUnavailable => {
                     self.state = PackageState::Extracting { progress: 0.0 };
                     Command::none()
                 }
+                Progress::ChecksumFailed(message) => {
+                    self.state = PackageState::Errored { message };
+                    Command::perform(
+                        Gui::pass_package(self.clone()),
+                        GuiMessage::InstallationStopped,
+                    )
+                }
                 Progress::ExtractionProgress(progress) => {
                     if let PackageState::Extracting { .. } = self.state {
                         self.state = PackageState::Extracting { progress };
@@ -62,16 +139,43 @@ impl Package {
                         GuiMessage::PackageInstalled,
                     )
                 }
+                Progress::Cancelled => {
+                    self.state = PackageState::default();
+                    Command::perform(
+                        Gui::pass_package(self.clone()),
+                        GuiMessage::InstallationStopped,
+                    )
+                }
                 Progress::Errored(message) => {
                     self.state = PackageState::Errored { message };
-                    Command::perform(Gui::pass_package(self.clone()), GuiMessage::CancelInstall)
+                    Command::perform(
+                        Gui::pass_package(self.clone()),
+                        GuiMessage::InstallationStopped,
+                    )
                 }
             },
-            PackageMessage::Cancel => {
-                self.state = PackageState::default();
+            PackageMessage::CancelInstall => {
                 Command::perform(Gui::pass_package(self.clone()), GuiMessage::CancelInstall)
             }
+            PackageMessage::Retry => {
+                self.state = PackageState::default();
+                self.update(PackageMessage::Install)
+            }
             PackageMessage::Remove => {
+                if get_setting().is_any_default_package(self) {
+                    Command::perform(
+                        Gui::pass_package(self.clone()),
+                        GuiMessage::ConfirmRemovingDefault,
+                    )
+                } else {
+                    self.update(PackageMessage::RemoveConfirmed)
+                }
+            }
+            PackageMessage::RemoveConfirmed if self.local_path.is_some() => Command::perform(
+                Gui::pass_package(self.clone()),
+                GuiMessage::LocalBuildNotRemovable,
+            ),
+            PackageMessage::RemoveConfirmed => {
                 self.remove();
                 Command::perform(Gui::pass_package(self.clone()), GuiMessage::PackageRemoved)
             }
@@ -84,53 +188,324 @@ impl Package {
                 GuiMessage::OpenBlenderWithFile,
             ),
             PackageMessage::SetDefault => {
-                set_setting().default_package = Some(self.clone());
-                save_settings();
+                SettingsService::update(|settings| {
+                    settings.set_default_package(Some(self.clone()))
+                });
                 Command::none()
             }
             PackageMessage::UnsetDefault => {
-                set_setting().default_package = None;
-                save_settings();
+                SettingsService::update(|settings| settings.set_default_package(None));
+                Command::none()
+            }
+            PackageMessage::OpenSandboxed => Command::perform(
+                open_blender_sandboxed(self.name.clone()),
+                GuiMessage::SandboxClosed,
+            ),
+            PackageMessage::OpenFolder => {
+                open_folder(&get_setting().packages_dir.join(&self.name));
+                Command::none()
+            }
+            PackageMessage::CopyInfo => {
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::CopyPackageInfo)
+            }
+            PackageMessage::TogglePin => {
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::TogglePin)
+            }
+            PackageMessage::ToggleFavourite => {
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::ToggleFavourite)
+            }
+            PackageMessage::EditNote => {
+                self.note_draft = Some(self.notes.clone().unwrap_or_default());
+                Command::none()
+            }
+            PackageMessage::NoteChanged(note) => {
+                self.note_draft = Some(note);
+                Command::none()
+            }
+            PackageMessage::NoteSaved => {
+                self.notes = self
+                    .note_draft
+                    .take()
+                    .map(|note| note.trim().to_string())
+                    .filter(|note| !note.is_empty());
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::NoteSaved)
+            }
+            PackageMessage::EditTags => {
+                self.tags_draft = Some(self.tags.clone());
+                self.tag_input.clear();
+                Command::none()
+            }
+            PackageMessage::TagInputChanged(tag) => {
+                self.tag_input = tag;
+                Command::none()
+            }
+            PackageMessage::TagAdded(tag) => {
+                let tag = tag.trim();
+                if let Some(draft) = &mut self.tags_draft {
+                    if !tag.is_empty() && !draft.iter().any(|draft_tag| draft_tag == tag) {
+                        draft.push(tag.to_string());
+                    }
+                }
+                self.tag_input.clear();
+                Command::none()
+            }
+            PackageMessage::TagRemoved(index) => {
+                if let Some(draft) = &mut self.tags_draft {
+                    if index < draft.len() {
+                        draft.remove(index);
+                    }
+                }
+                Command::none()
+            }
+            PackageMessage::TagsSaved => {
+                if let Some(mut tags) = self.tags_draft.take() {
+                    // Whatever was left typed in counts as well.
+                    let tag = self.tag_input.trim();
+                    if !tag.is_empty() && !tags.iter().any(|saved_tag| saved_tag == tag) {
+                        tags.push(tag.to_string());
+                    }
+                    self.tags = tags;
+                }
+                self.tag_input.clear();
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::TagsSaved)
+            }
+            PackageMessage::EditArgs => {
+                self.args_draft = Some(self.launch_args.join(" "));
+                Command::none()
+            }
+            PackageMessage::ArgsChanged(args) => {
+                self.args_draft = Some(args);
                 Command::none()
             }
-            PackageMessage::Bookmark => {
-                Command::perform(Gui::pass_package(self.clone()), GuiMessage::Bookmark)
+            PackageMessage::ArgsSaved => {
+                if let Some(args) = self.args_draft.take() {
+                    self.launch_args = args.split_whitespace().map(String::from).collect();
+                }
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::LaunchArgsSaved)
+            }
+            PackageMessage::VerifyIntegrity => {
+                Command::perform(Gui::verify_integrity(self.clone()), |(package, status)| {
+                    GuiMessage::IntegrityResult(package, status)
+                })
+            }
+            PackageMessage::TestLaunch => Command::perform(
+                Gui::test_launch(self.clone()),
+                |(package, success, output)| GuiMessage::TestLaunchResult(package, success, output),
+            ),
+            PackageMessage::EditEnv => {
+                self.env_draft = Some(self.env_vars.clone());
+                Command::none()
+            }
+            PackageMessage::EnvKeyChanged(index, key) => {
+                if let Some((entry_key, _)) = self
+                    .env_draft
+                    .as_mut()
+                    .and_then(|draft| draft.get_mut(index))
+                {
+                    *entry_key = key;
+                }
+                Command::none()
             }
+            PackageMessage::EnvValueChanged(index, value) => {
+                if let Some((_, entry_value)) = self
+                    .env_draft
+                    .as_mut()
+                    .and_then(|draft| draft.get_mut(index))
+                {
+                    *entry_value = value;
+                }
+                Command::none()
+            }
+            PackageMessage::EnvAdded => {
+                if let Some(draft) = &mut self.env_draft {
+                    draft.push((String::new(), String::new()));
+                }
+                Command::none()
+            }
+            PackageMessage::EnvRemoved(index) => {
+                if let Some(draft) = &mut self.env_draft {
+                    if index < draft.len() {
+                        draft.remove(index);
+                    }
+                }
+                Command::none()
+            }
+            PackageMessage::EnvMovedUp(index) => {
+                if let Some(draft) = &mut self.env_draft {
+                    if index > 0 && index < draft.len() {
+                        draft.swap(index - 1, index);
+                    }
+                }
+                Command::none()
+            }
+            PackageMessage::EnvSaved => {
+                if let Some(draft) = self.env_draft.take() {
+                    self.env_vars = draft
+                        .into_iter()
+                        .map(|(key, value)| (key.trim().to_string(), value))
+                        .filter(|(key, _)| !key.is_empty())
+                        .collect();
+                }
+                Command::perform(Gui::pass_package(self.clone()), GuiMessage::EnvVarsSaved)
+            }
+            PackageMessage::GroupSelectionToggled(selected) => {
+                Command::perform(Gui::pass_package(self.clone()), move |package| {
+                    GuiMessage::GroupSelectionChanged(package, selected)
+                })
+            }
+            PackageMessage::SelectionToggled(selected) => {
+                Command::perform(Gui::pass_package(self.clone()), move |package| {
+                    GuiMessage::PackageSelectionChanged(package, selected)
+                })
+            }
+            PackageMessage::CompareToggled(selected) => {
+                Command::perform(Gui::pass_package(self.clone()), move |package| {
+                    GuiMessage::CompareSelectionChanged(package, selected)
+                })
+            }
+            PackageMessage::ShowChangelog => Command::perform(
+                Gui::pass_package(self.clone()),
+                GuiMessage::ChangelogRequested,
+            ),
+            PackageMessage::OpenReleaseNotes => match &self.release_notes_url {
+                Some(url) => {
+                    Command::perform(Gui::pass_string(url.to_string()), GuiMessage::OpenBrowser)
+                }
+                None => Command::none(),
+            },
         }
     }
 
-    pub fn view(&self, file_exists: bool, is_odd: bool) -> Element<'_, PackageMessage> {
-        let is_default_package = get_setting().default_package.is_some()
-            && get_setting().default_package.clone().unwrap() == *self;
+    /// `compare_selected` is only set while in compare mode, and tells whether the package is
+    /// ticked for comparison. `group_selected` is the same while picking packages for groups,
+    /// and `multi_selected` while picking packages for the batch actions.
+    #[allow(clippy::too_many_arguments)]
+    pub fn view(
+        &self,
+        file_count: usize,
+        compare_selected: Option<bool>,
+        group_selected: Option<bool>,
+        multi_selected: Option<bool>,
+        pinned: bool,
+        is_odd: bool,
+        focused: bool,
+    ) -> Element<'_, PackageMessage> {
+        // While a profile is active, setting the default package sets the profile's.
+        let is_default_package = get_setting().active_default_package() == Some(self);
 
         let date_time = self.get_formatted_date_time();
 
-        let name = Row::new()
-            .spacing(10)
-            .push(
-                Text::new(&self.name)
+        let name = Row::new().spacing(10);
+
+        let name = match (compare_selected, &self.state) {
+            (Some(selected), PackageState::Installed) => name.push(
+                Checkbox::new(selected, "", PackageMessage::CompareToggled)
+                    .style(get_setting().theme),
+            ),
+            _ => name,
+        };
+
+        let name = match group_selected {
+            Some(selected) => name.push(
+                Checkbox::new(selected, "", PackageMessage::GroupSelectionToggled)
+                    .style(get_setting().theme),
+            ),
+            None => name,
+        };
+
+        let name = match multi_selected {
+            Some(selected) => name.push(
+                Checkbox::new(selected, "", PackageMessage::SelectionToggled)
+                    .style(get_setting().theme),
+            ),
+            None => name,
+        };
+
+        let name = name.push(
+            Text::new(&self.name)
+                .color(get_setting().theme.highlight_text())
+                .size(TEXT_SIZE + 10)
+                .width(Length::Fill),
+        );
+
+        let name = match &self.integrity {
+            Some(IntegrityStatus::ExecutableMissing) => name.push(
+                Text::new("[!] Executable missing")
+                    .color(get_setting().theme.highlight_text())
+                    .size(TEXT_SIZE + 4),
+            ),
+            Some(IntegrityStatus::Corrupted(problems)) => name.push(
+                Text::new(format!(
+                    "[!] {} problem{} found",
+                    problems.len(),
+                    if problems.len() == 1 { "" } else { "s" }
+                ))
+                .color(get_setting().theme.highlight_text())
+                .size(TEXT_SIZE + 4),
+            ),
+            Some(IntegrityStatus::Ok) | None => name,
+        };
+
+        let name = match self.test_launch {
+            Some(success) => name.push(
+                Row::new()
+                    .spacing(5)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Container::new(Space::new(Length::Units(10), Length::Units(10))).style(
+                            get_setting().theme.build_health_container(if success {
+                                BuildHealth::Green
+                            } else {
+                                BuildHealth::Red
+                            }),
+                        ),
+                    )
+                    .push(
+                        Text::new(if success { "Launches" } else { "Launch failed" })
+                            .size(TEXT_SIZE - 4),
+                    ),
+            ),
+            None => name,
+        };
+
+        let name = if pinned {
+            name.push(
+                Text::new("PINNED")
                     .color(get_setting().theme.highlight_text())
-                    .size(TEXT_SIZE + 10)
-                    .width(Length::Fill),
+                    .size(TEXT_SIZE + 4),
             )
+        } else {
+            name
+        };
+
+        let name = name
             .push(
-                Button::new(Text::new(if get_setting().bookmarks.contains(&self.name) {
-                    "[B]"
-                } else {
-                    "[M]"
-                }))
-                .on_press(PackageMessage::Bookmark)
+                Button::new(Text::new("[P]"))
+                    .on_press(PackageMessage::TogglePin)
+                    .style(get_setting().theme),
+            )
+            .push(
+                Button::new(Text::new(
+                    if get_setting().favourite_packages.contains(&self.name) {
+                        "[*]"
+                    } else {
+                        "[ ]"
+                    },
+                ))
+                .on_press(PackageMessage::ToggleFavourite)
                 .style(get_setting().theme),
             );
 
+        let version_label = version_label(&self.version.to_string());
         let details = Column::new()
             .push(
                 Row::new()
                     .align_items(Alignment::End)
                     .push(Text::new("Date: ").size(TEXT_SIZE - 4))
+                    .push(Text::new(date_time).color(get_setting().theme.highlight_text()))
                     .push(
-                        Text::new(date_time)
-                            .color(get_setting().theme.highlight_text())
+                        Text::new(format!("  ({})", self.get_relative_date()))
+                            .size(TEXT_SIZE - 4)
                             .width(Length::Fill),
                     ),
             )
@@ -144,27 +519,164 @@ impl Package {
                             .push(
                                 Text::new(self.version.to_string())
                                     .color(get_setting().theme.highlight_text()),
-                            ),
+                            )
+                            .push(match version_label {
+                                Some(label) => Row::new()
+                                    .push(Space::with_width(Length::Units(8)))
+                                    .push(tag_chip(label)),
+                                None => Row::new(),
+                            })
+                            .push(match self.build_health {
+                                Some(health) => Row::new()
+                                    .align_items(Alignment::Center)
+                                    .push(Space::with_width(Length::Units(8)))
+                                    .push(
+                                        Container::new(Space::new(
+                                            Length::Units(10),
+                                            Length::Units(10),
+                                        ))
+                                        .style(get_setting().theme.build_health_container(health)),
+                                    ),
+                                None => Row::new(),
+                            }),
                     )
+                    .push(if self.is_new_since_last_session {
+                        Row::new()
+                            .push(
+                                Container::new(Text::new("NEW").size(TEXT_SIZE - 4))
+                                    .padding([2, 6])
+                                    .style(get_setting().theme.new_since_last_session_container()),
+                            )
+                            .push(Space::with_width(Length::Units(8)))
+                    } else {
+                        Row::new()
+                    })
                     .push(
-                        Text::new(match self.status {
-                            PackageStatus::Update => "UPDATE   ",
-                            PackageStatus::New => "NEW   ",
-                            PackageStatus::Old => "",
+                        Text::new(match (&self.status, version_label) {
+                            _ if self.local_path.is_some() => String::from("LOCAL   "),
+                            (PackageStatus::Update, _) => String::from("UPDATE   "),
+                            (PackageStatus::New, Some(label)) => format!("NEW {}   ", label),
+                            (PackageStatus::New, None) => String::from("NEW   "),
+                            (PackageStatus::Old, _) => String::new(),
+                            (PackageStatus::External, _) => String::from("EXTERNAL   "),
                         })
                         .color(get_setting().theme.highlight_text())
                         .size(TEXT_SIZE + 4),
                     ),
+            );
+
+        let details = match &self.notes {
+            Some(note) => details.push(
+                Row::new()
+                    .align_items(Alignment::End)
+                    .push(Text::new("Note: ").size(TEXT_SIZE - 4))
+                    .push(
+                        Text::new(if note.chars().count() > NOTE_LENGTH {
+                            format!("{}...", note.chars().take(NOTE_LENGTH).collect::<String>())
+                        } else {
+                            note.clone()
+                        })
+                        .color(get_setting().theme.highlight_text()),
+                    ),
+            ),
+            None => details,
+        };
+
+        let details = if self.tags.is_empty() {
+            details
+        } else {
+            details.push(
+                self.tags.iter().fold(
+                    Row::new()
+                        .spacing(5)
+                        .align_items(Alignment::Center)
+                        .push(Text::new("Tags: ").size(TEXT_SIZE - 4)),
+                    |row, tag| row.push(tag_chip(tag)),
+                ),
             )
-            .push(
+        };
+
+        let details = if self.launch_args.is_empty() {
+            details
+        } else {
+            details.push(
                 Row::new()
                     .align_items(Alignment::End)
-                    .push(Text::new("Build: ").size(TEXT_SIZE - 4))
+                    .push(Text::new("Args: ").size(TEXT_SIZE - 4))
                     .push(
-                        Text::new(self.build_type.to_string())
+                        Text::new(self.launch_args.join(" "))
                             .color(get_setting().theme.highlight_text()),
                     ),
-            );
+            )
+        };
+
+        let details = if self.env_vars.is_empty() {
+            details
+        } else {
+            details.push(
+                Row::new()
+                    .align_items(Alignment::End)
+                    .push(Text::new("Env: ").size(TEXT_SIZE - 4))
+                    .push(
+                        Text::new(
+                            self.env_vars
+                                .iter()
+                                .map(|(key, value)| format!("{}={}", key, value))
+                                .collect::<Vec<String>>()
+                                .join(" "),
+                        )
+                        .color(get_setting().theme.highlight_text()),
+                    ),
+            )
+        };
+
+        let details = details.push(
+            Row::new()
+                .align_items(Alignment::End)
+                .push(Text::new("Build: ").size(TEXT_SIZE - 4))
+                .push(
+                    Text::new(self.build_type.to_string())
+                        .color(get_setting().theme.highlight_text()),
+                ),
+        );
+
+        let details = match (&self.state, self.last_opened) {
+            (PackageState::Installed, Some(last_opened)) => details.push(
+                Row::new()
+                    .align_items(Alignment::End)
+                    .push(Text::new("Last opened: ").size(TEXT_SIZE - 4))
+                    .push(
+                        Text::new(format_relative_date(&last_opened))
+                            .color(get_setting().theme.highlight_text()),
+                    ),
+            ),
+            (PackageState::Installed, None) => {
+                details.push(Text::new("Never opened").size(TEXT_SIZE - 4))
+            }
+            _ => details,
+        };
+
+        let details = match (&self.state, self.open_count) {
+            (PackageState::Installed, open_count) if open_count > 0 => details.push(
+                Text::new(format!(
+                    "Opened {} time{}",
+                    open_count,
+                    if open_count == 1 { "" } else { "s" }
+                ))
+                .size(TEXT_SIZE - 4),
+            ),
+            _ => details,
+        };
+
+        let details = match (&self.state, self.size) {
+            (PackageState::Installed, Some(size)) => details.push(
+                Row::new()
+                    .align_items(Alignment::End)
+                    .push(Text::new("Size: ").size(TEXT_SIZE - 4))
+                    .push(Text::new(format_size(size)).color(get_setting().theme.highlight_text())),
+            ),
+            _ => details,
+        };
 
         let button = |label, package_message: Option<PackageMessage>| {
             let button = Button::new(Text::new(label).horizontal_alignment(Horizontal::Center))
@@ -178,31 +690,85 @@ impl Package {
         };
 
         let controls: Element<'_, PackageMessage> = match &self.state {
-            PackageState::Fetched => Row::new()
-                .push(button(
+            PackageState::Fetched => {
+                let row = Row::new().spacing(10).push(button(
                     "[#] Install",
                     if CAN_CONNECT.load(Ordering::Relaxed) && !FETCHING.load(Ordering::Relaxed) {
                         Some(PackageMessage::Install)
                     } else {
                         None
                     },
-                ))
+                ));
+
+                // Updates are only marked as such when the build they update is installed.
+                if self.status == PackageStatus::Update {
+                    row.push(button(
+                        "[C] Changelog",
+                        CAN_CONNECT
+                            .load(Ordering::Relaxed)
+                            .then_some(PackageMessage::ShowChangelog),
+                    ))
+                    .into()
+                } else {
+                    row.into()
+                }
+            }
+            PackageState::Queued => Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new("Queued")
+                        .width(Length::Fill)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .push(
+                    Button::new(Text::new("[X] Cancel"))
+                        .on_press(PackageMessage::CancelInstall)
+                        .style(get_setting().theme),
+                )
                 .into(),
-            PackageState::Downloading { progress } => Row::new()
+            PackageState::Downloading {
+                progress,
+                downloaded,
+                bytes_per_sec,
+                eta_secs,
+            } => Row::new()
                 .spacing(10)
                 .align_items(Alignment::Center)
-                .push(Text::new(format!("Downloading... {:.2}%", progress)))
+                .push(Text::new(format!(
+                    "Downloading... {} - {:.1} MB/s - ETA {}",
+                    match self.download_size {
+                        Some(download_size) => {
+                            format!("{:.1}% of {}", progress, format_size(download_size))
+                        }
+                        None => format_size(*downloaded),
+                    },
+                    bytes_per_sec / 1024.0 / 1024.0,
+                    match *eta_secs {
+                        Some(eta) if eta >= 60 => format!("{} m {} s", eta / 60, eta % 60),
+                        Some(eta) => format!("{} s", eta),
+                        None => String::from("unknown"),
+                    }
+                )))
                 .push(
                     ProgressBar::new(0.0..=100.0, *progress)
                         .width(Length::Fill)
                         .style(get_setting().theme),
                 )
                 .push(
-                    Button::new(Text::new("Cancel"))
-                        .on_press(PackageMessage::Cancel)
+                    Button::new(Text::new("[X] Cancel"))
+                        .on_press(PackageMessage::CancelInstall)
                         .style(get_setting().theme),
                 )
                 .into(),
+            PackageState::Verifying => Row::new()
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new("Verifying...")
+                        .width(Length::Fill)
+                        .horizontal_alignment(Horizontal::Center),
+                )
+                .into(),
             PackageState::Extracting { progress } => {
                 // TODO: Figure out why cancelling doesn't work for extraction.
                 // It does visually get cancelled, but the extraction keeps going in the
@@ -221,7 +787,7 @@ impl Package {
                         )
                         /* .push(
                             Button::new(cancel_button, Text::new("Cancel"))
-                                .on_press(PackageMessage::Cancel)
+                                .on_press(PackageMessage::CancelInstall)
                                 .style(theme),
                         ) */
                         .into()
@@ -237,19 +803,166 @@ impl Package {
                         )
                         /* .push(
                             Button::new(cancel_button, Text::new("Cancel"))
-                                .on_press(PackageMessage::Cancel)
+                                .on_press(PackageMessage::CancelInstall)
                                 .style(theme),
                         ) */
                         .into()
                 }
             }
+            PackageState::Installed if self.note_draft.is_some() => Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(
+                        "Note",
+                        self.note_draft.as_deref().unwrap_or_default(),
+                        PackageMessage::NoteChanged,
+                    )
+                    .on_submit(PackageMessage::NoteSaved)
+                    .padding(5)
+                    .width(Length::Fill)
+                    .style(get_setting().theme),
+                )
+                .push(
+                    Button::new(Text::new("[S] Save"))
+                        .on_press(PackageMessage::NoteSaved)
+                        .style(get_setting().theme),
+                )
+                .into(),
+            PackageState::Installed if self.tags_draft.is_some() => {
+                let row = self.tags_draft.iter().flatten().enumerate().fold(
+                    Row::new().spacing(5).align_items(Alignment::Center),
+                    |row, (index, tag)| {
+                        row.push(tag_chip(tag)).push(
+                            Button::new(Text::new("[X]"))
+                                .on_press(PackageMessage::TagRemoved(index))
+                                .style(get_setting().theme),
+                        )
+                    },
+                );
+
+                let row = row.push(
+                    TextInput::new("New tag", &self.tag_input, PackageMessage::TagInputChanged)
+                        .on_submit(PackageMessage::TagAdded(self.tag_input.clone()))
+                        .padding(5)
+                        .width(Length::Fill)
+                        .style(get_setting().theme),
+                );
+
+                // Offers the tags used before that start with what's typed so far.
+                let input = self.tag_input.trim().to_lowercase();
+                let row = get_setting()
+                    .known_tags
+                    .iter()
+                    .filter(|tag| {
+                        tag.to_lowercase().starts_with(&input)
+                            && !self.tags_draft.iter().flatten().any(|draft| draft == *tag)
+                    })
+                    .take(TAG_SUGGESTIONS)
+                    .fold(row, |row, tag| {
+                        row.push(
+                            Button::new(Text::new(format!("[+] {}", tag)))
+                                .on_press(PackageMessage::TagAdded(tag.clone()))
+                                .style(get_setting().theme),
+                        )
+                    });
+
+                row.push(
+                    Button::new(Text::new("[S] Save"))
+                        .on_press(PackageMessage::TagsSaved)
+                        .style(get_setting().theme),
+                )
+                .into()
+            }
+            PackageState::Installed if self.args_draft.is_some() => Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    TextInput::new(
+                        "--factory-startup --no-window-focus",
+                        self.args_draft.as_deref().unwrap_or_default(),
+                        PackageMessage::ArgsChanged,
+                    )
+                    .on_submit(PackageMessage::ArgsSaved)
+                    .padding(5)
+                    .width(Length::Fill)
+                    .style(get_setting().theme),
+                )
+                .push(
+                    Button::new(Text::new("[S] Save"))
+                        .on_press(PackageMessage::ArgsSaved)
+                        .style(get_setting().theme),
+                )
+                .into(),
+            PackageState::Installed if self.env_draft.is_some() => self
+                .env_draft
+                .iter()
+                .flatten()
+                .enumerate()
+                .fold(Column::new().spacing(5), |column, (index, (key, value))| {
+                    column.push(
+                        Row::new()
+                            .spacing(10)
+                            .align_items(Alignment::Center)
+                            .push(
+                                TextInput::new("NAME", key, move |key| {
+                                    PackageMessage::EnvKeyChanged(index, key)
+                                })
+                                .padding(5)
+                                .width(Length::FillPortion(1))
+                                .style(get_setting().theme),
+                            )
+                            .push(
+                                TextInput::new("value", value, move |value| {
+                                    PackageMessage::EnvValueChanged(index, value)
+                                })
+                                .on_submit(PackageMessage::EnvSaved)
+                                .padding(5)
+                                .width(Length::FillPortion(2))
+                                .style(get_setting().theme),
+                            )
+                            .push({
+                                let up = Button::new(Text::new("[^]")).style(get_setting().theme);
+                                if index > 0 {
+                                    up.on_press(PackageMessage::EnvMovedUp(index))
+                                } else {
+                                    up
+                                }
+                            })
+                            .push(
+                                Button::new(Text::new("[X]"))
+                                    .on_press(PackageMessage::EnvRemoved(index))
+                                    .style(get_setting().theme),
+                            ),
+                    )
+                })
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .push(button("[+] Add", Some(PackageMessage::EnvAdded)))
+                        .push(button("[S] Save", Some(PackageMessage::EnvSaved))),
+                )
+                .into(),
             PackageState::Installed => {
                 let button1 =
                     Row::new().push(button("[=] Open", Some(PackageMessage::OpenBlender)));
 
+                let button1 = button1.push(
+                    Tooltip::new(
+                        button("[S2] Sandbox", Some(PackageMessage::OpenSandboxed)),
+                        sandbox_dir(&self.name).display(),
+                        Position::Top,
+                    )
+                    .style(get_setting().theme.info_container()),
+                );
+
                 let button2 = button1.push(button(
-                    "[+] Open file",
-                    if file_exists {
+                    if file_count > 1 {
+                        "[+] Open files"
+                    } else {
+                        "[+] Open file"
+                    },
+                    if file_count > 0 {
                         Some(PackageMessage::OpenBlenderWithFile)
                     } else {
                         None
@@ -271,6 +984,20 @@ impl Package {
 
                 button3
                     .spacing(10)
+                    .push(button("[N] Note", Some(PackageMessage::EditNote)))
+                    .push(button("[T] Tags", Some(PackageMessage::EditTags)))
+                    .push(button("[A] Args", Some(PackageMessage::EditArgs)))
+                    .push(button("[E] Env", Some(PackageMessage::EditEnv)))
+                    .push(button("[F] Folder", Some(PackageMessage::OpenFolder)))
+                    .push(button("[C] Copy", Some(PackageMessage::CopyInfo)))
+                    .push(button("[V] Verify", Some(PackageMessage::VerifyIntegrity)))
+                    .push(button("[L] Test", Some(PackageMessage::TestLaunch)))
+                    .push(button(
+                        "[?] Notes",
+                        self.release_notes_url
+                            .as_ref()
+                            .map(|_| PackageMessage::OpenReleaseNotes),
+                    ))
                     .push(button("[X] Uninstall", Some(PackageMessage::Remove)))
                     .into()
             }
@@ -280,30 +1007,51 @@ impl Package {
                 .spacing(10)
                 .align_items(Alignment::Center)
                 .push(Text::new(format!("Error: {}.", error_message)).width(Length::Fill))
-                .push(
-                    Button::new(Text::new("Retry"))
-                        // TODO: Disable if can't connect or fetching.
-                        .on_press(PackageMessage::Install)
-                        .style(get_setting().theme),
-                )
+                .push({
+                    let button = Button::new(Text::new("[#] Retry")).style(get_setting().theme);
+
+                    if CAN_CONNECT.load(Ordering::Relaxed) && !FETCHING.load(Ordering::Relaxed) {
+                        button.on_press(PackageMessage::Retry)
+                    } else {
+                        button
+                    }
+                })
                 .into(),
         };
 
-        Container::new(
+        let package = Container::new(
             Column::new().spacing(10).push(name).push(details).push(
                 Container::new(controls)
                     .height(Length::Units(40))
                     .center_y(),
             ),
         )
+        .width(Length::Fill)
         .style({
-            if is_odd {
+            if focused {
+                get_setting().theme.focused_container(is_odd)
+            } else if is_odd {
                 get_setting().theme.odd_container()
             } else {
                 get_setting().theme.even_container()
             }
         })
-        .padding(10)
-        .into()
+        .padding(10);
+
+        Row::new()
+            .align_items(Alignment::Fill)
+            .push(
+                Container::new(Space::with_width(Length::Units(5)))
+                    .height(Length::Fill)
+                    .style(get_setting().theme.build_type_container(&self.build)),
+            )
+            .push(package)
+            .into()
     }
 }
+
+fn tag_chip(tag: &str) -> Container<'_, PackageMessage> {
+    Container::new(Text::new(tag).size(TEXT_SIZE - 4))
+        .padding([2, 6])
+        .style(get_setting().theme.tag_container(tag))
+}
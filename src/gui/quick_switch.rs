@@ -0,0 +1,106 @@
+use super::message::GuiMessage;
+use crate::{
+    helpers::format_relative_date,
+    package::Package,
+    settings::{get_setting, TEXT_SIZE},
+};
+use iced::{
+    alignment::Horizontal,
+    pure::{
+        widget::{Button, Column, Container, Row, Text},
+        Element,
+    },
+    Alignment, Length,
+};
+use std::cmp::Reverse;
+
+/// The keys picking the packages, in the order they're listed.
+const KEYS: [char; 35] = [
+    '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J',
+    'K', 'L', 'M', 'N', 'O', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
+/// The installed packages offered, the most recently opened first, as many as there are keys.
+pub fn quick_switch_packages(installed: &[Package]) -> Vec<&Package> {
+    let mut packages: Vec<&Package> = installed.iter().collect();
+    packages.sort_by_key(|package| (Reverse(package.last_opened), Reverse(package.date)));
+    packages.truncate(KEYS.len());
+    packages
+}
+
+/// Where the package picked by a key is in the list, going by the name of its `iced` key code.
+/// The number row and the numpad both work for the digits.
+pub fn key_index(key: &str) -> Option<usize> {
+    let key = key
+        .strip_prefix("Key")
+        .or_else(|| key.strip_prefix("Numpad"))
+        .unwrap_or(key);
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(key), None) => KEYS.iter().position(|candidate| *candidate == key),
+        _ => None,
+    }
+}
+
+/// Covers the whole window, launched with the modifier key held down while bypassing the
+/// launcher.
+pub fn quick_switch_view<'a>(packages: &[&'a Package]) -> Element<'a, GuiMessage> {
+    let list =
+        packages
+            .iter()
+            .zip(KEYS)
+            .fold(Column::new().spacing(5), |column, (package, key)| {
+                let last_opened = match &package.last_opened {
+                    Some(last_opened) => format_relative_date(last_opened),
+                    None => String::from("never opened"),
+                };
+                column.push(
+                    Button::new(
+                        Row::new()
+                            .spacing(10)
+                            .align_items(Alignment::Center)
+                            .push(
+                                Text::new(format!("[{}]", key))
+                                    .color(get_setting().theme.highlight_text()),
+                            )
+                            .push(Text::new(&package.name).width(Length::Fill))
+                            .push(Text::new(last_opened).size(TEXT_SIZE - 4)),
+                    )
+                    .width(Length::Fill)
+                    .on_press(GuiMessage::QuickSwitchLaunch(package.name.clone()))
+                    .style(get_setting().theme),
+                )
+            });
+
+    Container::new(
+        Container::new(
+            Column::new()
+                .spacing(20)
+                .padding(20)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new("Quick switch")
+                        .size(TEXT_SIZE * 2)
+                        .color(get_setting().theme.highlight_text()),
+                )
+                .push(list)
+                .push(
+                    Button::new(
+                        Text::new("[Esc] Open the launcher")
+                            .horizontal_alignment(Horizontal::Center),
+                    )
+                    .width(Length::Units(250))
+                    .on_press(GuiMessage::QuickSwitchDismissed)
+                    .style(get_setting().theme.tab_button()),
+                ),
+        )
+        .max_width(600)
+        .style(get_setting().theme.info_container()),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x()
+    .center_y()
+    .style(get_setting().theme.normal_container())
+    .into()
+}
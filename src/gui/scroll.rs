@@ -0,0 +1,151 @@
+//! A `Scrollable` that opens where it was left.
+//!
+//! The pure `Scrollable` of Iced 0.4 keeps its offset inside the widget tree, so it starts at the top
+//! every time its tab is rebuilt and there's no way to set it from the application. This wraps one
+//! and starts its state snapped to an offset that's kept in `GuiState` through `on_scroll`.
+use iced_native::{
+    event::{self, Event},
+    layout::{self, Layout},
+    mouse, renderer,
+    widget::scrollable,
+    Clipboard, Length, Point, Rectangle, Shell,
+};
+use iced_pure::{
+    overlay,
+    widget::{
+        tree::{self, Tree},
+        Scrollable,
+    },
+    Element, Widget,
+};
+use std::marker::PhantomData;
+
+/// Marks the scroll position of the packages list.
+pub struct PackagesScroll;
+
+/// Marks the scroll position of the settings tab.
+pub struct SettingsScroll;
+
+/// A `Scrollable` starting at `offset`, from 0 to 1.
+///
+/// `Key` tells apart the state of each list, so that switching between tabs that happen to have
+/// their lists in the same place of the widget tree doesn't carry one's position to the other.
+pub struct RestoredScrollable<'a, Key, Message, Renderer> {
+    scrollable: Scrollable<'a, Message, Renderer>,
+    offset: f32,
+    key: PhantomData<Key>,
+}
+
+impl<'a, Key, Message, Renderer> RestoredScrollable<'a, Key, Message, Renderer> {
+    pub fn new(scrollable: Scrollable<'a, Message, Renderer>, offset: f32) -> Self {
+        Self {
+            scrollable,
+            offset,
+            key: PhantomData,
+        }
+    }
+}
+
+impl<'a, Key, Message, Renderer> Widget<Message, Renderer>
+    for RestoredScrollable<'a, Key, Message, Renderer>
+where
+    Key: 'static,
+    Renderer: iced_native::Renderer,
+{
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<Key>()
+    }
+
+    fn state(&self) -> tree::State {
+        let mut state = scrollable::State::new();
+        state.snap_to(self.offset);
+        tree::State::new(state)
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.scrollable.children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.scrollable.diff(tree)
+    }
+
+    fn width(&self) -> Length {
+        self.scrollable.width()
+    }
+
+    fn height(&self) -> Length {
+        Widget::height(&self.scrollable)
+    }
+
+    fn layout(&self, renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        self.scrollable.layout(renderer, limits)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut Renderer,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+    ) {
+        self.scrollable
+            .draw(tree, renderer, style, layout, cursor_position, viewport)
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        renderer: &Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        self.scrollable.on_event(
+            tree,
+            event,
+            layout,
+            cursor_position,
+            renderer,
+            clipboard,
+            shell,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor_position: Point,
+        viewport: &Rectangle,
+        renderer: &Renderer,
+    ) -> mouse::Interaction {
+        self.scrollable
+            .mouse_interaction(tree, layout, cursor_position, viewport, renderer)
+    }
+
+    fn overlay<'b>(
+        &'b self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &Renderer,
+    ) -> Option<overlay::Element<'b, Message, Renderer>> {
+        self.scrollable.overlay(tree, layout, renderer)
+    }
+}
+
+impl<'a, Key, Message, Renderer> From<RestoredScrollable<'a, Key, Message, Renderer>>
+    for Element<'a, Message, Renderer>
+where
+    Key: 'static,
+    Message: 'a,
+    Renderer: 'a + iced_native::Renderer,
+{
+    fn from(scrollable: RestoredScrollable<'a, Key, Message, Renderer>) -> Self {
+        Element::new(scrollable)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::package::Package;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Clone, Copy, Debug, Eq, Deserialize, PartialEq, Serialize)]
 pub enum SortBy {
@@ -9,19 +10,40 @@ pub enum SortBy {
     DateDescending,
     VersionAscending,
     VersionDescending,
+    SizeAscending,
+    SizeDescending,
+    LastOpenedDescending,
+    OpenCountDescending,
 }
 
 impl SortBy {
-    pub const ALL: [SortBy; 6] = [
+    pub const ALL: [SortBy; 10] = [
         SortBy::NameAscending,
         SortBy::NameDescending,
         SortBy::DateAscending,
         SortBy::DateDescending,
         SortBy::VersionAscending,
         SortBy::VersionDescending,
+        SortBy::SizeAscending,
+        SortBy::SizeDescending,
+        SortBy::LastOpenedDescending,
+        SortBy::OpenCountDescending,
     ];
 
-    pub fn get_ordering(&self, a: &Package, b: &Package) -> std::cmp::Ordering {
+    pub fn is_by_size(&self) -> bool {
+        matches!(self, SortBy::SizeAscending | SortBy::SizeDescending)
+    }
+
+    /// `sizes` holds the cached disk usage of installed packages, falling back to the size
+    /// found when syncing for those installed since. Packages without a size go first.
+    pub fn get_ordering(
+        &self,
+        a: &Package,
+        b: &Package,
+        sizes: &HashMap<String, u64>,
+    ) -> std::cmp::Ordering {
+        let size = |package: &Package| sizes.get(&package.name).copied().or(package.size);
+
         match self {
             SortBy::NameAscending => Ord::cmp(&a.name, &b.name),
             SortBy::NameDescending => Ord::cmp(&a.name, &b.name).reverse(),
@@ -29,6 +51,11 @@ impl SortBy {
             SortBy::DateDescending => Ord::cmp(&a.date, &b.date).reverse(),
             SortBy::VersionAscending => Ord::cmp(&a.version, &b.version),
             SortBy::VersionDescending => Ord::cmp(&a.version, &b.version).reverse(),
+            SortBy::SizeAscending => Ord::cmp(&size(a), &size(b)),
+            SortBy::SizeDescending => Ord::cmp(&size(a), &size(b)).reverse(),
+            // Never opened packages are `None`, which sorts first and so ends up last.
+            SortBy::LastOpenedDescending => Ord::cmp(&a.last_opened, &b.last_opened).reverse(),
+            SortBy::OpenCountDescending => Ord::cmp(&a.open_count, &b.open_count).reverse(),
         }
     }
 }
@@ -51,6 +78,10 @@ impl std::fmt::Display for SortBy {
                 SortBy::DateDescending => " Date [D]",
                 SortBy::VersionAscending => " Version [A]",
                 SortBy::VersionDescending => " Version [D]",
+                SortBy::SizeAscending => " Size [A]",
+                SortBy::SizeDescending => " Size [D]",
+                SortBy::LastOpenedDescending => " Last opened",
+                SortBy::OpenCountDescending => " Most opened",
             }
         )
     }
@@ -1,5 +1,10 @@
+#[cfg(feature = "gui")]
+use crate::package::{Build, BuildHealth};
+#[cfg(feature = "gui")]
 use iced::{
-    pure::widget::{button, checkbox, container, pick_list, progress_bar, radio, rule},
+    pure::widget::{
+        button, checkbox, container, pick_list, progress_bar, radio, rule, slider, text_input,
+    },
     Color,
 };
 use serde::{Deserialize, Serialize};
@@ -7,6 +12,7 @@ use serde::{Deserialize, Serialize};
 /// Creates a const Color. Takes values from 0 to 255.
 /// First argument is the const name.
 /// Then Red, Green, Blue, and optionally Alpha.
+#[cfg(feature = "gui")]
 macro_rules! const_color {
     ($const_name:ident, $red:expr, $green:expr, $blue:expr) => {
         pub const $const_name: Color = Color::from_rgb(
@@ -31,6 +37,7 @@ pub enum Theme {
     Dark,
 }
 
+#[cfg(feature = "gui")]
 impl Theme {
     pub const ALL: [Theme; 2] = [Theme::Light, Theme::Dark];
 
@@ -77,6 +84,21 @@ impl Theme {
         }
     }
 
+    /// Dims everything behind a modal.
+    pub fn backdrop_container(&self) -> Box<dyn container::StyleSheet> {
+        match self {
+            Theme::Light => light::ContainerBackdrop.into(),
+            Theme::Dark => dark::ContainerBackdrop.into(),
+        }
+    }
+
+    pub fn dialog_container(&self) -> Box<dyn container::StyleSheet> {
+        match self {
+            Theme::Light => light::ContainerDialog.into(),
+            Theme::Dark => dark::ContainerDialog.into(),
+        }
+    }
+
     pub fn sidebar_container(&self) -> Box<dyn container::StyleSheet> {
         match self {
             Theme::Light => light::ContainerSidebar.into(),
@@ -98,6 +120,62 @@ impl Theme {
         }
     }
 
+    /// A package picked with the arrow keys.
+    pub fn focused_container(&self, is_odd: bool) -> Box<dyn container::StyleSheet> {
+        match self {
+            Theme::Light => light::ContainerFocused(if is_odd {
+                light::ODD_BACKGROUND
+            } else {
+                light::EVEN_BACKGROUND
+            })
+            .into(),
+            Theme::Dark => dark::ContainerFocused(if is_odd {
+                dark::ODD_BACKGROUND
+            } else {
+                dark::EVEN_BACKGROUND
+            })
+            .into(),
+        }
+    }
+
+    /// The stripe on the left of a package, coloured by the kind of build so the list can be
+    /// scanned without reading.
+    pub fn build_type_container(&self, build: &Build) -> Box<dyn container::StyleSheet> {
+        match self {
+            Theme::Light => light::ContainerBuildType(light::build_type_color(build)).into(),
+            Theme::Dark => dark::ContainerBuildType(dark::build_type_color(build)).into(),
+        }
+    }
+
+    /// The dot next to the version of a package, coloured by how its build did on the buildbot.
+    pub fn build_health_container(&self, health: BuildHealth) -> Box<dyn container::StyleSheet> {
+        match self {
+            Theme::Light => light::ContainerBuildHealth(light::build_health_color(health)).into(),
+            Theme::Dark => dark::ContainerBuildHealth(dark::build_health_color(health)).into(),
+        }
+    }
+
+    /// The chip a tag is shown in. A tag keeps its colour, which is picked going by its name.
+    pub fn tag_container(&self, tag: &str) -> Box<dyn container::StyleSheet> {
+        let index = tag.bytes().map(usize::from).sum::<usize>();
+        match self {
+            Theme::Light => {
+                light::ContainerTag(light::TAG_COLORS[index % light::TAG_COLORS.len()]).into()
+            }
+            Theme::Dark => {
+                dark::ContainerTag(dark::TAG_COLORS[index % dark::TAG_COLORS.len()]).into()
+            }
+        }
+    }
+
+    /// The badge of a package that turned up since the last session, in a colour no tag gets.
+    pub fn new_since_last_session_container(&self) -> Box<dyn container::StyleSheet> {
+        match self {
+            Theme::Light => light::ContainerTag(light::NEW_SINCE_LAST_SESSION).into(),
+            Theme::Dark => dark::ContainerTag(dark::NEW_SINCE_LAST_SESSION).into(),
+        }
+    }
+
     pub fn status_container(&self) -> Box<dyn container::StyleSheet> {
         match self {
             Theme::Light => light::ContainerStatus.into(),
@@ -119,6 +197,7 @@ impl Default for Theme {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn container::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -128,6 +207,7 @@ impl From<Theme> for Box<dyn container::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn radio::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -137,6 +217,7 @@ impl From<Theme> for Box<dyn radio::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn button::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -146,6 +227,7 @@ impl From<Theme> for Box<dyn button::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn progress_bar::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -155,6 +237,7 @@ impl From<Theme> for Box<dyn progress_bar::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn checkbox::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -164,6 +247,7 @@ impl From<Theme> for Box<dyn checkbox::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn pick_list::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -173,6 +257,7 @@ impl From<Theme> for Box<dyn pick_list::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
 impl From<Theme> for Box<dyn rule::StyleSheet> {
     fn from(theme: Theme) -> Self {
         match theme {
@@ -182,9 +267,33 @@ impl From<Theme> for Box<dyn rule::StyleSheet> {
     }
 }
 
+#[cfg(feature = "gui")]
+impl From<Theme> for Box<dyn slider::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Light => light::Slider.into(),
+            Theme::Dark => dark::Slider.into(),
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
+impl From<Theme> for Box<dyn text_input::StyleSheet> {
+    fn from(theme: Theme) -> Self {
+        match theme {
+            Theme::Light => light::TextInput.into(),
+            Theme::Dark => dark::TextInput.into(),
+        }
+    }
+}
+
+#[cfg(feature = "gui")]
 mod light {
+    use crate::package::{Build, BuildHealth};
     use iced::{
-        pure::widget::{button, checkbox, container, pick_list, progress_bar, radio, rule},
+        pure::widget::{
+            button, checkbox, container, pick_list, progress_bar, radio, rule, slider, text_input,
+        },
         Color, Vector,
     };
 
@@ -204,6 +313,17 @@ mod light {
     const_color!(STATUS_BACKGROUND, 255, 0, 0);
     const_color!(TEXT, 26, 26, 26);
     const_color!(ACTIVE_TEXT, 0, 0, 0);
+    const_color!(DAILY, 230, 126, 0);
+    const_color!(BRANCHED, 60, 160, 60);
+    const_color!(STABLE, 50, 110, 210);
+    const_color!(LTS, 200, 160, 0);
+    const_color!(ARCHIVED, 120, 120, 120);
+    const_color!(HEALTH_GREEN, 40, 160, 60);
+    const_color!(HEALTH_YELLOW, 220, 180, 0);
+    const_color!(HEALTH_RED, 210, 40, 40);
+    const_color!(HEALTH_UNKNOWN, 120, 120, 120);
+    const_color!(BACKDROP, 110, 110, 110);
+    const_color!(NEW_SINCE_LAST_SESSION, 150, 70, 200);
 
     pub struct Button;
     impl button::StyleSheet for Button {
@@ -323,6 +443,29 @@ mod light {
         }
     }
 
+    pub struct ContainerBackdrop;
+    impl container::StyleSheet for ContainerBackdrop {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: BACKDROP.into(),
+                ..Container.style()
+            }
+        }
+    }
+
+    pub struct ContainerDialog;
+    impl container::StyleSheet for ContainerDialog {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: INFO_BACKGROUND.into(),
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: ACTIVE,
+                ..Container.style()
+            }
+        }
+    }
+
     pub struct ContainerSidebar;
     impl container::StyleSheet for ContainerSidebar {
         fn style(&self) -> container::Style {
@@ -353,6 +496,75 @@ mod light {
         }
     }
 
+    pub struct ContainerFocused(pub Color);
+    impl container::StyleSheet for ContainerFocused {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: self.0.into(),
+                border_width: 2.0,
+                border_color: ACTIVE,
+                ..Container.style()
+            }
+        }
+    }
+
+    pub fn build_type_color(build: &Build) -> Color {
+        match build {
+            Build::DailyLatest(_) => DAILY,
+            Build::ExperimentalLatest(_) | Build::PatchLatest(_) => BRANCHED,
+            Build::StableLatest => STABLE,
+            Build::Lts(_) => LTS,
+            Build::DailyArchive(_)
+            | Build::ExperimentalArchive(_)
+            | Build::PatchArchive(_)
+            | Build::StableArchive => ARCHIVED,
+        }
+    }
+
+    pub fn build_health_color(health: BuildHealth) -> Color {
+        match health {
+            BuildHealth::Green => HEALTH_GREEN,
+            BuildHealth::Yellow => HEALTH_YELLOW,
+            BuildHealth::Red => HEALTH_RED,
+            BuildHealth::Unknown => HEALTH_UNKNOWN,
+        }
+    }
+
+    pub struct ContainerBuildHealth(pub Color);
+    impl container::StyleSheet for ContainerBuildHealth {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: self.0.into(),
+                border_radius: 5.0,
+                ..Container.style()
+            }
+        }
+    }
+
+    pub struct ContainerBuildType(pub Color);
+    impl container::StyleSheet for ContainerBuildType {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: self.0.into(),
+                ..Container.style()
+            }
+        }
+    }
+
+    pub const TAG_COLORS: [Color; 5] = [DAILY, BRANCHED, STABLE, LTS, HEALTH_RED];
+
+    pub struct ContainerTag(pub Color);
+    impl container::StyleSheet for ContainerTag {
+        fn style(&self) -> container::Style {
+            container::Style {
+                text_color: ACTIVE_TEXT.into(),
+                background: self.0.into(),
+                border_radius: 5.0,
+                ..Container.style()
+            }
+        }
+    }
+
     pub struct ContainerStatus;
     impl container::StyleSheet for ContainerStatus {
         fn style(&self) -> container::Style {
@@ -438,11 +650,89 @@ mod light {
             }
         }
     }
+
+    pub struct Slider;
+    impl slider::StyleSheet for Slider {
+        fn active(&self) -> slider::Style {
+            slider::Style {
+                rail_colors: (ACTIVE, INACTIVE),
+                handle: slider::Handle {
+                    shape: slider::HandleShape::Rectangle {
+                        width: 8,
+                        border_radius: 4.0,
+                    },
+                    color: INACTIVE,
+                    border_width: 1.0,
+                    border_color: ACTIVE,
+                },
+            }
+        }
+
+        fn hovered(&self) -> slider::Style {
+            let active = self.active();
+
+            slider::Style {
+                handle: slider::Handle {
+                    color: HOVERED,
+                    ..active.handle
+                },
+                ..active
+            }
+        }
+
+        fn dragging(&self) -> slider::Style {
+            let active = self.active();
+
+            slider::Style {
+                handle: slider::Handle {
+                    color: ACTIVE,
+                    ..active.handle
+                },
+                ..active
+            }
+        }
+    }
+
+    pub struct TextInput;
+    impl text_input::StyleSheet for TextInput {
+        fn active(&self) -> text_input::Style {
+            text_input::Style {
+                background: PICK_LIST_BACKGROUND.into(),
+                border_radius: 5.0,
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            }
+        }
+
+        fn focused(&self) -> text_input::Style {
+            text_input::Style {
+                border_width: 1.0,
+                border_color: ACTIVE,
+                ..self.active()
+            }
+        }
+
+        fn placeholder_color(&self) -> Color {
+            INACTIVE
+        }
+
+        fn value_color(&self) -> Color {
+            ACTIVE_TEXT
+        }
+
+        fn selection_color(&self) -> Color {
+            ACTIVE
+        }
+    }
 }
 
+#[cfg(feature = "gui")]
 mod dark {
+    use crate::package::{Build, BuildHealth};
     use iced::{
-        pure::widget::{button, checkbox, container, pick_list, progress_bar, radio, rule},
+        pure::widget::{
+            button, checkbox, container, pick_list, progress_bar, radio, rule, slider, text_input,
+        },
         Color, Vector,
     };
 
@@ -462,6 +752,17 @@ mod dark {
     const_color!(STATUS_BACKGROUND, 255, 0, 0);
     const_color!(TEXT, 217, 217, 217);
     const_color!(ACTIVE_TEXT, 255, 255, 255);
+    const_color!(DAILY, 255, 152, 36);
+    const_color!(BRANCHED, 98, 196, 98);
+    const_color!(STABLE, 86, 146, 240);
+    const_color!(LTS, 232, 192, 40);
+    const_color!(ARCHIVED, 140, 140, 140);
+    const_color!(HEALTH_GREEN, 80, 200, 100);
+    const_color!(HEALTH_YELLOW, 240, 200, 40);
+    const_color!(HEALTH_RED, 240, 80, 80);
+    const_color!(HEALTH_UNKNOWN, 140, 140, 140);
+    const_color!(BACKDROP, 25, 25, 25);
+    const_color!(NEW_SINCE_LAST_SESSION, 175, 110, 235);
 
     pub struct Button;
     impl button::StyleSheet for Button {
@@ -581,6 +882,29 @@ mod dark {
         }
     }
 
+    pub struct ContainerBackdrop;
+    impl container::StyleSheet for ContainerBackdrop {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: BACKDROP.into(),
+                ..Container.style()
+            }
+        }
+    }
+
+    pub struct ContainerDialog;
+    impl container::StyleSheet for ContainerDialog {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: INFO_BACKGROUND.into(),
+                border_radius: 5.0,
+                border_width: 1.0,
+                border_color: ACTIVE,
+                ..Container.style()
+            }
+        }
+    }
+
     pub struct ContainerSidebar;
     impl container::StyleSheet for ContainerSidebar {
         fn style(&self) -> container::Style {
@@ -611,6 +935,75 @@ mod dark {
         }
     }
 
+    pub struct ContainerFocused(pub Color);
+    impl container::StyleSheet for ContainerFocused {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: self.0.into(),
+                border_width: 2.0,
+                border_color: ACTIVE,
+                ..Container.style()
+            }
+        }
+    }
+
+    pub fn build_type_color(build: &Build) -> Color {
+        match build {
+            Build::DailyLatest(_) => DAILY,
+            Build::ExperimentalLatest(_) | Build::PatchLatest(_) => BRANCHED,
+            Build::StableLatest => STABLE,
+            Build::Lts(_) => LTS,
+            Build::DailyArchive(_)
+            | Build::ExperimentalArchive(_)
+            | Build::PatchArchive(_)
+            | Build::StableArchive => ARCHIVED,
+        }
+    }
+
+    pub fn build_health_color(health: BuildHealth) -> Color {
+        match health {
+            BuildHealth::Green => HEALTH_GREEN,
+            BuildHealth::Yellow => HEALTH_YELLOW,
+            BuildHealth::Red => HEALTH_RED,
+            BuildHealth::Unknown => HEALTH_UNKNOWN,
+        }
+    }
+
+    pub struct ContainerBuildHealth(pub Color);
+    impl container::StyleSheet for ContainerBuildHealth {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: self.0.into(),
+                border_radius: 5.0,
+                ..Container.style()
+            }
+        }
+    }
+
+    pub struct ContainerBuildType(pub Color);
+    impl container::StyleSheet for ContainerBuildType {
+        fn style(&self) -> container::Style {
+            container::Style {
+                background: self.0.into(),
+                ..Container.style()
+            }
+        }
+    }
+
+    pub const TAG_COLORS: [Color; 5] = [DAILY, BRANCHED, STABLE, LTS, HEALTH_RED];
+
+    pub struct ContainerTag(pub Color);
+    impl container::StyleSheet for ContainerTag {
+        fn style(&self) -> container::Style {
+            container::Style {
+                text_color: ACTIVE_TEXT.into(),
+                background: self.0.into(),
+                border_radius: 5.0,
+                ..Container.style()
+            }
+        }
+    }
+
     pub struct ContainerStatus;
     impl container::StyleSheet for ContainerStatus {
         fn style(&self) -> container::Style {
@@ -696,4 +1089,78 @@ mod dark {
             }
         }
     }
+
+    pub struct Slider;
+    impl slider::StyleSheet for Slider {
+        fn active(&self) -> slider::Style {
+            slider::Style {
+                rail_colors: (ACTIVE, INACTIVE),
+                handle: slider::Handle {
+                    shape: slider::HandleShape::Rectangle {
+                        width: 8,
+                        border_radius: 4.0,
+                    },
+                    color: INACTIVE,
+                    border_width: 1.0,
+                    border_color: ACTIVE,
+                },
+            }
+        }
+
+        fn hovered(&self) -> slider::Style {
+            let active = self.active();
+
+            slider::Style {
+                handle: slider::Handle {
+                    color: HOVERED,
+                    ..active.handle
+                },
+                ..active
+            }
+        }
+
+        fn dragging(&self) -> slider::Style {
+            let active = self.active();
+
+            slider::Style {
+                handle: slider::Handle {
+                    color: ACTIVE,
+                    ..active.handle
+                },
+                ..active
+            }
+        }
+    }
+
+    pub struct TextInput;
+    impl text_input::StyleSheet for TextInput {
+        fn active(&self) -> text_input::Style {
+            text_input::Style {
+                background: PICK_LIST_BACKGROUND.into(),
+                border_radius: 5.0,
+                border_width: 0.0,
+                border_color: Color::TRANSPARENT,
+            }
+        }
+
+        fn focused(&self) -> text_input::Style {
+            text_input::Style {
+                border_width: 1.0,
+                border_color: ACTIVE,
+                ..self.active()
+            }
+        }
+
+        fn placeholder_color(&self) -> Color {
+            INACTIVE
+        }
+
+        fn value_color(&self) -> Color {
+            ACTIVE_TEXT
+        }
+
+        fn selection_color(&self) -> Color {
+            ACTIVE
+        }
+    }
 }
@@ -1,8 +1,14 @@
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "gui")]
 pub mod about;
+#[cfg(feature = "gui")]
+pub mod logs;
+#[cfg(feature = "gui")]
 pub mod packages;
 pub mod recent_files;
+#[cfg(feature = "gui")]
 pub mod self_updater;
+#[cfg(feature = "gui")]
 pub mod settings;
 
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -11,6 +17,7 @@ pub enum Tab {
     Packages,
     Settings,
     SelfUpdater,
+    Logs,
     About,
 }
 
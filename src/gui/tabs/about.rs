@@ -1,19 +1,20 @@
 use super::Tab;
 use crate::{
-    gui::message::GuiMessage,
+    gui::{extra::GuiState, message::GuiMessage},
+    helpers::format_size,
     settings::{get_setting, TEXT_SIZE},
 };
 use clap::crate_version;
 use iced::{
     pure::{
-        widget::{Button, Column, Container, Row, Text},
+        widget::{Button, Column, Container, Row, Scrollable, Text},
         Element,
     },
     Alignment, Length, Space,
 };
 
 impl Tab {
-    pub fn about_body() -> Element<'static, GuiMessage> {
+    pub fn about_body(state: &GuiState) -> Element<'_, GuiMessage> {
         let link = |label, url| {
             Row::new()
                 .spacing(10)
@@ -30,11 +31,78 @@ impl Tab {
                 )
         };
 
+        let build_info = |label, value| {
+            Row::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(label)
+                        .width(Length::Units(100))
+                        .color(get_setting().theme.highlight_text()),
+                )
+                .push(Text::new(value))
+        };
+
+        let credits = include_str!(env!("ABLAVEMA_CREDITS_PATH"))
+            .lines()
+            .filter_map(|line| line.split_once(' '))
+            .fold(Column::new().padding(10), |column, (name, version)| {
+                column.push(
+                    Row::new()
+                        .spacing(10)
+                        .push(Text::new(name).width(Length::Units(200)))
+                        .push(
+                            Text::new(version)
+                                .width(Length::Fill)
+                                .color(get_setting().theme.highlight_text()),
+                        ),
+                )
+            });
+
+        let launcher_update: Element<'_, GuiMessage> = match &state.launcher_release {
+            Some(release) => Container::new(
+                Row::new()
+                    .padding(10)
+                    .spacing(10)
+                    .align_items(Alignment::Center)
+                    .push(
+                        Text::new(match &state.launcher_update_error {
+                            Some(error) => format!("Update failed: {}.", error),
+                            None => format!("Ablavema {} is available!", release.version),
+                        })
+                        .width(Length::Fill),
+                    )
+                    .push(
+                        Button::new(Text::new("Release notes"))
+                            .on_press(GuiMessage::OpenBrowser(release.url.clone()))
+                            .style(get_setting().theme),
+                    )
+                    .push({
+                        let button = Button::new(Text::new(if state.updating_launcher {
+                            "Updating..."
+                        } else {
+                            "[U] Update and restart"
+                        }))
+                        .style(get_setting().theme);
+
+                        if state.updating_launcher {
+                            button
+                        } else {
+                            button.on_press(GuiMessage::UpdateLauncher)
+                        }
+                    }),
+            )
+            .width(Length::Fill)
+            .style(get_setting().theme.info_container())
+            .into(),
+            None => Space::with_height(Length::Units(10)).into(),
+        };
+
         Container::new(
             Column::new()
                 .spacing(10)
                 .align_items(Alignment::Center)
-                .push(Space::with_height(Length::Units(10)))
+                .push(launcher_update)
                 .push(
                     Row::new()
                         .spacing(10)
@@ -49,7 +117,7 @@ impl Tab {
                         .push(Space::with_height(Length::Units(30)))
                         .push(link(
                             "Repository:",
-                            String::from("https://github.com/AlexChaplinBraz/Ablavema"),
+                            String::from(env!("CARGO_PKG_REPOSITORY")),
                         ))
                         .push(link(
                             "Discord:",
@@ -62,8 +130,56 @@ impl Tab {
                         .push(link(
                             "Donate:",
                             String::from("https://donate.alexchaplinbraz.com"),
-                        )),
-                ),
+                        ))
+                        .push(Space::with_height(Length::Units(10)))
+                        .push(build_info("Commit:", env!("ABLAVEMA_GIT_HASH")))
+                        .push(build_info("Built on:", env!("ABLAVEMA_BUILD_DATE"))),
+                )
+                .push(Space::with_height(Length::Units(20)))
+                .push(Text::new("Usage statistics").size(TEXT_SIZE + 4))
+                .push(
+                    Row::new()
+                        .spacing(30)
+                        .align_items(Alignment::Center)
+                        .push(
+                            Column::new()
+                                .spacing(10)
+                                .push(build_info(
+                                    "Downloaded:",
+                                    &format_size(
+                                        get_setting().download_stats.total_bytes_downloaded,
+                                    ),
+                                ))
+                                .push(build_info(
+                                    "Installed:",
+                                    &format!(
+                                        "{} packages",
+                                        get_setting().download_stats.total_packages_installed
+                                    ),
+                                ))
+                                .push(build_info(
+                                    "Removed:",
+                                    &format!(
+                                        "{} packages",
+                                        get_setting().download_stats.total_packages_removed
+                                    ),
+                                )),
+                        )
+                        .push(
+                            Button::new(Text::new("Reset stats"))
+                                .on_press(GuiMessage::ResetStats)
+                                .style(get_setting().theme),
+                        ),
+                )
+                .push(Space::with_height(Length::Units(20)))
+                .push(Text::new("Built with").size(TEXT_SIZE + 4))
+                .push(
+                    Container::new(Scrollable::new(credits))
+                        .width(Length::Units(600))
+                        .height(Length::Fill)
+                        .style(get_setting().theme.info_container()),
+                )
+                .push(Space::with_height(Length::Units(10))),
         )
         .height(Length::Fill)
         .width(Length::Fill)
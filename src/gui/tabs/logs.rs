@@ -0,0 +1,62 @@
+use super::Tab;
+use crate::{
+    gui::message::GuiMessage,
+    logger::get_logs,
+    settings::{get_setting, TEXT_SIZE},
+};
+use iced::{
+    pure::{
+        widget::{Button, Column, Container, Row, Scrollable, Text},
+        Element,
+    },
+    Alignment, Length,
+};
+
+impl Tab {
+    pub fn logs_body<'a>() -> Element<'a, GuiMessage> {
+        let logs = get_logs();
+
+        let controls = Container::new(
+            Row::new()
+                .padding(10)
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(Text::new(format!("{} lines", logs.len())).width(Length::Fill))
+                .push(
+                    Button::new(Text::new("[C] Copy to clipboard"))
+                        .on_press(GuiMessage::LogsCopied)
+                        .style(get_setting().theme),
+                )
+                .push(
+                    Button::new(Text::new("[X] Clear"))
+                        .on_press(GuiMessage::LogsCleared)
+                        .style(get_setting().theme),
+                ),
+        )
+        .width(Length::Fill)
+        .style(get_setting().theme.info_container());
+
+        let log_view: Element<'_, GuiMessage> = if logs.is_empty() {
+            Container::new(Text::new("Nothing logged yet").size(TEXT_SIZE * 2))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .center_x()
+                .center_y()
+                .into()
+        } else {
+            Scrollable::new(
+                Container::new(Text::new(logs.join("\n")).size(TEXT_SIZE - 2))
+                    .width(Length::Fill)
+                    .padding(10),
+            )
+            .height(Length::Fill)
+            .into()
+        };
+
+        Container::new(Column::new().push(controls).push(log_view))
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .style(get_setting().theme.normal_container())
+            .into()
+    }
+}
@@ -1,30 +1,121 @@
 use super::Tab;
 use crate::{
-    gui::{controls::Controls, message::GuiMessage},
-    package::Package,
+    gui::{
+        controls::Controls,
+        extra::GuiState,
+        message::GuiMessage,
+        scroll::{PackagesScroll, RestoredScrollable},
+        tabs::recent_files::{RecentFile, RecentFileChoice},
+    },
+    helpers::format_file_paths,
+    package::{Package, PackageState},
     releases::UpdateCount,
-    settings::{get_setting, FETCHING, TEXT_SIZE},
+    settings::{
+        get_setting, CAN_CONNECT, FETCHING, LOADING_STAGE, LOADING_STAGES, NO_PROFILE, TEXT_SIZE,
+    },
 };
 use iced::{
+    alignment::Horizontal,
     pure::{
-        widget::{Button, Column, Container, Row, Scrollable, Text},
+        widget::{
+            Button, Checkbox, Column, Container, PickList, ProgressBar, Row, Scrollable, Text,
+        },
         Element,
     },
     Alignment, Length, Space,
 };
 use itertools::Itertools;
-use std::sync::atomic::Ordering;
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    sync::atomic::Ordering,
+};
+
+/// How many of the most recently opened files are offered in the info panel.
+const RECENT_FILES_SHOWN: usize = 10;
+const SPINNER: [char; 4] = ['|', '/', '-', '\\'];
+
+/// How tall the install queue can get before it scrolls.
+const QUEUE_MAX_HEIGHT: u32 = 150;
+
+/// The packages left by the filters and the search, in the order they're listed.
+pub fn shown_packages<'a>(
+    packages: &'a [Package],
+    search: &str,
+    pinned: &HashSet<String>,
+    package_sizes: &HashMap<String, u64>,
+) -> Vec<&'a Package> {
+    packages
+        .iter()
+        .filter(|package| get_setting().filters.matches(package, search, pinned))
+        .sorted_by(|a, b| get_setting().sort_by.get_ordering(a, b, package_sizes))
+        .collect()
+}
 
 impl<'a> Tab {
+    /// Shown in place of the packages until the first fetch is done.
+    pub fn loading_body(spinner_frame: usize) -> Element<'a, GuiMessage> {
+        Container::new(
+            Column::new()
+                .spacing(10)
+                .align_items(Alignment::Center)
+                .push(
+                    Text::new(format!(
+                        "{} Loading releases…",
+                        SPINNER[spinner_frame % SPINNER.len()]
+                    ))
+                    .size(TEXT_SIZE * 2),
+                )
+                .push(Text::new(format!(
+                    "Fetching {}",
+                    LOADING_STAGES[LOADING_STAGE.load(Ordering::Relaxed)]
+                ))),
+        )
+        .height(Length::Fill)
+        .width(Length::Fill)
+        .center_x()
+        .center_y()
+        .style(get_setting().theme.normal_container())
+        .into()
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn packages_body(
         packages: &'a [Package],
-        file_path: Option<String>,
+        file_paths: &'a [String],
+        recent_files: &'a [RecentFile],
         update_count: UpdateCount,
-        file_exists: bool,
         controls: &'a Controls,
+        pinned: &'a HashSet<String>,
+        state: &'a GuiState,
+        downloads_paused: bool,
+        focused_package_index: Option<usize>,
+        selected_packages: &'a HashSet<String>,
+        rate_limited: bool,
+        queue: Vec<&'a Package>,
     ) -> Element<'a, GuiMessage> {
+        let minutes_to_check = get_setting()
+            .minutes_between_updates
+            .saturating_sub(state.elapsed_minutes);
         // TODO: Use real icons for the buttons.
         // TODO: Add tooltips.
+        let installed_size = packages
+            .iter()
+            .filter_map(|package| match package.state {
+                PackageState::Installed => package.size,
+                _ => None,
+            })
+            .sum();
+        let default_package = get_setting()
+            .launch_default_package()
+            .map(|package| package.name.clone());
+        let selected_recent_file = match file_paths {
+            [file_path] => recent_files
+                .iter()
+                .find(|recent_file| recent_file.path.display().to_string() == *file_path),
+            _ => None,
+        };
+
         let button = |label, message: Option<GuiMessage>| {
             let button = Button::new(Text::new(label)).style(get_setting().theme);
 
@@ -44,21 +135,37 @@ impl<'a> Tab {
                         .align_items(Alignment::Center)
                         .push(button(
                             "[=]",
-                            if get_setting().default_package.is_some() {
-                                Some(GuiMessage::OpenBlender(
-                                    get_setting().default_package.clone().unwrap().name,
-                                ))
-                            } else {
-                                None
-                            },
+                            default_package.clone().map(GuiMessage::OpenBlender),
                         ))
                         .push(Text::new("Default package:"))
                         .push(
-                            Text::new(match get_setting().default_package.clone() {
-                                Some(package) => package.name,
-                                None => String::from("not set"),
-                            })
+                            Text::new(
+                                default_package
+                                    .clone()
+                                    .unwrap_or_else(|| String::from("not set")),
+                            )
                             .color(get_setting().theme.highlight_text()),
+                        )
+                        .push(Space::with_width(Length::Fill))
+                        .push(
+                            PickList::new(
+                                iter::once(String::from(NO_PROFILE))
+                                    .chain(
+                                        get_setting()
+                                            .profiles
+                                            .iter()
+                                            .map(|profile| profile.name.clone()),
+                                    )
+                                    .collect::<Vec<String>>(),
+                                Some(
+                                    get_setting()
+                                        .active_profile
+                                        .clone()
+                                        .unwrap_or_else(|| String::from(NO_PROFILE)),
+                                ),
+                                GuiMessage::ProfileSelected,
+                            )
+                            .style(get_setting().theme.normal_pick_list()),
                         ),
                 )
                 .push(
@@ -67,45 +174,92 @@ impl<'a> Tab {
                         .align_items(Alignment::Center)
                         .push(button(
                             "[+]",
-                            if file_path.is_some() && get_setting().default_package.is_some() {
-                                Some(GuiMessage::OpenBlenderWithFile(
-                                    get_setting().default_package.clone().unwrap().name,
-                                ))
-                            } else {
-                                None
+                            match &default_package {
+                                Some(package) if !file_paths.is_empty() => {
+                                    Some(GuiMessage::OpenBlenderWithFile(package.clone()))
+                                }
+                                _ => None,
                             },
                         ))
-                        .push(Text::new("File:"))
+                        .push(Text::new(if file_paths.len() > 1 {
+                            "Files:"
+                        } else {
+                            "File:"
+                        }))
                         .push(
-                            Text::new(match &file_path {
-                                Some(file_path) => file_path,
-                                None => "none",
-                            })
-                            .color(get_setting().theme.highlight_text()),
+                            Text::new(format_file_paths(file_paths))
+                                .color(get_setting().theme.highlight_text()),
                         )
+                        .push(Text::new(match &selected_recent_file {
+                            Some(recent_file) if recent_file.differs_from_default() => {
+                                format!("[!] last used with: {}", recent_file.last_opened_with)
+                            }
+                            Some(recent_file) => {
+                                format!("(last used with: {})", recent_file.last_opened_with)
+                            }
+                            None => String::new(),
+                        }))
                         .push(Space::with_width(Length::Fill))
                         .push(
-                            Button::new(Text::new("Select file"))
+                            Button::new(Text::new("Select files"))
                                 .on_press(GuiMessage::SelectFile)
                                 .style(get_setting().theme),
                         ),
+                )
+                .push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                        .push(Text::new("Recent:"))
+                        .push(
+                            PickList::new(
+                                recent_files
+                                    .iter()
+                                    .take(RECENT_FILES_SHOWN)
+                                    .map(RecentFileChoice::from)
+                                    .collect::<Vec<RecentFileChoice>>(),
+                                selected_recent_file.map(RecentFileChoice::from),
+                                |choice| GuiMessage::RecentFileSelected(choice.path),
+                            )
+                            .placeholder("Select a recent file")
+                            .width(Length::Fill)
+                            .style(get_setting().theme.normal_pick_list()),
+                        ),
                 ),
         )
         .width(Length::Fill)
         .style(get_setting().theme.info_container())
         .into();
 
+        let shown = shown_packages(packages, &controls.search, pinned, &state.package_sizes);
+
         let packages: Element<'_, GuiMessage> = {
             let mut package_count: u16 = 0;
             let filtered_packages = Container::new(
-                packages
+                shown
                     .iter()
-                    .filter(|package| get_setting().filters.matches(package))
-                    .sorted_by(|a, b| get_setting().sort_by.get_ordering(a, b))
                     .fold(Column::new(), |column, package| {
+                        let focused = focused_package_index == Some(package_count as usize);
                         package_count += 1;
                         let index = package.index;
-                        let element = package.view(file_exists, package_count & 1 != 0);
+                        let compare_selected = controls
+                            .compare_mode
+                            .then(|| controls.compare_selection.contains(&package.name));
+                        let group_selected = controls
+                            .group_mode
+                            .then(|| controls.group_selection.contains(&package.name));
+                        let multi_selected = controls
+                            .multi_select
+                            .then(|| selected_packages.contains(&package.name));
+                        let element = package.view(
+                            file_paths.len(),
+                            compare_selected,
+                            group_selected,
+                            multi_selected,
+                            pinned.contains(&package.name),
+                            package_count & 1 != 0,
+                            focused,
+                        );
                         column.push(
                             element
                                 .map(move |message| GuiMessage::PackageMessage((index, message))),
@@ -131,23 +285,183 @@ impl<'a> Tab {
                 .center_y()
                 .style(get_setting().theme)
                 .into()
-            } else {
-                Container::new(Scrollable::new(filtered_packages))
+            } else if controls.multi_select {
+                let all_selected = shown
+                    .iter()
+                    .all(|package| selected_packages.contains(&package.name));
+                let header = Container::new(
+                    Checkbox::new(
+                        all_selected,
+                        format!("Select all [{} selected]", selected_packages.len()),
+                        GuiMessage::SelectAllPackages,
+                    )
+                    .style(get_setting().theme),
+                )
+                .width(Length::Fill)
+                .padding(10)
+                .style(get_setting().theme.info_container());
+
+                let list = Column::new().push(header).push(
+                    Container::new(RestoredScrollable::<PackagesScroll, _, _>::new(
+                        Scrollable::new(filtered_packages).on_scroll(GuiMessage::PackagesScrolled),
+                        state.packages_scroll_offset,
+                    ))
                     .height(Length::Fill)
                     .width(Length::Fill)
-                    .style(get_setting().theme.normal_container())
+                    .style(get_setting().theme.normal_container()),
+                );
+
+                if selected_packages.is_empty() {
+                    list.into()
+                } else {
+                    let button = |label, message: Option<GuiMessage>| {
+                        let button =
+                            Button::new(Text::new(label).horizontal_alignment(Horizontal::Center))
+                                .width(Length::Fill)
+                                .style(get_setting().theme);
+                        match message {
+                            Some(message) => button.on_press(message),
+                            None => button,
+                        }
+                    };
+                    let selected = |state: fn(&PackageState) -> bool| {
+                        packages.iter().any(|package| {
+                            selected_packages.contains(&package.name) && state(&package.state)
+                        })
+                    };
+                    let can_install = CAN_CONNECT.load(Ordering::Relaxed)
+                        && !FETCHING.load(Ordering::Relaxed)
+                        && selected(|state| {
+                            matches!(state, PackageState::Fetched | PackageState::Errored { .. })
+                        });
+                    let can_remove = selected(|state| matches!(state, PackageState::Installed));
+
+                    list.push(
+                        Container::new(
+                            Row::new()
+                                .spacing(10)
+                                .push(button(
+                                    "[#] Install selected",
+                                    can_install.then_some(GuiMessage::InstallSelected),
+                                ))
+                                .push(button(
+                                    "[X] Remove selected",
+                                    can_remove.then_some(GuiMessage::ConfirmRemovingSelected),
+                                ))
+                                .push(button(
+                                    "[E] Export selected list",
+                                    Some(GuiMessage::ExportSelectedPackages),
+                                )),
+                        )
+                        .width(Length::Fill)
+                        .padding(10)
+                        .style(get_setting().theme.info_container()),
+                    )
                     .into()
+                }
+            } else {
+                Container::new(RestoredScrollable::<PackagesScroll, _, _>::new(
+                    Scrollable::new(filtered_packages).on_scroll(GuiMessage::PackagesScrolled),
+                    state.packages_scroll_offset,
+                ))
+                .height(Length::Fill)
+                .width(Length::Fill)
+                .style(get_setting().theme.normal_container())
+                .into()
             }
         };
 
-        Container::new(
+        let packages = if queue.is_empty() {
+            packages
+        } else {
             Column::new()
-                .push(info)
-                .push(Row::new().push(controls.view(update_count)).push(packages)),
+                .push(packages)
+                .push(Tab::install_queue(queue))
+                .into()
+        };
+
+        Container::new(
+            Column::new().push(info).push(
+                Row::new()
+                    .push(controls.view(
+                        update_count,
+                        installed_size,
+                        minutes_to_check,
+                        downloads_paused,
+                        rate_limited,
+                    ))
+                    .push(packages),
+            ),
         )
         .height(Length::Fill)
         .width(Length::Fill)
         .style(get_setting().theme.normal_container())
         .into()
     }
+
+    /// Every install on its way, in the order they were started, each with its own cancel
+    /// button.
+    fn install_queue(queue: Vec<&'a Package>) -> Element<'a, GuiMessage> {
+        let rows = queue
+            .iter()
+            .fold(Column::new().spacing(5), |column, package| {
+                let (state, progress) = match &package.state {
+                    PackageState::Queued => (String::from("Queued"), None),
+                    PackageState::Downloading { progress, .. } => {
+                        (format!("Downloading {:.0}%", progress), Some(*progress))
+                    }
+                    PackageState::Verifying => (String::from("Verifying"), None),
+                    PackageState::Extracting { progress } => {
+                        (format!("Extracting {:.0}%", progress), Some(*progress))
+                    }
+                    PackageState::Installed => (String::from("Done"), None),
+                    PackageState::Errored { .. } => (String::from("Failed"), None),
+                    PackageState::Fetched => (String::from("Stopping"), None),
+                };
+                let cancellable = !matches!(
+                    package.state,
+                    PackageState::Installed | PackageState::Errored { .. } | PackageState::Fetched
+                );
+
+                let cancel_button = Button::new(Text::new("[X] Cancel")).style(get_setting().theme);
+                column.push(
+                    Row::new()
+                        .spacing(10)
+                        .align_items(Alignment::Center)
+                        .push(Text::new(&package.name).width(Length::Fill))
+                        .push(
+                            Column::new()
+                                .width(Length::Units(150))
+                                .push(Text::new(state).size(TEXT_SIZE - 4))
+                                .push(match progress {
+                                    Some(progress) => Container::new(
+                                        ProgressBar::new(0.0..=100.0, progress)
+                                            .height(Length::Units(5))
+                                            .style(get_setting().theme),
+                                    ),
+                                    None => Container::new(Space::with_height(Length::Units(5))),
+                                }),
+                        )
+                        .push(if cancellable {
+                            cancel_button.on_press(GuiMessage::CancelInstall((*package).clone()))
+                        } else {
+                            cancel_button
+                        }),
+                )
+            });
+
+        Container::new(
+            Column::new()
+                .spacing(5)
+                .push(
+                    Text::new(format!("Queue [{}]", queue.len()))
+                        .color(get_setting().theme.highlight_text()),
+                )
+                .push(Container::new(Scrollable::new(rows)).max_height(QUEUE_MAX_HEIGHT)),
+        )
+        .width(Length::Fill)
+        .padding(10)
+        .style(get_setting().theme.info_container())
+        .into()
+    }
 }
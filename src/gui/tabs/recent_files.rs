@@ -1,10 +1,11 @@
+#[cfg(feature = "gui")]
 use super::Tab;
-use crate::{
-    gui::message::GuiMessage,
-    settings::{get_setting, TEXT_SIZE},
-};
+use crate::settings::get_setting;
+#[cfg(feature = "gui")]
+use crate::{gui::message::GuiMessage, helpers::format_file_paths, settings::TEXT_SIZE};
 use chrono::{DateTime, Local};
 use derive_deref::{Deref, DerefMut};
+#[cfg(feature = "gui")]
 use iced::{
     alignment::Horizontal,
     pure::{
@@ -15,9 +16,9 @@ use iced::{
 };
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, fmt, path::PathBuf};
 
-#[derive(Debug, Default, Deref, DerefMut, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deref, DerefMut, Deserialize, Serialize)]
 pub struct RecentFiles(HashMap<PathBuf, RecentFile>);
 
 impl RecentFiles {
@@ -31,6 +32,32 @@ impl RecentFiles {
     }
 }
 
+/// A recent file as offered in a pick list, labelled with the package it was last opened with.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecentFileChoice {
+    pub path: String,
+    pub last_opened_with: String,
+}
+
+impl From<&RecentFile> for RecentFileChoice {
+    fn from(recent_file: &RecentFile) -> Self {
+        Self {
+            path: recent_file.path.display().to_string(),
+            last_opened_with: recent_file.last_opened_with.clone(),
+        }
+    }
+}
+
+impl fmt::Display for RecentFileChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (last used with: {})",
+            self.path, self.last_opened_with
+        )
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RecentFile {
     pub name: String,
@@ -40,6 +67,14 @@ pub struct RecentFile {
 }
 
 impl RecentFile {
+    /// Whether the file was last opened with something other than the current default package.
+    pub fn differs_from_default(&self) -> bool {
+        match &get_setting().default_package {
+            Some(package) => package.name != self.last_opened_with,
+            None => false,
+        }
+    }
+
     pub fn new(path: PathBuf, last_opened_with: String) -> Self {
         Self {
             name: path.file_name().unwrap().to_str().unwrap().to_string(),
@@ -49,13 +84,26 @@ impl RecentFile {
         }
     }
 
+    #[cfg(feature = "gui")]
     pub fn view(&self, is_odd: bool) -> Element<'_, RecentFileMessage> {
-        let name = Row::new().spacing(10).push(
-            Text::new(&self.name)
+        let name = Row::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(
+                Text::new(&self.name)
+                    .color(get_setting().theme.highlight_text())
+                    .size(TEXT_SIZE + 10)
+                    .width(Length::Fill),
+            )
+            .push(
+                Text::new(if self.differs_from_default() {
+                    "NOT DEFAULT"
+                } else {
+                    ""
+                })
                 .color(get_setting().theme.highlight_text())
-                .size(TEXT_SIZE + 10)
-                .width(Length::Fill),
-        );
+                .size(TEXT_SIZE + 4),
+            );
 
         let date_time = {
             let mut formatter = timeago::Formatter::new();
@@ -157,9 +205,10 @@ impl RecentFile {
     }
 }
 
+#[cfg(feature = "gui")]
 impl<'a> Tab {
     pub fn recent_files_body(
-        file_path: Option<String>,
+        file_paths: &'a [String],
         recent_files: &'a [RecentFile],
     ) -> Element<'a, GuiMessage> {
         let button = |label, message: Option<GuiMessage>| {
@@ -204,7 +253,7 @@ impl<'a> Tab {
                         .align_items(Alignment::Center)
                         .push(button(
                             "[+]",
-                            if file_path.is_some() && get_setting().default_package.is_some() {
+                            if !file_paths.is_empty() && get_setting().default_package.is_some() {
                                 Some(GuiMessage::OpenBlenderWithFile(
                                     get_setting().default_package.clone().unwrap().name,
                                 ))
@@ -212,17 +261,18 @@ impl<'a> Tab {
                                 None
                             },
                         ))
-                        .push(Text::new("File:"))
+                        .push(Text::new(if file_paths.len() > 1 {
+                            "Files:"
+                        } else {
+                            "File:"
+                        }))
                         .push(
-                            Text::new(match &file_path {
-                                Some(file_path) => file_path,
-                                None => "none",
-                            })
-                            .color(get_setting().theme.highlight_text()),
+                            Text::new(format_file_paths(file_paths))
+                                .color(get_setting().theme.highlight_text()),
                         )
                         .push(Space::with_width(Length::Fill))
                         .push(
-                            Button::new(Text::new("Select file"))
+                            Button::new(Text::new("Select files"))
                                 .on_press(GuiMessage::SelectFile)
                                 .style(get_setting().theme),
                         ),
@@ -274,6 +324,7 @@ impl<'a> Tab {
     }
 }
 
+#[cfg(feature = "gui")]
 #[derive(Clone, Debug)]
 pub enum RecentFileMessage {
     OpenWithLastBlender(String),
@@ -1,18 +1,25 @@
 use super::Tab;
 use crate::{
     gui::{
-        extra::{BuildTypeSettings, Choice, DiskSpace, Location},
+        extra::{BuildTypeSettings, Choice, GuiState, KeepCount, Location},
         message::GuiMessage,
+        scroll::{RestoredScrollable, SettingsScroll},
         style::Theme,
     },
-    package::Build,
+    package::{Build, ARCHIVE_EXTENSIONS},
     releases::{ReleaseType, Releases},
-    settings::{get_setting, ModifierKey, CONFIG_FILE_ENV, PORTABLE, PROJECT_DIRS, TEXT_SIZE},
+    settings::{
+        get_setting, FontFamily, KeyAction, ModifierKey, UpdateChannel, CONFIG_FILE_ENV,
+        DEFAULT_COPY_INFO_TEMPLATE, INSTALLING, PORTABLE, PROJECT_DIRS, TEXT_SIZE,
+    },
 };
 use iced::{
     alignment::Horizontal,
     pure::{
-        widget::{Button, Column, Container, Radio, Row, Scrollable, Text},
+        widget::{
+            Button, Checkbox, Column, Container, PickList, ProgressBar, Radio, Row, Scrollable,
+            Slider, Text, TextInput,
+        },
         Element,
     },
     Alignment, Length, Rule, Space,
@@ -20,10 +27,11 @@ use iced::{
 use std::sync::atomic::Ordering;
 
 impl Tab {
-    pub fn settings_body(
-        releases: &Releases,
-        disk_space: Option<DiskSpace>,
-    ) -> Element<'_, GuiMessage> {
+    pub fn settings_body<'a>(releases: &'a Releases, state: &GuiState) -> Element<'a, GuiMessage> {
+        let disk_space = state.disk_space;
+        // The package being moved right now was already taken off the list.
+        let migrated_packages =
+            (state.migration_total - state.migrating_packages.len()).saturating_sub(1);
         let settings_block_intro = |title, description| {
             Column::new()
                 .spacing(10)
@@ -190,7 +198,7 @@ impl Tab {
         let lts_packages_exist = releases
             .installed
             .iter()
-            .filter(|package| package.build == Build::Lts)
+            .filter(|package| matches!(package.build, Build::Lts(_)))
             .count()
             > 0;
 
@@ -253,6 +261,565 @@ Maximum is a day (1440 minutes).",
                 .push(Space::with_width(Length::Units(10)))
         };
 
+        let max_concurrent_downloads = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Concurrent downloads")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+How many packages can be downloaded at the same time. Any further installations wait in a queue \
+until a download finishes.",
+                    )),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .align_items(Alignment::Center)
+                    .width(Length::Units(150))
+                    .spacing(3)
+                    .push(
+                        Slider::new(
+                            1..=8,
+                            get_setting().max_concurrent_downloads as u8,
+                            GuiMessage::MaxConcurrentDownloadsChanged,
+                        )
+                        .style(get_setting().theme),
+                    )
+                    .push(Text::new(
+                        get_setting().max_concurrent_downloads.to_string(),
+                    )),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let resume_downloads = choice_setting!(
+            "Resume downloads",
+            "\
+Keep the partially downloaded archive when a download is interrupted or fails, and continue \
+from where it left off the next time the package is installed. Cancelled downloads are always \
+deleted.",
+            &Choice::ALL,
+            Some(choice(get_setting().resume_downloads).unwrap()),
+            GuiMessage::ResumeDownloads,
+        );
+
+        let proxy = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Proxy")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Send every request through an HTTP or HTTPS proxy, except for the hosts in the comma-separated \
+bypass list. Leave the URL empty to connect directly. Takes effect after restarting Ablavema.",
+                    ))
+                    .push(
+                        TextInput::new(
+                            "http://proxy.example.com:8080",
+                            &state.proxy_url,
+                            GuiMessage::ProxyUrlChanged,
+                        )
+                        .padding(5)
+                        .style(get_setting().theme),
+                    )
+                    .push(
+                        TextInput::new(
+                            "localhost, .example.com",
+                            &state.proxy_bypass,
+                            GuiMessage::ProxyBypassChanged,
+                        )
+                        .padding(5)
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let profiles = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                get_setting().profiles.iter().enumerate().fold(
+                    Column::new()
+                        .width(Length::Fill)
+                        .spacing(10)
+                        .push(
+                            Text::new("Launch profiles")
+                                .color(get_setting().theme.highlight_text())
+                                .size(TEXT_SIZE * 2),
+                        )
+                        .push(Text::new(
+                            "\
+Launch arguments and environment variables for a project, added to those of the package being \
+opened while the profile is picked on the Packages tab. Setting a default package while a profile \
+is picked sets the profile's, which is then opened when bypassing the launcher. Environment \
+variables are written as KEY=value pairs separated by semicolons.",
+                        ))
+                        .push(
+                            Row::new()
+                                .spacing(10)
+                                .push(
+                                    TextInput::new(
+                                        "New profile",
+                                        &state.new_profile,
+                                        GuiMessage::NewProfileChanged,
+                                    )
+                                    .on_submit(GuiMessage::ProfileCreated)
+                                    .padding(5)
+                                    .style(get_setting().theme),
+                                )
+                                .push(
+                                    Button::new(Text::new("[+] Add"))
+                                        .on_press(GuiMessage::ProfileCreated)
+                                        .style(get_setting().theme),
+                                ),
+                        ),
+                    |column, (index, profile)| {
+                        let (args, env) = state
+                            .profile_drafts
+                            .get(index)
+                            .map(|(args, env)| (args.as_str(), env.as_str()))
+                            .unwrap_or_default();
+                        column.push(
+                            Row::new()
+                                .spacing(10)
+                                .align_items(Alignment::Center)
+                                .push(Text::new(&profile.name).width(Length::Units(100)))
+                                .push(
+                                    TextInput::new("--factory-startup", args, move |args| {
+                                        GuiMessage::ProfileArgsChanged(index, args)
+                                    })
+                                    .padding(5)
+                                    .style(get_setting().theme),
+                                )
+                                .push(
+                                    TextInput::new("KEY=value; KEY=value", env, move |env| {
+                                        GuiMessage::ProfileEnvChanged(index, env)
+                                    })
+                                    .padding(5)
+                                    .style(get_setting().theme),
+                                )
+                                .push(
+                                    Button::new(Text::new("[X]"))
+                                        .on_press(GuiMessage::ProfileDeleted(index))
+                                        .style(get_setting().theme),
+                                ),
+                        )
+                    },
+                ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let naming_template = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Directory names")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Name the directories of newly installed packages after a template, where {version}, {date}, \
+{build} and {branch} are filled in. Leave it empty to keep the names of the archives. Packages \
+already installed keep their names.",
+                    ))
+                    .push(
+                        TextInput::new(
+                            "blender-{build}-{version}-{date}",
+                            &state.naming_template,
+                            GuiMessage::NamingTemplateChanged,
+                        )
+                        .padding(5)
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let copy_info_template = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Copied package info")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+What the copy button of installed packages puts in the clipboard, like for pasting into a bug \
+report. The same tokens as the directory names are filled in.",
+                    ))
+                    .push(
+                        TextInput::new(
+                            DEFAULT_COPY_INFO_TEMPLATE,
+                            &state.copy_info_template,
+                            GuiMessage::CopyInfoTemplateChanged,
+                        )
+                        .padding(5)
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let mirrors = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                get_setting().mirror_urls.iter().enumerate().fold(
+                    Column::new()
+                        .width(Length::Fill)
+                        .spacing(10)
+                        .push(
+                            Text::new("Mirrors")
+                                .color(get_setting().theme.highlight_text())
+                                .size(TEXT_SIZE * 2),
+                        )
+                        .push(Text::new(
+                            "\
+Servers to download packages from when the one they're published on can't be reached, tried from \
+top to bottom. The path of a download is added to the mirror's URL, so with \
+https://mirror.example.com/blender a daily build is looked for under \
+https://mirror.example.com/blender/download/daily/.",
+                        ))
+                        .push(
+                            Row::new()
+                                .spacing(10)
+                                .push(
+                                    TextInput::new(
+                                        "https://mirror.example.com",
+                                        &state.new_mirror,
+                                        GuiMessage::NewMirrorChanged,
+                                    )
+                                    .on_submit(GuiMessage::MirrorAdded)
+                                    .padding(5)
+                                    .style(get_setting().theme),
+                                )
+                                .push(
+                                    Button::new(Text::new("[+] Add"))
+                                        .on_press(GuiMessage::MirrorAdded)
+                                        .style(get_setting().theme),
+                                ),
+                        ),
+                    |column, (index, mirror)| {
+                        column.push(
+                            Row::new()
+                                .spacing(10)
+                                .align_items(Alignment::Center)
+                                .push(Text::new(mirror).width(Length::Fill))
+                                .push(Text::new(
+                                    state.mirror_tests.get(mirror).cloned().unwrap_or_default(),
+                                ))
+                                .push(
+                                    Button::new(Text::new("Test mirror"))
+                                        .on_press(GuiMessage::TestMirror(mirror.clone()))
+                                        .style(get_setting().theme),
+                                )
+                                .push(
+                                    Button::new(Text::new("[X]"))
+                                        .on_press(GuiMessage::MirrorRemoved(index))
+                                        .style(get_setting().theme),
+                                ),
+                        )
+                    },
+                ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let local_builds = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                get_setting().local_builds.iter().fold(
+                    Column::new()
+                        .width(Length::Fill)
+                        .spacing(10)
+                        .push(
+                            Text::new("Local builds")
+                                .color(get_setting().theme.highlight_text())
+                                .size(TEXT_SIZE * 2),
+                        )
+                        .push(Text::new(
+                            "\
+Blender builds compiled locally, listed along with the installed packages. Pick the directory with \
+the Blender executable, which is asked for its version, date and commit. They're never \
+uninstalled by the launcher, and removing them from this list leaves their files alone.",
+                        ))
+                        .push(
+                            Button::new(Text::new("[+] Add local build"))
+                                .on_press(GuiMessage::PickLocalBuild)
+                                .style(get_setting().theme),
+                        ),
+                    |column, local_build| {
+                        column.push(
+                            Row::new()
+                                .spacing(10)
+                                .align_items(Alignment::Center)
+                                .push(Text::new(&local_build.name).width(Length::FillPortion(1)))
+                                .push(
+                                    Text::new(
+                                        local_build
+                                            .local_path
+                                            .as_ref()
+                                            .map(|path| path.display().to_string())
+                                            .unwrap_or_default(),
+                                    )
+                                    .width(Length::FillPortion(2)),
+                                )
+                                .push(
+                                    Button::new(Text::new("[X]"))
+                                        .on_press(GuiMessage::ForgetLocalBuild(
+                                            local_build.name.clone(),
+                                        ))
+                                        .style(get_setting().theme),
+                                ),
+                        )
+                    },
+                ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let custom_package_input = |placeholder, value, message: fn(String) -> GuiMessage| {
+            TextInput::new(placeholder, value, message)
+                .on_submit(GuiMessage::AddCustomPackage)
+                .padding(5)
+                .style(get_setting().theme)
+        };
+        let custom_packages = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                get_setting().custom_packages.iter().fold(
+                    Column::new()
+                        .width(Length::Fill)
+                        .spacing(10)
+                        .push(
+                            Text::new("Custom packages")
+                                .color(get_setting().theme.highlight_text())
+                                .size(TEXT_SIZE * 2),
+                        )
+                        .push(Text::new(format!(
+                            "\
+Packages from anywhere else, like a build shared by someone or a fork, added by the direct URL of \
+their archive ({}). The URL is checked before the package is listed along with the rest, to be \
+installed like any other stable archive build. The name defaults to the name of the archive.",
+                            ARCHIVE_EXTENSIONS.join(", ")
+                        )))
+                        .push(custom_package_input(
+                            "https://example.com/blender-3.6.0-linux-x64.tar.xz",
+                            &state.custom_package_url,
+                            GuiMessage::CustomPackageUrlChanged,
+                        ))
+                        .push(
+                            Row::new()
+                                .spacing(10)
+                                .push(custom_package_input(
+                                    "Name",
+                                    &state.custom_package_name,
+                                    GuiMessage::CustomPackageNameChanged,
+                                ))
+                                .push(custom_package_input(
+                                    "Version, like 3.6.0",
+                                    &state.custom_package_version,
+                                    GuiMessage::CustomPackageVersionChanged,
+                                ))
+                                .push(if state.checking_custom_package {
+                                    Button::new(Text::new("Checking...")).style(get_setting().theme)
+                                } else {
+                                    Button::new(Text::new("[+] Add custom package"))
+                                        .on_press(GuiMessage::AddCustomPackage)
+                                        .style(get_setting().theme)
+                                }),
+                        ),
+                    |column, package| {
+                        column.push(
+                            Row::new()
+                                .spacing(10)
+                                .align_items(Alignment::Center)
+                                .push(
+                                    Text::new(format!("{} ({})", package.name, package.version))
+                                        .width(Length::FillPortion(1)),
+                                )
+                                .push(Text::new(&package.url).width(Length::FillPortion(2)))
+                                .push(
+                                    Button::new(Text::new("[X]"))
+                                        .on_press(GuiMessage::ForgetCustomPackage(
+                                            package.url.clone(),
+                                        ))
+                                        .style(get_setting().theme),
+                                ),
+                        )
+                    },
+                ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let buildbot = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Build health")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Ask the buildbot how the tests went for the packages shown, marking them with a dot next to their \
+version: green when they passed, yellow for warnings and red for failures. Nothing is asked until \
+the URL of its API is set, like the one shown, and only for packages with a known commit.",
+                    ))
+                    .push(
+                        TextInput::new(
+                            "https://builder.blender.org/admin/api/v2",
+                            &state.buildbot_api_url,
+                            GuiMessage::BuildbotApiUrlChanged,
+                        )
+                        .padding(5)
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let timeouts = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Timeouts")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+How many seconds to wait for a server to accept the connection, and to answer a request or send \
+more of a download, before giving up. Takes effect after restarting Ablavema.",
+                    )),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .align_items(Alignment::Center)
+                    .width(Length::Units(150))
+                    .spacing(3)
+                    .push(
+                        Slider::new(
+                            1..=60,
+                            get_setting().connect_timeout_secs as u8,
+                            GuiMessage::ConnectTimeoutChanged,
+                        )
+                        .style(get_setting().theme),
+                    )
+                    .push(Text::new(format!(
+                        "Connect: {} s",
+                        get_setting().connect_timeout_secs
+                    )))
+                    .push(
+                        Slider::new(
+                            5..=120,
+                            get_setting().read_timeout_secs as u8,
+                            GuiMessage::ReadTimeoutChanged,
+                        )
+                        .style(get_setting().theme),
+                    )
+                    .push(Text::new(format!(
+                        "Read: {} s",
+                        get_setting().read_timeout_secs
+                    ))),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let keybindings = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Keyboard shortcuts")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Click a shortcut and press the new key combination for it, or Escape to keep the current one.",
+                    ))
+                    .push(
+                        Button::new(Text::new("Reset to defaults"))
+                            .on_press(GuiMessage::ResetKeybindings)
+                            .style(get_setting().theme.tab_button()),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(KeyAction::ALL.iter().fold(
+                Column::new().spacing(5).width(Length::Units(300)),
+                |column, action| {
+                    let label = if state.rebinding == Some(*action) {
+                        String::from("Press a key...")
+                    } else {
+                        get_setting()
+                            .keybindings
+                            .get(action)
+                            .map(|combo| combo.to_string())
+                            .unwrap_or_else(|| String::from("None"))
+                    };
+
+                    column.push(
+                        Row::new()
+                            .spacing(10)
+                            .align_items(Alignment::Center)
+                            .push(Text::new(action.to_string()).width(Length::Fill))
+                            .push(
+                                Button::new(
+                                    Text::new(label).horizontal_alignment(Horizontal::Center),
+                                )
+                                .on_press(GuiMessage::RebindKey(*action))
+                                .width(Length::Units(120))
+                                .style(get_setting().theme.tab_button()),
+                            ),
+                    )
+                },
+            ))
+            .push(Space::with_width(Length::Units(10)));
+
+        let update_channel = choice_setting!(
+            "Update channel",
+            "\
+How far from stable releases to look for updates, on top of the build types checked below. \
+StableOnly only checks latest stable packages, StableAndLts adds Long-term Support packages, \
+IncludeBranched adds experimental and patched packages, and IncludeDaily checks everything.",
+            &UpdateChannel::ALL,
+            Some(get_setting().update_channel),
+            GuiMessage::UpdateChannelChanged,
+        );
+
         let check_daily_latest = choice_setting!(
             "Check latest daily packages",
             "\
@@ -297,6 +864,155 @@ look for updates for itself.",
             GuiMessage::UpdateLts,
         );
 
+        let auto_install_updates = choice_setting!(
+            "Install updates automatically",
+            "\
+Install the updates found when checking at launch in the background, without asking. When the \
+launcher is bypassed, the updates are installed before opening the default package.",
+            &Choice::ALL,
+            Some(choice(get_setting().auto_install_updates).unwrap()),
+            GuiMessage::AutoInstallUpdates,
+        );
+
+        let auto_install_builds = {
+            let checkbox = |enabled, label, message: fn(bool) -> GuiMessage| {
+                Checkbox::new(enabled, label, message).style(get_setting().theme)
+            };
+
+            Row::new()
+                .spacing(20)
+                .padding(10)
+                .push(Text::new("For:").color(get_setting().theme.highlight_text()))
+                .push(checkbox(
+                    get_setting().auto_install_daily,
+                    "Daily",
+                    GuiMessage::AutoInstallDaily,
+                ))
+                .push(checkbox(
+                    get_setting().auto_install_experimental,
+                    "Experimental",
+                    GuiMessage::AutoInstallExperimental,
+                ))
+                .push(checkbox(
+                    get_setting().auto_install_patch,
+                    "Patch",
+                    GuiMessage::AutoInstallPatch,
+                ))
+                .push(checkbox(
+                    get_setting().auto_install_stable,
+                    "Stable",
+                    GuiMessage::AutoInstallStable,
+                ))
+                .push(checkbox(
+                    get_setting().auto_install_lts,
+                    "LTS",
+                    GuiMessage::AutoInstallLts,
+                ))
+        };
+
+        let keep_counts = {
+            let keep_count = |label, keep_count, message: fn(KeepCount) -> GuiMessage| {
+                Column::new()
+                    .spacing(3)
+                    .align_items(Alignment::Center)
+                    .push(Text::new(label))
+                    .push(
+                        PickList::new(&KeepCount::ALL[..], Some(KeepCount(keep_count)), message)
+                            .width(Length::Units(70))
+                            .style(get_setting().theme.normal_pick_list()),
+                    )
+            };
+
+            Row::new()
+                .align_items(Alignment::Center)
+                .push(Space::with_width(Length::Units(10)))
+                .push(
+                    Column::new()
+                        .width(Length::Fill)
+                        .spacing(10)
+                        .push(
+                            Text::new("Packages to keep")
+                                .color(get_setting().theme.highlight_text())
+                                .size(TEXT_SIZE * 2),
+                        )
+                        .push(Text::new(
+                            "\
+How many installed packages of each build to keep. After installing a package, the oldest ones \
+past this amount are removed. Daily, experimental and patch packages are counted per branch, and \
+LTS packages per release series. The default package is never removed.",
+                        ))
+                        .push(
+                            Row::new()
+                                .spacing(10)
+                                .push(keep_count(
+                                    "Daily",
+                                    get_setting().keep_count_daily,
+                                    GuiMessage::KeepCountDaily,
+                                ))
+                                .push(keep_count(
+                                    "Experimental",
+                                    get_setting().keep_count_experimental,
+                                    GuiMessage::KeepCountExperimental,
+                                ))
+                                .push(keep_count(
+                                    "Patch",
+                                    get_setting().keep_count_patch,
+                                    GuiMessage::KeepCountPatch,
+                                ))
+                                .push(keep_count(
+                                    "Stable",
+                                    get_setting().keep_count_stable,
+                                    GuiMessage::KeepCountStable,
+                                ))
+                                .push(keep_count(
+                                    "LTS",
+                                    get_setting().keep_count_lts,
+                                    GuiMessage::KeepCountLts,
+                                )),
+                        ),
+                )
+                .push(Space::with_width(Length::Units(10)))
+        };
+
+        let auto_cleanup = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Remove old packages")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+After installing a package, remove the installed ones built more than this many days ago. The \
+default package, favourites and pinned packages are never removed.",
+                    )),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .align_items(Alignment::Center)
+                    .width(Length::Units(150))
+                    .spacing(3)
+                    .push(
+                        Slider::new(
+                            0..=365,
+                            get_setting().auto_cleanup_days.unwrap_or_default() as u16,
+                            GuiMessage::AutoCleanupDaysChanged,
+                        )
+                        .style(get_setting().theme),
+                    )
+                    .push(Text::new(match get_setting().auto_cleanup_days {
+                        Some(days) => format!("{} day{}", days, if days == 1 { "" } else { "s" }),
+                        None => String::from("Never"),
+                    })),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
         let others_block =
             settings_block_intro("Miscellaneous", "A few miscellaneous but useful settings.");
 
@@ -305,7 +1021,9 @@ look for updates for itself.",
             "\
 The preferred way to use this launcher. If a default package is set and no updates were found, \
 only open launcher when the selected modifier key is held down. This way the launcher only makes \
-itself known if there's an update or if you want to launch a different package.",
+itself known if there's an update or if you want to launch a different package. Holding the key \
+down shows a quick switch listing the installed packages, each picked with a single key, and \
+Escape opens the whole launcher.",
             &Choice::ALL,
             Some(choice(get_setting().bypass_launcher).unwrap()),
             GuiMessage::BypassLauncher,
@@ -331,6 +1049,27 @@ can be higher) when installing an update.",
             GuiMessage::UseLatestAsDefault,
         );
 
+        let backup_prefs_on_default_change = choice_setting!(
+            "Back up preferences on default change",
+            "\
+Copy the Blender config of the old default package next to itself, with a timestamp in its name, \
+when using the latest as default switches to a newer package. Packages of the same version share \
+their config, so this keeps a copy from before the newer one touches it.",
+            &Choice::ALL,
+            Some(choice(get_setting().backup_prefs_on_default_change).unwrap()),
+            GuiMessage::BackupPrefsOnDefaultChange,
+        );
+
+        let hide_pre_release = choice_setting!(
+            "Hide pre-releases",
+            "\
+Leave out packages whose version is marked as alpha, beta, release candidate or similar, leaving \
+only final releases. Pinned packages are still shown.",
+            &Choice::ALL,
+            Some(choice(get_setting().hide_pre_release).unwrap()),
+            GuiMessage::HidePreRelease,
+        );
+
         let choose_theme = choice_setting!(
             "Choose the theme",
             "Both try to mimic Blender's colour schemes as much as possible.",
@@ -339,6 +1078,218 @@ can be higher) when installing an update.",
             GuiMessage::ThemeChanged,
         );
 
+        let font_family = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .width(Length::Fill)
+                    .spacing(10)
+                    .push(
+                        Text::new("Font")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Use the font bundled with Ablavema, the system's default or monospace one, or any installed font \
+by its family name. Fonts that can't be found fall back to the system one. Takes effect after \
+restarting Ablavema.",
+                    ))
+                    .push(
+                        if matches!(get_setting().font_family, FontFamily::Custom(_)) {
+                            Column::new().push(
+                                TextInput::new(
+                                    "Noto Sans",
+                                    &state.custom_font,
+                                    GuiMessage::CustomFontChanged,
+                                )
+                                .padding(5)
+                                .style(get_setting().theme),
+                            )
+                        } else {
+                            Column::new()
+                        },
+                    ),
+            )
+            .push(Space::with_width(Length::Units(20)))
+            .push(
+                PickList::new(
+                    vec![
+                        FontFamily::Bundled,
+                        FontFamily::System,
+                        FontFamily::Monospace,
+                        FontFamily::Custom(state.custom_font.trim().to_string()),
+                    ],
+                    Some(get_setting().font_family.clone()),
+                    GuiMessage::FontFamilyChanged,
+                )
+                .width(Length::Units(150))
+                .style(get_setting().theme.normal_pick_list()),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let import_export = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .spacing(10)
+                    .width(Length::Fill)
+                    .push(
+                        Text::new("Import and export")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Save these settings to a JSON file to bring them over to another machine. The locations below \
+aren't imported, and settings the file doesn't have are left as they are.",
+                    )),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .spacing(10)
+                    .width(Length::Units(150))
+                    .push(
+                        Button::new(Text::new("Export").horizontal_alignment(Horizontal::Center))
+                            .width(Length::Fill)
+                            .on_press(GuiMessage::ExportSettings)
+                            .style(get_setting().theme.tab_button()),
+                    )
+                    .push(
+                        Button::new(Text::new("Import").horizontal_alignment(Horizontal::Center))
+                            .width(Length::Fill)
+                            .on_press(GuiMessage::ImportSettings)
+                            .style(get_setting().theme.tab_button()),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let restore_defaults = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .spacing(10)
+                    .width(Length::Fill)
+                    .push(
+                        Text::new("Restore defaults")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(
+                        "\
+Put every setting back to how it was on a fresh install, except for the locations below. The \
+default package, favourites and recent files are kept unless cleared as well.",
+                    ))
+                    .push(
+                        Checkbox::new(
+                            state.reset_package_data,
+                            "Also clear package data",
+                            GuiMessage::ResetPackageDataToggled,
+                        )
+                        .style(get_setting().theme),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Button::new(Text::new("Restore").horizontal_alignment(Horizontal::Center))
+                    .width(Length::Units(150))
+                    .on_press(GuiMessage::ConfirmResetSettings)
+                    .style(get_setting().theme.tab_button()),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let package_list = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .spacing(10)
+                    .width(Length::Fill)
+                    .push(
+                        Text::new("Package list")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(format!(
+                        "\
+Save the list of installed packages ({}) to a JSON file to set up the same ones on a clean \
+machine. Importing a list installs the packages it has that aren't installed yet, as long as \
+they're among the fetched packages.",
+                        releases.installed.len()
+                    ))),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .spacing(10)
+                    .width(Length::Units(150))
+                    .push({
+                        let button = Button::new(
+                            Text::new("Export").horizontal_alignment(Horizontal::Center),
+                        )
+                        .width(Length::Fill)
+                        .style(get_setting().theme.tab_button());
+                        if releases.installed.is_empty() {
+                            button
+                        } else {
+                            button.on_press(GuiMessage::ExportPackageList)
+                        }
+                    })
+                    .push(
+                        Button::new(Text::new("Import").horizontal_alignment(Horizontal::Center))
+                            .width(Length::Fill)
+                            .on_press(GuiMessage::ImportPackageList)
+                            .style(get_setting().theme.tab_button()),
+                    ),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
+        let file_association = Row::new()
+            .align_items(Alignment::Center)
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Column::new()
+                    .spacing(10)
+                    .width(Length::Fill)
+                    .push(
+                        Text::new("Open .blend files with Ablavema")
+                            .color(get_setting().theme.highlight_text())
+                            .size(TEXT_SIZE * 2),
+                    )
+                    .push(Text::new(if state.file_association_registered {
+                        "\
+.blend files are set to open with Ablavema for your user, which launches them with the default \
+package."
+                    } else {
+                        "\
+Set .blend files to open with Ablavema for your user, so they're launched with the default \
+package. On Linux this adds a desktop entry, and on Windows a registry entry."
+                    })),
+            )
+            .push(Space::with_width(Length::Units(10)))
+            .push(
+                Button::new(
+                    Text::new(if state.file_association_registered {
+                        "Unregister"
+                    } else {
+                        "Register"
+                    })
+                    .horizontal_alignment(Horizontal::Center),
+                )
+                .width(Length::Units(150))
+                .on_press(if state.file_association_registered {
+                    GuiMessage::UnregisterFileAssociation
+                } else {
+                    GuiMessage::RegisterFileAssociation
+                })
+                .style(get_setting().theme.tab_button()),
+            )
+            .push(Space::with_width(Length::Units(10)));
+
         let change_location = Row::new()
             .align_items(Alignment::Center)
             .push(Space::with_width(Length::Units(10)))
@@ -354,8 +1305,8 @@ can be higher) when installing an update.",
                     .push(if PORTABLE.load(Ordering::Relaxed) {
                         Container::new(Text::new(
                             "\
-Can't change locations because portable mode is enabled. Delete the \"portable\" file in the \
-executable's directory to disable it.",
+Can't change locations because portable mode is enabled. Delete the \"portable\" or \
+\"portable.txt\" file in the executable's directory to disable it.",
                         ))
                         .width(Length::Fill)
                     } else {
@@ -410,7 +1361,60 @@ whatever its name is.",
                                             Location::Cache,
                                             get_setting().cache_dir == PROJECT_DIRS.cache_dir(),
                                         )),
-                                ),
+                                )
+                                .push(match &state.previous_packages_dir {
+                                    Some(_) if state.migration_total > 0 => Container::new(
+                                        Row::new()
+                                            .spacing(10)
+                                            .align_items(Alignment::Center)
+                                            .push(Text::new(format!(
+                                                "Moving packages... {}/{}",
+                                                migrated_packages, state.migration_total
+                                            )))
+                                            .push(
+                                                ProgressBar::new(
+                                                    0.0..=state.migration_total as f32,
+                                                    migrated_packages as f32,
+                                                )
+                                                .width(Length::Fill)
+                                                .style(get_setting().theme),
+                                            ),
+                                    ),
+                                    Some(previous_packages_dir) => Container::new(
+                                        Row::new()
+                                            .spacing(10)
+                                            .align_items(Alignment::Center)
+                                            .push(
+                                                Text::new(match &state.move_packages_error {
+                                                    Some(error) => format!(
+                                                        "Couldn't move all packages, the rest are \
+                                                         still in '{}': {}.",
+                                                        previous_packages_dir.display(),
+                                                        error
+                                                    ),
+                                                    None => format!(
+                                                        "Installed packages are still in '{}'.",
+                                                        previous_packages_dir.display()
+                                                    ),
+                                                })
+                                                .width(Length::Fill),
+                                            )
+                                            .push({
+                                                let button =
+                                                    Button::new(Text::new("Move them here"))
+                                                        .style(get_setting().theme.tab_button());
+
+                                                if INSTALLING.load(Ordering::Relaxed) {
+                                                    button
+                                                } else {
+                                                    button.on_press(GuiMessage::MigratePackages(
+                                                        previous_packages_dir.clone(),
+                                                    ))
+                                                }
+                                            }),
+                                    ),
+                                    None => Container::new(Space::with_height(Length::Units(0))),
+                                }),
                         )
                     }),
             )
@@ -642,6 +1646,8 @@ to see if a bug was there before or whatnot.",
             .push(separator())
             .push(minutes_between_updates)
             .push(separator())
+            .push(update_channel)
+            .push(separator())
             .push(check_daily_latest)
             .push(separator())
             .push(check_experimental_latest)
@@ -652,6 +1658,13 @@ to see if a bug was there before or whatnot.",
             .push(separator())
             .push(check_lts)
             .push(separator())
+            .push(auto_install_updates)
+            .push(auto_install_builds)
+            .push(separator())
+            .push(keep_counts)
+            .push(separator())
+            .push(auto_cleanup)
+            .push(separator())
             .push(others_block)
             .push(separator())
             .push(bypass_launcher)
@@ -660,8 +1673,46 @@ to see if a bug was there before or whatnot.",
             .push(separator())
             .push(use_latest_as_default)
             .push(separator())
+            .push(backup_prefs_on_default_change)
+            .push(separator())
+            .push(hide_pre_release)
+            .push(separator())
+            .push(max_concurrent_downloads)
+            .push(separator())
+            .push(resume_downloads)
+            .push(separator())
+            .push(proxy)
+            .push(separator())
+            .push(mirrors)
+            .push(separator())
+            .push(local_builds)
+            .push(separator())
+            .push(custom_packages)
+            .push(separator())
+            .push(buildbot)
+            .push(separator())
+            .push(timeouts)
+            .push(separator())
+            .push(keybindings)
+            .push(separator())
             .push(choose_theme)
             .push(separator())
+            .push(font_family)
+            .push(separator())
+            .push(import_export)
+            .push(separator())
+            .push(restore_defaults)
+            .push(separator())
+            .push(package_list)
+            .push(separator())
+            .push(file_association)
+            .push(separator())
+            .push(profiles)
+            .push(separator())
+            .push(naming_template)
+            .push(separator())
+            .push(copy_info_template)
+            .push(separator())
             .push(change_location)
             .push(separator())
             .push(remove_databases)
@@ -672,7 +1723,7 @@ to see if a bug was there before or whatnot.",
             .push(separator())
             .push(self_updater);
 
-        Container::new(Scrollable::new(if get_setting().self_updater {
+        let settings = if get_setting().self_updater {
             settings.push(separator()).push(choice_setting!(
                 "Check for Ablavema updates at launch",
                 "\
@@ -684,7 +1735,11 @@ downgrade you will be prompted to update Ablavema every time updates are checked
             ))
         } else {
             settings
-        }))
+        };
+        Container::new(RestoredScrollable::<SettingsScroll, _, _>::new(
+            Scrollable::new(settings).on_scroll(GuiMessage::SettingsScrolled),
+            state.settings_scroll_offset,
+        ))
         .height(Length::Fill)
         .width(Length::Fill)
         .style(get_setting().theme)
@@ -0,0 +1,187 @@
+use super::{
+    extra::{Choice, Location},
+    message::GuiMessage,
+};
+use crate::settings::{get_setting, PORTABLE, TEXT_SIZE};
+use iced::{
+    alignment::Horizontal,
+    pure::{
+        widget::{Button, Checkbox, Column, Container, Row, Text},
+        Element,
+    },
+    Alignment, Length, Space,
+};
+use std::sync::atomic::Ordering;
+
+/// The steps of the setup shown the first time Ablavema runs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WizardState {
+    Welcome,
+    ChooseInstallDir,
+    ChooseBuildTypes,
+    Finish,
+}
+
+impl WizardState {
+    pub fn next(self) -> Self {
+        match self {
+            WizardState::Welcome => WizardState::ChooseInstallDir,
+            WizardState::ChooseInstallDir => WizardState::ChooseBuildTypes,
+            WizardState::ChooseBuildTypes | WizardState::Finish => WizardState::Finish,
+        }
+    }
+
+    pub fn previous(self) -> Self {
+        match self {
+            WizardState::Welcome | WizardState::ChooseInstallDir => WizardState::Welcome,
+            WizardState::ChooseBuildTypes => WizardState::ChooseInstallDir,
+            WizardState::Finish => WizardState::ChooseBuildTypes,
+        }
+    }
+}
+
+/// Covers the whole window until the setup is finished or skipped.
+pub fn wizard_view<'a>(wizard: WizardState) -> Element<'a, GuiMessage> {
+    let button = |label, message: Option<GuiMessage>| {
+        let button = Button::new(Text::new(label).horizontal_alignment(Horizontal::Center))
+            .width(Length::Units(200))
+            .style(get_setting().theme.tab_button());
+
+        match message {
+            Some(message) => button.on_press(message),
+            None => button,
+        }
+    };
+
+    let (title, description) = match wizard {
+        WizardState::Welcome => (
+            "Welcome to Ablavema",
+            "\
+Ablavema keeps track of the Blender builds published on blender.org, installs the ones you pick \
+and opens your files with them. A couple of quick choices and it's ready to go.",
+        ),
+        WizardState::ChooseInstallDir => (
+            "Where to install",
+            "\
+Packages can take a few hundred megabytes each, so pick a drive with room to spare. This can be \
+changed later on the Settings tab, and installed packages get moved along.",
+        ),
+        WizardState::ChooseBuildTypes => (
+            "What to keep up to date",
+            "\
+Pick the builds you want to hear about when checking for updates. Every build can still be \
+browsed and installed from the Packages tab.",
+        ),
+        WizardState::Finish => (
+            "All set",
+            "\
+Ablavema can look for packages right away, or you can do it later from the Packages tab.",
+        ),
+    };
+
+    let step: Element<'_, GuiMessage> = match wizard {
+        WizardState::Welcome | WizardState::Finish => Space::with_height(Length::Units(0)).into(),
+        WizardState::ChooseInstallDir => Column::new()
+            .spacing(10)
+            .align_items(Alignment::Center)
+            .push(Text::new(get_setting().packages_dir.display().to_string()))
+            .push(if PORTABLE.load(Ordering::Relaxed) {
+                button("Portable mode", None)
+            } else {
+                button(
+                    "[C] Change",
+                    Some(GuiMessage::ChangeLocation(Location::Packages)),
+                )
+            })
+            .into(),
+        WizardState::ChooseBuildTypes => {
+            let build_type = |enabled, label, message: fn(Choice) -> GuiMessage| {
+                Checkbox::new(enabled, label, move |checked| {
+                    message(if checked {
+                        Choice::Enable
+                    } else {
+                        Choice::Disable
+                    })
+                })
+                .style(get_setting().theme)
+            };
+
+            Column::new()
+                .spacing(10)
+                .push(build_type(
+                    get_setting().update_daily_latest,
+                    "Daily builds",
+                    GuiMessage::UpdateDailyLatest,
+                ))
+                .push(build_type(
+                    get_setting().update_experimental_latest,
+                    "Experimental branches",
+                    GuiMessage::UpdateExperimentalLatest,
+                ))
+                .push(build_type(
+                    get_setting().update_patch_latest,
+                    "Patch builds",
+                    GuiMessage::UpdatePatchLatest,
+                ))
+                .push(build_type(
+                    get_setting().update_stable_latest,
+                    "Stable releases",
+                    GuiMessage::UpdateStableLatest,
+                ))
+                .push(build_type(
+                    get_setting().update_lts,
+                    "Long-term support releases",
+                    GuiMessage::UpdateLts,
+                ))
+                .into()
+        }
+    };
+
+    let navigation = Row::new()
+        .spacing(10)
+        .push(button(
+            "[<] Back",
+            (wizard != WizardState::Welcome).then(|| GuiMessage::WizardStep(wizard.previous())),
+        ))
+        .push(match wizard {
+            WizardState::Finish => Row::new()
+                .spacing(10)
+                .push(button(
+                    "[F] Check for packages",
+                    Some(GuiMessage::WizardFinished(true)),
+                ))
+                .push(button("[X] Later", Some(GuiMessage::WizardFinished(false)))),
+            _ => Row::new()
+                .spacing(10)
+                .push(button(
+                    "[>] Next",
+                    Some(GuiMessage::WizardStep(wizard.next())),
+                ))
+                .push(button("[X] Skip", Some(GuiMessage::WizardFinished(false)))),
+        });
+
+    Container::new(
+        Column::new()
+            .spacing(20)
+            .max_width(600)
+            .align_items(Alignment::Center)
+            .push(
+                Text::new(title)
+                    .size(TEXT_SIZE * 3)
+                    .color(get_setting().theme.highlight_text()),
+            )
+            .push(
+                Text::new(description)
+                    .width(Length::Fill)
+                    .horizontal_alignment(Horizontal::Center),
+            )
+            .push(step)
+            .push(navigation),
+    )
+    .width(Length::Fill)
+    .height(Length::Fill)
+    .center_x()
+    .center_y()
+    .style(get_setting().theme.normal_container())
+    .into()
+}
@@ -1,8 +1,107 @@
-use crate::settings::{get_setting, CAN_CONNECT};
-use reqwest::{self, ClientBuilder};
+#[cfg(all(target_os = "linux", feature = "dbus"))]
+pub mod ipc;
+#[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+pub mod tray;
+
+use crate::{
+    package::Package,
+    settings::{get_setting, FontFamily, CAN_CONNECT, RATE_LIMITED_SECS},
+};
+use chrono::{DateTime, Local, Utc};
+use directories_next::BaseDirs;
+use fs_extra::dir;
+use lazy_static::lazy_static;
+use reqwest::{self, header::RETRY_AFTER, Client, ClientBuilder, Proxy, StatusCode, Url};
 use select::document::Document;
-use std::{path::Path, process::Command, sync::atomic::Ordering, time::Duration};
-use tokio::{join, time::sleep};
+use semver::{Prerelease, Version};
+use std::{
+    env::temp_dir,
+    fs::{create_dir_all, read, remove_dir, remove_dir_all},
+    path::{Path, PathBuf},
+    process::{self, Command},
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+use tokio::{join, task::spawn_blocking, time::sleep};
+use versions::Versioning;
+
+lazy_static! {
+    static ref HTTP_CLIENT: Client = http_client_builder()
+        .timeout(Duration::from_secs(get_setting().read_timeout_secs))
+        .build()
+        .unwrap();
+    static ref DOWNLOAD_CLIENT: Client = http_client_builder().build().unwrap();
+}
+
+/// The client shared by every request, built once at launch with the proxy and timeout
+/// settings applied.
+pub fn http_client() -> &'static Client {
+    &HTTP_CLIENT
+}
+
+/// Same as [`http_client`], but without a limit on how long the whole request can take, since
+/// downloads easily go past it. Reads should be timed out separately instead.
+pub fn download_client() -> &'static Client {
+    &DOWNLOAD_CLIENT
+}
+
+/// Moves a download URL over to a mirror, keeping its path. A mirror with a path of its own,
+/// like `https://mirror.example.com/blender`, gets the download's path added to it.
+pub fn mirror_url(url: &str, mirror: &str) -> Option<String> {
+    let url = Url::parse(url).ok()?;
+    let mirror = Url::parse(mirror.trim()).ok()?;
+    let mut mirrored = format!("{}{}", mirror.as_str().trim_end_matches('/'), url.path());
+    if let Some(query) = url.query() {
+        mirrored.push('?');
+        mirrored.push_str(query);
+    }
+    Some(mirrored)
+}
+
+/// Sends a HEAD request to a mirror, returning how long it took to answer.
+pub async fn test_mirror(mirror: String) -> (String, Result<Duration, String>) {
+    let start = Instant::now();
+    let result = match http_client().head(mirror.trim()).send().await {
+        Ok(response) if response.status().is_server_error() => {
+            Err(format!("answered with {}", response.status()))
+        }
+        Ok(_) => Ok(start.elapsed()),
+        Err(e) => Err(e.to_string()),
+    };
+    (mirror, result)
+}
+
+/// Sets up a client to go through the configured proxy, except for the bypassed hosts.
+fn http_client_builder() -> ClientBuilder {
+    let builder = ClientBuilder::new()
+        .connect_timeout(Duration::from_secs(get_setting().connect_timeout_secs));
+
+    let proxy_url = match &get_setting().proxy_url {
+        Some(proxy_url) => match Url::parse(proxy_url) {
+            Ok(proxy_url) => proxy_url,
+            Err(e) => {
+                log::warn!("Ignoring invalid proxy URL '{}': {}", proxy_url, e);
+                return builder;
+            }
+        },
+        None => return builder,
+    };
+    let bypass = get_setting().proxy_bypass.clone();
+
+    builder.proxy(Proxy::custom(move |url| {
+        let host = url.host_str().unwrap_or_default();
+        let bypassed = bypass.iter().any(|bypassed_host| {
+            let bypassed_host = bypassed_host.trim_start_matches('.');
+            host == bypassed_host || host.ends_with(&format!(".{}", bypassed_host))
+        });
+
+        if bypassed {
+            None
+        } else {
+            Some(proxy_url.clone())
+        }
+    }))
+}
 
 /// Check whether there's a working connection to the download servers.
 pub async fn check_connection() {
@@ -13,10 +112,7 @@ pub async fn check_connection() {
         "https://github.com/AlexChaplinBraz/Ablavema",
     ];
 
-    let client = ClientBuilder::new()
-        .connect_timeout(Duration::from_secs(1))
-        .build()
-        .unwrap();
+    let client = http_client();
 
     let (r0, r1, r2, r3) = join!(
         client.get(&*urls[0]).send(),
@@ -33,16 +129,16 @@ pub async fn check_connection() {
                 let url = response.url().to_string();
 
                 if response.status().is_client_error() {
-                    eprintln!("Client error connecting to '{}'.", url);
+                    log::error!("Client error connecting to '{}'.", url);
                     failed_urls.push(url);
                 } else if response.status().is_server_error() {
-                    eprintln!("Server error connecting to '{}'.", url);
+                    log::error!("Server error connecting to '{}'.", url);
                     failed_urls.push(url);
                 }
             }
             Err(e) => {
                 let url = e.url().unwrap().to_string();
-                eprintln!("Error connecting to '{}'.\nThe error was: {}", url, e);
+                log::error!("Error connecting to '{}'.\nThe error was: {}", url, e);
                 failed_urls.push(url);
             }
         }
@@ -62,17 +158,17 @@ pub async fn check_connection() {
         match client.get(&url).send().await {
             Ok(response) => {
                 if response.status().is_client_error() {
-                    eprintln!("Client error connecting to '{}'.", url);
+                    log::error!("Client error connecting to '{}'.", url);
                     CAN_CONNECT.store(false, Ordering::Relaxed);
                     return;
                 } else if response.status().is_server_error() {
-                    eprintln!("Server error connecting to '{}'.", url);
+                    log::error!("Server error connecting to '{}'.", url);
                     CAN_CONNECT.store(false, Ordering::Relaxed);
                     return;
                 }
             }
             Err(e) => {
-                eprintln!("Error connecting to '{}'.\nThe error was: {}", url, e);
+                log::error!("Error connecting to '{}'.\nThe error was: {}", url, e);
                 CAN_CONNECT.store(false, Ordering::Relaxed);
                 return;
             }
@@ -82,31 +178,100 @@ pub async fn check_connection() {
     CAN_CONNECT.store(true, Ordering::Relaxed);
 }
 
-pub async fn get_document(url: &str) -> Document {
-    // TODO: Fix hang on getting temp banned mid fetching.
-    // Should be resolved by adding a timeout, but the requirement is being
-    // able to pass an error around and handle it.
-    let resp = reqwest::get(url).await.unwrap();
-    assert!(resp.status().is_success());
-    let resp = resp.bytes().await.unwrap();
-    Document::from_read(&resp[..]).unwrap()
+/// How long to wait after being rate limited when the server doesn't say, or gives a date
+/// instead of a number of seconds.
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
+/// Fetches and parses a page, returning nothing if it couldn't be reached in time. That's
+/// treated as having lost the connection, so the GUI shows it and offers to check again.
+pub async fn get_document(url: &str) -> Option<Document> {
+    let bytes = match http_client().get(url).send().await {
+        Ok(response) if response.status().is_success() => response.bytes().await,
+        // Being rate limited doesn't mean the connection is gone, so that's left alone.
+        Ok(response) if response.status() == StatusCode::TOO_MANY_REQUESTS => {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|retry_after| retry_after.to_str().ok())
+                .and_then(|retry_after| retry_after.trim().parse().ok())
+                .unwrap_or(DEFAULT_RETRY_AFTER_SECS);
+            log::warn!(
+                "Rate limited fetching '{}', retrying in {} s.",
+                url,
+                retry_after
+            );
+            RATE_LIMITED_SECS.fetch_max(retry_after, Ordering::Relaxed);
+            return None;
+        }
+        Ok(response) => {
+            log::error!("Error fetching '{}': {}", url, response.status());
+            CAN_CONNECT.store(false, Ordering::Relaxed);
+            return None;
+        }
+        Err(e) => Err(e),
+    };
+
+    match bytes {
+        Ok(bytes) => Document::from_read(&bytes[..]).ok(),
+        Err(e) => {
+            log::error!("Error fetching '{}': {}", url, e);
+            CAN_CONNECT.store(false, Ordering::Relaxed);
+            None
+        }
+    }
+}
+
+/// Path to the Blender executable of an installed package, or of a local build.
+pub fn blender_executable(package: &str) -> PathBuf {
+    let local_path = get_setting()
+        .local_builds
+        .iter()
+        .find(|local_build| local_build.name == package)
+        .and_then(|local_build| local_build.local_path.clone());
+    match local_path {
+        Some(local_path) => executable_in(&local_path),
+        None => executable_in(&get_setting().packages_dir.join(package)),
+    }
 }
 
-pub fn open_blender(package: String, file_path: Option<String>) {
-    let mut cmd = Command::new(get_setting().packages_dir.join(package).join({
+/// Path to the Blender executable in a directory holding a Blender build.
+pub fn executable_in(dir: &Path) -> PathBuf {
+    dir.join({
         if cfg!(target_os = "linux") {
             "blender"
         } else if cfg!(target_os = "windows") {
             "blender.exe"
         } else if cfg!(target_os = "macos") {
-            todo!("macos executable");
+            "Blender.app/Contents/MacOS/Blender"
         } else {
             unreachable!("Unsupported OS");
         }
-    }));
-    if let Some(path) = file_path {
-        cmd.arg(path);
+    })
+}
+
+/// The version in the output of `blender --version`, which starts with a line like
+/// "Blender 3.6.2" or "Blender 4.0.0 Alpha".
+pub fn printed_blender_version(output: &str) -> Option<&str> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("Blender "))
+        .and_then(|version| version.split_whitespace().next())
+}
+
+pub fn open_blender(package: String, file_paths: Vec<String>) {
+    let mut cmd = Command::new(blender_executable(&package));
+    if let Some(mut installed_package) = Package::load_info(&package) {
+        cmd.args(&installed_package.launch_args);
+        cmd.envs(installed_package.env_vars.clone());
+        installed_package.last_opened = Some(Utc::now());
+        installed_package.open_count += 1;
+        installed_package.save_info();
     }
+    if let Some(profile) = get_setting().active_profile() {
+        cmd.args(&profile.launch_args);
+        cmd.envs(profile.env_vars.clone());
+    }
+    cmd.args(file_paths);
     // TODO: Consider handling possible errors when launching Blender.
     // I've seen this panic inside a Windows VM with:
     // "The application has failed to start because its side-by-side configuration is incorrect.
@@ -137,6 +302,347 @@ pub fn open_blender(package: String, file_path: Option<String>) {
     cmd.spawn().unwrap();
 }
 
+/// Where a sandboxed package keeps its Blender config. It's unique to this run of Ablavema,
+/// so whatever a crash left behind never gets reused.
+pub fn sandbox_dir(package: &str) -> PathBuf {
+    temp_dir()
+        .join(format!("ablavema-sandbox-{}", process::id()))
+        .join(package)
+}
+
+/// Opens a package with factory settings and its user config pointed at a throwaway
+/// directory, so the actual config is left untouched. The directory is removed once Blender
+/// exits, which is when this returns.
+pub async fn open_blender_sandboxed(package: String) -> Result<String, String> {
+    let dir = sandbox_dir(&package);
+    create_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+    let mut child = match Command::new(blender_executable(&package))
+        .args(["--factory-startup", "--python-use-system-env"])
+        .env("BLENDER_USER_CONFIG", &dir)
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            let _ = remove_dir_all(&dir);
+            return Err(format!("Couldn't open {}: {}", package, e));
+        }
+    };
+    let _ = spawn_blocking(move || child.wait()).await;
+
+    remove_dir_all(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    // Only goes through once no other sandbox is open.
+    let _ = remove_dir(dir.parent().unwrap());
+    Ok(package)
+}
+
+/// Where Blender keeps the user config of a version, like `~/.config/blender/3.6` on Linux.
+/// Every package of the same major and minor version shares it.
+pub fn blender_config_dir(version: &Versioning) -> Option<PathBuf> {
+    let base_dirs = BaseDirs::new()?;
+    let series = format!("{}.{}", version.nth(0)?, version.nth(1)?);
+    Some(if cfg!(target_os = "linux") {
+        base_dirs.config_dir().join("blender").join(series)
+    } else if cfg!(target_os = "windows") {
+        base_dirs
+            .config_dir()
+            .join("Blender Foundation")
+            .join("Blender")
+            .join(series)
+    } else if cfg!(target_os = "macos") {
+        base_dirs.config_dir().join("Blender").join(series)
+    } else {
+        unreachable!("Unsupported OS");
+    })
+}
+
+/// Copies the user config of a Blender version next to itself, with the time in its name.
+/// Returns where the copy went, or nothing if the version has no config to back up.
+pub async fn backup_blender_prefs(version: Versioning) -> Result<Option<PathBuf>, String> {
+    let config_dir = match blender_config_dir(&version) {
+        Some(config_dir) if config_dir.is_dir() => config_dir,
+        _ => return Ok(None),
+    };
+    spawn_blocking(move || {
+        let mut backup_name = config_dir.file_name().unwrap().to_os_string();
+        backup_name.push(format!(".backup.{}", Local::now().format("%Y%m%d-%H%M%S")));
+        let backup_dir = config_dir.with_file_name(backup_name);
+
+        let mut options = dir::CopyOptions::new();
+        options.copy_inside = true;
+        if let Err(e) = dir::copy(&config_dir, &backup_dir, &options) {
+            let _ = remove_dir_all(&backup_dir);
+            return Err(format!("{}: {}", backup_dir.display(), e));
+        }
+        Ok(Some(backup_dir))
+    })
+    .await
+    .unwrap()
+}
+
+#[cfg(target_os = "macos")]
+fn run(command: &mut Command) -> Result<(), String> {
+    let status = command
+        .status()
+        .map_err(|e| format!("{:?}: {}", command, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{:?} failed with {}", command, status))
+    }
+}
+
+/// Copies the app bundle out of a disk image into `dest`, taking it out of quarantine so
+/// Gatekeeper doesn't refuse to open it. The image is mounted next to itself and always
+/// detached afterwards.
+#[cfg(target_os = "macos")]
+pub fn extract_dmg(path: &Path, dest: &Path) -> Result<(), String> {
+    let mount_point = path.with_extension("mount");
+    create_dir_all(&mount_point).map_err(|e| format!("{}: {}", mount_point.display(), e))?;
+    run(Command::new("hdiutil")
+        .args(["attach", "-nobrowse", "-readonly", "-mountpoint"])
+        .arg(&mount_point)
+        .arg(path))?;
+
+    let result = (|| {
+        let app = std::fs::read_dir(&mount_point)
+            .map_err(|e| format!("{}: {}", mount_point.display(), e))?
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .find(|entry| {
+                entry
+                    .extension()
+                    .map_or(false, |extension| extension == "app")
+            })
+            .ok_or_else(|| String::from("no app bundle in the disk image"))?;
+        create_dir_all(dest).map_err(|e| format!("{}: {}", dest.display(), e))?;
+        run(Command::new("ditto")
+            .arg(&app)
+            .arg(dest.join(app.file_name().unwrap())))?;
+        run(Command::new("xattr").arg("-cr").arg(dest))
+    })();
+
+    let _ = run(Command::new("hdiutil").arg("detach").arg(&mount_point));
+    let _ = remove_dir(&mount_point);
+    result
+}
+
+/// Opens a directory in the system's file manager.
+pub fn open_folder(path: &Path) {
+    let file_manager = if cfg!(target_os = "linux") {
+        "xdg-open"
+    } else if cfg!(target_os = "windows") {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        unreachable!("Unsupported OS");
+    };
+
+    if let Err(e) = Command::new(file_manager).arg(path).spawn() {
+        log::error!("Couldn't open {}: {}", path.display(), e);
+    }
+}
+
+/// Lists the given files for display, mentioning only how many are left past the third.
+pub fn format_file_paths(file_paths: &[String]) -> String {
+    match file_paths.len() {
+        0 => String::from("none"),
+        1..=3 => file_paths.join(", "),
+        count => format!("{} and {} more", file_paths[..3].join(", "), count - 3),
+    }
+}
+
+/// Formats a size in bytes as megabytes or, past a gigabyte, as gigabytes.
+pub fn format_size(bytes: u64) -> String {
+    let megabytes = bytes as f64 / 1024.0 / 1024.0;
+    if megabytes < 1024.0 {
+        format!("{:.0} MB", megabytes)
+    } else {
+        format!("{:.1} GB", megabytes / 1024.0)
+    }
+}
+
+/// Describes how long ago the given date was in days, weeks, months or years.
+pub fn format_relative_date(date: &DateTime<Utc>) -> String {
+    relative_days(Utc::now().signed_duration_since(*date).num_days())
+}
+
+/// What `format_relative_date` says for a date the given number of days ago.
+fn relative_days(days: i64) -> String {
+    let (count, unit) = match days {
+        i64::MIN..=0 => return String::from("today"),
+        1 => return String::from("yesterday"),
+        2..=6 => (days, "day"),
+        7..=29 => (days / 7, "week"),
+        30..=364 => (days / 30, "month"),
+        _ => (days / 365, "year"),
+    };
+
+    format!("{} {}{} ago", count, unit, if count > 1 { "s" } else { "" })
+}
+
+/// Gives the data of the chosen font for Iced, which needs it for the whole run.
+pub fn font_data(family: &FontFamily) -> &'static [u8] {
+    const BUNDLED: &[u8] = include_bytes!("../extra/fonts/Lambda.ttf");
+
+    let path = match family {
+        FontFamily::Bundled => return BUNDLED,
+        FontFamily::System => find_font("sans-serif", false),
+        FontFamily::Monospace => find_font("monospace", false),
+        FontFamily::Custom(name) => find_font(name, true).or_else(|| {
+            log::warn!("Couldn't find the font '{}', using the system one.", name);
+            find_font("sans-serif", false)
+        }),
+    };
+
+    match path.and_then(|path| {
+        read(&path)
+            .map_err(|e| log::warn!("Couldn't read the font {}: {}", path.display(), e))
+            .ok()
+    }) {
+        Some(data) => Box::leak(data.into_boxed_slice()),
+        None => {
+            log::warn!("Couldn't load the chosen font, using the bundled one.");
+            BUNDLED
+        }
+    }
+}
+
+/// Only TrueType and OpenType files can be loaded, not collections.
+fn is_loadable_font(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(|extension| {
+            extension.eq_ignore_ascii_case("ttf") || extension.eq_ignore_ascii_case("otf")
+        })
+        .unwrap_or_default()
+}
+
+/// Asks fontconfig for the file of a font family or generic name like `monospace`. It always
+/// answers with its closest match, so `exact` checks the family is really the one asked for.
+#[cfg(target_os = "linux")]
+fn find_font(name: &str, exact: bool) -> Option<PathBuf> {
+    let output = Command::new("fc-match")
+        .args(["--format", "%{family}\n%{file}", name])
+        .output()
+        .ok()?;
+    let output = String::from_utf8(output.stdout).ok()?;
+    let (families, file) = output.split_once('\n')?;
+
+    if exact
+        && !families
+            .split(',')
+            .any(|family| family.trim().eq_ignore_ascii_case(name.trim()))
+    {
+        return None;
+    }
+
+    let path = PathBuf::from(file);
+    is_loadable_font(&path).then_some(path)
+}
+
+/// Looks through the system fonts directory for a file named after the family, which is how
+/// the bundled Windows fonts are named, like `segoeui.ttf` for Segoe UI.
+#[cfg(target_os = "windows")]
+fn find_font(name: &str, _exact: bool) -> Option<PathBuf> {
+    let stem = match name {
+        "sans-serif" => String::from("segoeui"),
+        "monospace" => String::from("consola"),
+        name => name.replace(' ', "").to_lowercase(),
+    };
+    let fonts_dir = PathBuf::from(std::env::var_os("WINDIR")?).join("Fonts");
+
+    std::fs::read_dir(fonts_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            is_loadable_font(path)
+                && path
+                    .file_stem()
+                    .map(|file_stem| file_stem.to_string_lossy().to_lowercase() == stem)
+                    .unwrap_or_default()
+        })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "windows")))]
+fn find_font(_name: &str, _exact: bool) -> Option<PathBuf> {
+    None
+}
+
+/// Parses a package version as semver, getting rid of what Blender's builds add on top. Build
+/// metadata like `+main.abc1234` is dropped, as is a bare git hash tacked on with a dash, and
+/// versions short of a patch number get a zero one.
+pub fn parse_semver(version: &str) -> Option<Version> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or(version);
+    let version = match version.rsplit_once('-') {
+        Some((rest, suffix))
+            if suffix.len() >= 7 && suffix.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            rest
+        }
+        _ => version,
+    };
+
+    Version::parse(version).ok().or_else(|| {
+        let (numbers, pre) = match version.split_once('-') {
+            Some((numbers, pre)) => (numbers, Some(pre)),
+            None => (version, None),
+        };
+        let mut numbers = numbers.split('.');
+        let major = numbers.next()?.parse().ok()?;
+        let minor = numbers.next().map_or(Some(0), |minor| minor.parse().ok())?;
+        let mut version = Version::new(major, minor, 0);
+        if let Some(pre) = pre {
+            version.pre = Prerelease::new(pre).ok()?;
+        }
+        Some(version)
+    })
+}
+
+/// The major and minor versions of Blender's long-term support series.
+const LTS_VERSIONS: [(u64, u64); 5] = [(2, 83), (2, 93), (3, 3), (3, 6), (4, 2)];
+
+/// Labels the versions worth telling apart at a glance: pre-releases by their stage, and the
+/// releases of the long-term support series.
+pub fn version_label(version: &str) -> Option<&'static str> {
+    let version = version.trim().to_lowercase();
+    let numbers_end = version
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(version.len());
+    let (numbers, suffix) = version.split_at(numbers_end);
+    // What comes after a plus is the branch and commit, which say nothing about the stage.
+    let suffix = suffix.split('+').next().unwrap_or_default();
+
+    if suffix.contains("alpha") {
+        return Some("Alpha");
+    }
+    if suffix.contains("beta") {
+        return Some("Beta");
+    }
+    if let Some(rc) = suffix.find("rc") {
+        return Some(
+            match suffix[rc + 2..]
+                .trim_start_matches(['.', '-'])
+                .chars()
+                .next()
+            {
+                Some('1') => "RC1",
+                Some('2') => "RC2",
+                Some('3') => "RC3",
+                _ => "RC",
+            },
+        );
+    }
+
+    let mut numbers = numbers.split('.');
+    let major = numbers.next()?.parse().ok()?;
+    let minor = numbers.next()?.parse().ok()?;
+    LTS_VERSIONS.contains(&(major, minor)).then_some("LTS")
+}
+
 pub fn get_file_stem(filename: &str) -> &str {
     if filename.contains(".tar.") {
         let f = Path::new(filename).file_stem().unwrap().to_str().unwrap();
@@ -168,3 +674,40 @@ pub trait ReturnOption: Default + PartialEq {
 }
 
 impl ReturnOption for usize {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relative_days_around_today() {
+        assert_eq!(relative_days(-1), "today");
+        assert_eq!(relative_days(0), "today");
+        assert_eq!(relative_days(1), "yesterday");
+        assert_eq!(relative_days(2), "2 days ago");
+    }
+
+    #[test]
+    fn relative_days_around_a_week() {
+        assert_eq!(relative_days(6), "6 days ago");
+        assert_eq!(relative_days(7), "1 week ago");
+        assert_eq!(relative_days(8), "1 week ago");
+        assert_eq!(relative_days(14), "2 weeks ago");
+    }
+
+    #[test]
+    fn relative_days_around_a_month() {
+        assert_eq!(relative_days(29), "4 weeks ago");
+        assert_eq!(relative_days(30), "1 month ago");
+        assert_eq!(relative_days(31), "1 month ago");
+        assert_eq!(relative_days(60), "2 months ago");
+    }
+
+    #[test]
+    fn relative_days_around_a_year() {
+        assert_eq!(relative_days(364), "12 months ago");
+        assert_eq!(relative_days(365), "1 year ago");
+        assert_eq!(relative_days(366), "1 year ago");
+        assert_eq!(relative_days(730), "2 years ago");
+    }
+}
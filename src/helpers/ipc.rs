@@ -0,0 +1,130 @@
+//! The `org.ablavema.Launcher` D-Bus service, letting scripts ask the running launcher about
+//! its packages without going through the GUI.
+use crate::{
+    package::{Package, PackageState},
+    settings::get_setting,
+};
+use dbus::blocking::Connection;
+use dbus_crossroads::Crossroads;
+use iced::Subscription;
+use std::{
+    sync::{Arc, PoisonError, RwLock},
+    thread,
+};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    Mutex,
+};
+
+const SERVICE_NAME: &str = "org.ablavema.Launcher";
+const OBJECT_PATH: &str = "/org/ablavema/Launcher";
+
+/// Names of the packages asked to be installed through `InstallUpdate`.
+pub type InstallRequests = Arc<Mutex<UnboundedReceiver<String>>>;
+
+/// The packages as of the GUI's last sync, so the service answers from them instead of going
+/// through the packages directory on its own.
+pub type SyncedPackages = Arc<RwLock<Vec<(String, bool)>>>;
+
+/// What the service's methods get to work with.
+struct Service {
+    sender: UnboundedSender<String>,
+    /// Names of the listed packages along with whether they're installed.
+    packages: SyncedPackages,
+}
+
+/// Registers the service on the session bus from a thread of its own. Without a session bus,
+/// or with the name already taken by another launcher, nothing is registered and no requests
+/// ever come through.
+pub fn start() -> (InstallRequests, SyncedPackages) {
+    let (sender, receiver) = unbounded_channel();
+    let packages = SyncedPackages::default();
+    let service = Service {
+        sender,
+        packages: packages.clone(),
+    };
+    thread::spawn(move || {
+        if let Err(e) = serve(service) {
+            log::warn!("Couldn't register the D-Bus service: {}", e);
+        }
+    });
+    (Arc::new(Mutex::new(receiver)), packages)
+}
+
+/// Hands the service the packages as they are after a sync.
+pub fn publish(synced: &SyncedPackages, packages: &[Package]) {
+    *synced.write().unwrap_or_else(PoisonError::into_inner) = packages
+        .iter()
+        .map(|package| {
+            (
+                package.name.clone(),
+                matches!(package.state, PackageState::Installed),
+            )
+        })
+        .collect();
+}
+
+fn serve(service: Service) -> Result<(), dbus::Error> {
+    let connection = Connection::new_session()?;
+    connection.request_name(SERVICE_NAME, false, true, true)?;
+
+    let mut crossroads = Crossroads::new();
+    let interface = crossroads.register(SERVICE_NAME, |builder| {
+        builder.method(
+            "ListInstalled",
+            (),
+            ("names",),
+            |_, service: &mut Service, ()| {
+                let names: Vec<String> = service
+                    .packages
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .iter()
+                    .filter(|(_, installed)| *installed)
+                    .map(|(name, _)| name.clone())
+                    .collect();
+                Ok((names,))
+            },
+        );
+        // D-Bus has no optional values, so an empty name means there's no default package.
+        builder.method("GetDefault", (), ("name",), |_, _, ()| {
+            let name = match get_setting().active_default_package() {
+                Some(package) => package.name.clone(),
+                None => String::new(),
+            };
+            Ok((name,))
+        });
+        // Only says whether the launcher took the request, since installing happens later. Names
+        // that aren't listed, or are already installed, aren't taken.
+        builder.method(
+            "InstallUpdate",
+            ("name",),
+            ("accepted",),
+            |_, service: &mut Service, (name,): (String,)| {
+                let installable = service
+                    .packages
+                    .read()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .iter()
+                    .any(|(listed, installed)| *listed == name && !installed);
+                Ok((installable && service.sender.send(name).is_ok(),))
+            },
+        );
+    });
+    crossroads.insert(OBJECT_PATH, &[interface], service);
+
+    log::info!("Registered the D-Bus service {}.", SERVICE_NAME);
+    crossroads.serve(&connection)
+}
+
+/// Passes on the install requests to the GUI as they come.
+pub fn subscription(requests: &InstallRequests) -> Subscription<String> {
+    iced_native::subscription::unfold(SERVICE_NAME, requests.clone(), |requests| async move {
+        let name = requests.lock().await.recv().await;
+        if name.is_none() {
+            // The service is gone, so there's nothing left to wait for.
+            let _: () = iced::futures::future::pending().await;
+        }
+        (name, requests)
+    })
+}
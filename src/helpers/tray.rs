@@ -0,0 +1,269 @@
+//! The system tray icon keeping the launcher around while its window is closed, through `ksni`
+//! on Linux and `tray-icon` on Windows. Both run apart from Iced's event loop and pass what was
+//! clicked on to the GUI through a channel.
+use iced::Subscription;
+use std::{fmt, sync::Arc};
+use tokio::sync::{
+    mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+    Mutex,
+};
+
+#[cfg(target_os = "linux")]
+use ksni::blocking::TrayMethods;
+#[cfg(target_os = "windows")]
+use tray_icon::{
+    menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem},
+    MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent,
+};
+
+const ICON_DATA: &[u8] = include_bytes!(env!("ICED_ICON_DATA_PATH"));
+const TOOLTIP: &str = "Ablavema";
+
+/// What was clicked on in the tray.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrayEventKind {
+    /// Left-clicking the icon, which brings the window back.
+    Restore,
+    CheckForUpdates,
+    OpenDefaultPackage,
+    Quit,
+}
+
+/// The tray icon along with what was clicked on in it.
+pub struct SystemTray {
+    events: Arc<Mutex<UnboundedReceiver<TrayEventKind>>>,
+    has_updates: bool,
+    #[cfg(target_os = "linux")]
+    handle: Option<ksni::blocking::Handle<LauncherTray>>,
+    #[cfg(target_os = "windows")]
+    icon: Option<TrayIcon>,
+}
+
+impl SystemTray {
+    /// Puts the icon in the tray. Without a tray to put it in, like on a desktop with no
+    /// StatusNotifierItem host, there's no icon and closing the window quits as usual.
+    pub fn start() -> Self {
+        let (sender, receiver) = unbounded_channel();
+        Self {
+            events: Arc::new(Mutex::new(receiver)),
+            has_updates: false,
+            #[cfg(target_os = "linux")]
+            handle: start_ksni(sender),
+            #[cfg(target_os = "windows")]
+            icon: start_tray_icon(sender),
+        }
+    }
+
+    /// Whether the icon made it into the tray, so the window can be hidden rather than closed.
+    pub fn is_shown(&self) -> bool {
+        #[cfg(target_os = "linux")]
+        return self.handle.is_some();
+        #[cfg(target_os = "windows")]
+        return self.icon.is_some();
+    }
+
+    /// Shows a dot on the icon while there are updates, going by the same count as the title.
+    pub fn set_update_badge(&mut self, has_updates: bool) {
+        if self.has_updates == has_updates {
+            return;
+        }
+        self.has_updates = has_updates;
+
+        #[cfg(target_os = "linux")]
+        if let Some(handle) = &self.handle {
+            handle.update(|tray| tray.has_updates = has_updates);
+        }
+        #[cfg(target_os = "windows")]
+        if let Some(icon) = &self.icon {
+            let (rgba, width, height) = icon_rgba(has_updates);
+            match tray_icon::Icon::from_rgba(rgba, width, height) {
+                Ok(image) => {
+                    if let Err(e) = icon.set_icon(Some(image)) {
+                        log::warn!("Couldn't update the tray icon: {}", e);
+                    }
+                }
+                Err(e) => log::warn!("Couldn't update the tray icon: {}", e),
+            }
+        }
+    }
+}
+
+// Neither tray implementation can be printed.
+impl fmt::Debug for SystemTray {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SystemTray")
+            .field("shown", &self.is_shown())
+            .field("has_updates", &self.has_updates)
+            .finish()
+    }
+}
+
+/// Passes on what was clicked on in the tray to the GUI as it comes.
+pub fn subscription(tray: &SystemTray) -> Subscription<TrayEventKind> {
+    iced_native::subscription::unfold("tray", tray.events.clone(), |events| async move {
+        let event = events.lock().await.recv().await;
+        if event.is_none() {
+            // The tray is gone, so there's nothing left to wait for.
+            let _: () = iced::futures::future::pending().await;
+        }
+        (event, events)
+    })
+}
+
+/// The window icon as RGBA, with a red dot in the top right corner when there are updates.
+fn icon_rgba(has_updates: bool) -> (Vec<u8>, u32, u32) {
+    let width: u32 = env!("ICED_ICON_WIDTH").parse().unwrap();
+    let height: u32 = env!("ICED_ICON_HEIGHT").parse().unwrap();
+    let mut rgba = ICON_DATA.to_vec();
+
+    if has_updates {
+        let radius = width.min(height) as f32 / 5.0;
+        let (centre_x, centre_y) = (width as f32 - radius, radius);
+        for y in 0..height {
+            for x in 0..width {
+                let (dx, dy) = (x as f32 + 0.5 - centre_x, y as f32 + 0.5 - centre_y);
+                if dx * dx + dy * dy <= radius * radius {
+                    let pixel = ((y * width + x) * 4) as usize;
+                    rgba[pixel..pixel + 4].copy_from_slice(&[230, 40, 40, 255]);
+                }
+            }
+        }
+    }
+
+    (rgba, width, height)
+}
+
+#[cfg(target_os = "linux")]
+pub struct LauncherTray {
+    sender: UnboundedSender<TrayEventKind>,
+    has_updates: bool,
+}
+
+#[cfg(target_os = "linux")]
+impl ksni::Tray for LauncherTray {
+    fn id(&self) -> String {
+        String::from("ablavema")
+    }
+
+    fn title(&self) -> String {
+        String::from(TOOLTIP)
+    }
+
+    fn icon_pixmap(&self) -> Vec<ksni::Icon> {
+        let (mut data, width, height) = icon_rgba(self.has_updates);
+        // StatusNotifierItem wants ARGB.
+        for pixel in data.chunks_exact_mut(4) {
+            pixel.rotate_right(1);
+        }
+        vec![ksni::Icon {
+            width: width as i32,
+            height: height as i32,
+            data,
+        }]
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        let _ = self.sender.send(TrayEventKind::Restore);
+    }
+
+    fn menu(&self) -> Vec<ksni::MenuItem<Self>> {
+        let item = |label: &str, event: TrayEventKind| {
+            ksni::menu::StandardItem {
+                label: label.to_string(),
+                activate: Box::new(move |tray: &mut Self| {
+                    let _ = tray.sender.send(event);
+                }),
+                ..Default::default()
+            }
+            .into()
+        };
+        vec![
+            item("Check for updates", TrayEventKind::CheckForUpdates),
+            item("Open default package", TrayEventKind::OpenDefaultPackage),
+            ksni::MenuItem::Separator,
+            item("Quit", TrayEventKind::Quit),
+        ]
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn start_ksni(
+    sender: UnboundedSender<TrayEventKind>,
+) -> Option<ksni::blocking::Handle<LauncherTray>> {
+    let tray = LauncherTray {
+        sender,
+        has_updates: false,
+    };
+    match tray.spawn() {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            log::warn!("Couldn't put the icon in the tray: {}", e);
+            None
+        }
+    }
+}
+
+/// Has to run on the thread of the window's event loop, which is what delivers the clicks.
+#[cfg(target_os = "windows")]
+fn start_tray_icon(sender: UnboundedSender<TrayEventKind>) -> Option<TrayIcon> {
+    const CHECK_FOR_UPDATES: &str = "check-for-updates";
+    const OPEN_DEFAULT_PACKAGE: &str = "open-default-package";
+    const QUIT: &str = "quit";
+
+    let menu = Menu::new();
+    let appended = menu.append_items(&[
+        &MenuItem::with_id(CHECK_FOR_UPDATES, "Check for updates", true, None),
+        &MenuItem::with_id(OPEN_DEFAULT_PACKAGE, "Open default package", true, None),
+        &PredefinedMenuItem::separator(),
+        &MenuItem::with_id(QUIT, "Quit", true, None),
+    ]);
+    if let Err(e) = appended {
+        log::warn!("Couldn't build the tray menu: {}", e);
+        return None;
+    }
+
+    let (rgba, width, height) = icon_rgba(false);
+    let icon = match tray_icon::Icon::from_rgba(rgba, width, height) {
+        Ok(icon) => icon,
+        Err(e) => {
+            log::warn!("Couldn't put the icon in the tray: {}", e);
+            return None;
+        }
+    };
+    let tray = match TrayIconBuilder::new()
+        .with_menu(Box::new(menu))
+        .with_menu_on_left_click(false)
+        .with_icon(icon)
+        .with_tooltip(TOOLTIP)
+        .build()
+    {
+        Ok(tray) => tray,
+        Err(e) => {
+            log::warn!("Couldn't put the icon in the tray: {}", e);
+            return None;
+        }
+    };
+
+    let menu_sender = sender.clone();
+    MenuEvent::set_event_handler(Some(move |event: MenuEvent| {
+        let event = match event.id.as_ref() {
+            CHECK_FOR_UPDATES => TrayEventKind::CheckForUpdates,
+            OPEN_DEFAULT_PACKAGE => TrayEventKind::OpenDefaultPackage,
+            QUIT => TrayEventKind::Quit,
+            _ => return,
+        };
+        let _ = menu_sender.send(event);
+    }));
+    TrayIconEvent::set_event_handler(Some(move |event: TrayIconEvent| {
+        if let TrayIconEvent::Click {
+            button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
+            ..
+        } = event
+        {
+            let _ = sender.send(TrayEventKind::Restore);
+        }
+    }));
+
+    Some(tray)
+}
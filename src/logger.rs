@@ -0,0 +1,100 @@
+use crate::settings::LOG_PATH;
+use chrono::Local;
+use lazy_static::lazy_static;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::{
+    collections::VecDeque,
+    fs::{metadata, rename, File, OpenOptions},
+    io::Write,
+    sync::Mutex,
+};
+
+/// How many of the latest lines are kept around for the Logs tab.
+const BUFFER_LINES: usize = 500;
+/// Past this size the log file is rotated at launch, keeping only the previous one.
+const MAX_LOG_FILE_SIZE: u64 = 1024 * 1024;
+
+lazy_static! {
+    static ref BUFFER: Mutex<VecDeque<String>> = Mutex::new(VecDeque::with_capacity(BUFFER_LINES));
+}
+
+struct Logger {
+    file: Mutex<Option<File>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Dependencies like wgpu are quite chatty, so only their warnings are of interest.
+        metadata.target().starts_with(env!("CARGO_CRATE_NAME")) || metadata.level() <= Level::Warn
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "{} {:<5} [{}] {}",
+            Local::now().format("%Y-%m-%d %H:%M:%S"),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+
+        eprintln!("{}", record.args());
+
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = writeln!(file, "{}", line);
+        }
+
+        let mut buffer = BUFFER.lock().unwrap();
+        if buffer.len() == BUFFER_LINES {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+/// Sets up logging to stderr, the log file next to the config file and the buffer shown
+/// on the Logs tab.
+pub fn init_logger() {
+    if let Ok(file_metadata) = metadata(&*LOG_PATH) {
+        if file_metadata.len() > MAX_LOG_FILE_SIZE {
+            let _ = rename(&*LOG_PATH, LOG_PATH.with_extension("log.1"));
+        }
+    }
+
+    let file = match OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&*LOG_PATH)
+    {
+        Ok(file) => Some(file),
+        Err(e) => {
+            eprintln!("Couldn't open log file {}: {}", LOG_PATH.display(), e);
+            None
+        }
+    };
+
+    if log::set_boxed_logger(Box::new(Logger {
+        file: Mutex::new(file),
+    }))
+    .is_ok()
+    {
+        log::set_max_level(LevelFilter::Info);
+    }
+}
+
+pub fn get_logs() -> Vec<String> {
+    BUFFER.lock().unwrap().iter().cloned().collect()
+}
+
+pub fn clear_logs() {
+    BUFFER.lock().unwrap().clear();
+}
@@ -1,27 +1,44 @@
 #![windows_subsystem = "windows"]
 #![warn(rust_2018_idioms)]
+// Much of what's shared with the command line is only used by the launcher's window.
+#![cfg_attr(not(feature = "gui"), allow(dead_code))]
 //#![allow(dead_code, unused_imports, unused_variables)]
 mod cli;
+mod file_association;
 mod gui;
 mod helpers;
+mod logger;
 mod package;
 mod releases;
 mod self_updater;
 mod settings;
 use crate::{
     cli::run_cli,
-    gui::Gui,
     helpers::open_blender,
+    logger::init_logger,
     settings::{get_setting, LAUNCH_GUI},
 };
+#[cfg(feature = "gui")]
+use crate::{
+    cli::GuiFlags,
+    gui::{
+        compact::COMPACT_WINDOW_SIZE,
+        monitor::{available_monitors, window_position},
+        Gui,
+    },
+    helpers::font_data,
+    settings::TEXT_SIZE,
+};
 use helpers::check_connection;
+#[cfg(feature = "gui")]
 use iced::pure::Application;
-use settings::TEXT_SIZE;
-use std::{env, sync::atomic::Ordering};
+use std::{process::exit, sync::atomic::Ordering};
+
+#[cfg(all(feature = "gui", feature = "cli-only"))]
+compile_error!("The cli-only feature is for building without the default gui feature.");
 
 // TODO: Fix window cascading on Windows. This will involve creating our own window which we'll
 // give to Iced.
-// TODO: Remember user's window size.
 // TODO: Add Windows metadata.
 // TODO: Consider building custom window decorations.
 // Something along the lines of how browsers have tabs next to the window buttons.
@@ -36,44 +53,66 @@ async fn main() {
         unsafe { wincon::AttachConsole(wincon::ATTACH_PARENT_PROCESS) };
     }
 
+    init_logger();
     check_connection().await;
 
     // TODO: Error reporting on unrecoverable failure.
-    // TODO: Implement error logging.
-    // Saving the file and line of the error for easier debugging.
     run().await;
 }
 
 async fn run() {
     let gui_args = run_cli().await;
 
-    if LAUNCH_GUI.load(Ordering::Relaxed) || get_setting().default_package.is_none() {
-        let mut window = iced::window::Settings::default();
-        window.size = (680, 620);
-        window.min_size = Some((680, 620));
-        window.icon = Some(
-            iced::window::Icon::from_rgba(
-                include_bytes!(env!("ICED_ICON_DATA_PATH")).to_vec(),
-                env!("ICED_ICON_WIDTH").parse().unwrap(),
-                env!("ICED_ICON_HEIGHT").parse().unwrap(),
-            )
-            .unwrap(),
-        );
-
-        let mut settings = iced::Settings::with_flags(gui_args);
-        settings.id = Some(String::from("Ablavema"));
-        settings.window = window;
-        settings.default_font = Some(include_bytes!("../extra/fonts/Lambda.ttf"));
-        settings.default_text_size = TEXT_SIZE;
+    let default_package = get_setting()
+        .launch_default_package()
+        .map(|package| package.name.clone());
+    if cfg!(feature = "gui") && (LAUNCH_GUI.load(Ordering::Relaxed) || default_package.is_none()) {
+        #[cfg(feature = "gui")]
+        run_gui(gui_args);
+    } else if let Some(default_package) = default_package {
+        open_blender(default_package, gui_args.file_paths);
+    } else {
+        eprintln!("No default package is set, and this build has no launcher to pick one in.");
+        exit(1);
+    }
+}
 
-        Gui::run(settings).unwrap();
+#[cfg(feature = "gui")]
+fn run_gui(mut gui_args: GuiFlags) {
+    let mut window = iced::window::Settings::default();
+    if gui_args.compact {
+        window.size = COMPACT_WINDOW_SIZE;
+        window.min_size = Some(COMPACT_WINDOW_SIZE);
     } else {
-        match &gui_args.file_path {
-            Some(file_path) => open_blender(
-                get_setting().default_package.clone().unwrap().name,
-                Some(file_path.to_owned()),
-            ),
-            None => open_blender(get_setting().default_package.clone().unwrap().name, None),
-        }
+        window.size = (get_setting().window_width, get_setting().window_height);
+        window.min_size = Some((680, 620));
+    }
+    window.icon = Some(
+        iced::window::Icon::from_rgba(
+            include_bytes!(env!("ICED_ICON_DATA_PATH")).to_vec(),
+            env!("ICED_ICON_WIDTH").parse().unwrap(),
+            env!("ICED_ICON_HEIGHT").parse().unwrap(),
+        )
+        .unwrap(),
+    );
+    gui_args.monitors = available_monitors();
+    window.position = window_position(
+        &gui_args.monitors,
+        get_setting().window_monitor,
+        get_setting().window_x.zip(get_setting().window_y),
+        window.size,
+    );
+
+    let mut settings = iced::Settings::with_flags(gui_args);
+    settings.id = Some(String::from("Ablavema"));
+    settings.window = window;
+    settings.default_font = Some(font_data(&get_setting().font_family));
+    settings.default_text_size = TEXT_SIZE;
+    // Closing the window hides it to the tray instead, if the icon made it there.
+    #[cfg(all(any(target_os = "linux", target_os = "windows"), feature = "tray"))]
+    {
+        settings.exit_on_close_request = false;
     }
+
+    Gui::run(settings).unwrap();
 }
@@ -1,13 +1,40 @@
-use crate::settings::get_setting;
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use crate::{
+    helpers::{
+        blender_executable, executable_in, format_relative_date, get_file_stem, parse_semver,
+        printed_blender_version,
+    },
+    settings::{get_setting, SettingsService},
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use fs_extra::dir;
+use ron::{
+    from_str,
+    ser::{to_string_pretty, PrettyConfig},
+};
 use serde::{Deserialize, Serialize};
-use std::{fmt::Write, fs::remove_dir_all, mem};
-use timeago::{self, TimeUnit::Minutes};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs::{metadata, read_to_string, remove_dir_all, write},
+    mem,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+use url::Url;
 use versions::Versioning;
 
+/// The archives that can be extracted on this system, which custom packages have to be.
+#[cfg(target_os = "linux")]
+pub const ARCHIVE_EXTENSIONS: [&str; 3] = [".tar.xz", ".tar.bz2", ".tar.gz"];
+#[cfg(target_os = "windows")]
+pub const ARCHIVE_EXTENSIONS: [&str; 1] = [".zip"];
+#[cfg(target_os = "macos")]
+pub const ARCHIVE_EXTENSIONS: [&str; 1] = [".dmg"];
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Package {
-    // TODO: Add "label" field so users can describe what a package is for if needed.
     pub version: Versioning,
     pub name: String,
     pub build: Build,
@@ -16,6 +43,64 @@ pub struct Package {
     pub url: String,
     pub os: Os,
     pub changelog: Vec<Change>,
+    #[serde(default)]
+    pub release_notes_url: Option<Url>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// The note being edited, if any.
+    #[serde(skip)]
+    pub note_draft: Option<String>,
+    /// Labels picked by the user, like "production" or "gpu-render".
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// The tags being edited, if any.
+    #[serde(skip)]
+    pub tags_draft: Option<Vec<String>>,
+    /// The tag being typed in the tag editor.
+    #[serde(skip)]
+    pub tag_input: String,
+    /// Extra arguments passed to Blender when launching this package.
+    #[serde(default)]
+    pub launch_args: Vec<String>,
+    /// The launch arguments being edited as a space-separated string, if any.
+    #[serde(skip)]
+    pub args_draft: Option<String>,
+    /// Environment variables set for Blender when launching this package, in order.
+    #[serde(default)]
+    pub env_vars: Vec<(String, String)>,
+    /// The environment variables being edited, if any.
+    #[serde(skip)]
+    pub env_draft: Option<Vec<(String, String)>>,
+    /// Directory of a locally compiled build, which is used in place of the packages directory
+    /// and never removed by the launcher.
+    #[serde(default)]
+    pub local_path: Option<PathBuf>,
+    /// When the package was last launched through `open_blender`.
+    #[serde(default)]
+    pub last_opened: Option<DateTime<Utc>>,
+    /// How many times the package was launched through `open_blender`.
+    #[serde(default)]
+    pub open_count: u64,
+    /// Size of the installed package, filled in when it's first seen installed.
+    #[serde(skip)]
+    pub size: Option<u64>,
+    /// Result of the last integrity check, if one was run this session.
+    #[serde(skip)]
+    pub integrity: Option<IntegrityStatus>,
+    /// Whether the last test launch went through, if one was run this session.
+    #[serde(skip)]
+    pub test_launch: Option<bool>,
+    /// How the buildbot's tests went for the package's commit, once asked.
+    #[serde(skip)]
+    pub build_health: Option<BuildHealth>,
+    /// Size of the archive being downloaded, from the `Content-Length` of the response.
+    #[serde(skip)]
+    pub download_size: Option<u64>,
+    /// Whether the package turned up in a fetch this session and hasn't been scrolled into view
+    /// since. Unlike `PackageStatus::New`, which only the update checks of latest builds set, it
+    /// covers every list and goes away once the package is seen.
+    #[serde(skip)]
+    pub is_new_since_last_session: bool,
     #[serde(skip)]
     pub state: PackageState,
     #[serde(skip)]
@@ -27,33 +112,502 @@ pub struct Package {
 }
 
 impl Package {
+    /// The name the package is published under. It's also the name of its directory unless a
+    /// naming template gave it another one.
+    pub fn archive_name(&self) -> &str {
+        if self.url.is_empty() {
+            &self.name
+        } else {
+            get_file_stem(&self.url)
+        }
+    }
+
+    /// The commit the package was built from. Packages fetched before it was stored still carry
+    /// it in their name, like `blender-3.3.0-alpha+master.47f8c444a3fe-linux.x86_64-release`.
+    pub fn commit_hash(&self) -> &str {
+        if !self.commit.is_empty() {
+            return &self.commit;
+        }
+        self.archive_name()
+            .split('-')
+            .find(|part| part.contains('+'))
+            .and_then(|part| part.rsplit('.').next())
+            .unwrap_or_default()
+    }
+
+    /// Whether this package is a newer build of the installed `package`, the same way
+    /// `refresh_status` tells updates apart.
+    pub fn updates(&self, package: &Package) -> bool {
+        let same_line = match &self.build {
+            Build::DailyLatest(_) | Build::ExperimentalLatest(_) | Build::PatchLatest(_) => {
+                self.version == package.version && self.build == package.build
+            }
+            Build::StableLatest | Build::Lts(_) => self.build == package.build,
+            _ => false,
+        };
+        same_line && self.date > package.date
+    }
+
+    /// Fills in the `{version}`, `{date}`, `{build}` and `{branch}` tokens of a template.
+    pub fn fill_template(&self, template: &str) -> String {
+        let (build, branch) = match &self.build {
+            Build::DailyLatest(branch) | Build::DailyArchive(branch) => ("daily", branch.as_str()),
+            Build::ExperimentalLatest(branch) | Build::ExperimentalArchive(branch) => {
+                ("experimental", branch.as_str())
+            }
+            Build::PatchLatest(branch) | Build::PatchArchive(branch) => ("patch", branch.as_str()),
+            Build::StableLatest | Build::StableArchive => ("stable", "stable"),
+            Build::Lts(series) => ("lts", series.as_str()),
+        };
+
+        template
+            .replace("{version}", &self.version.to_string())
+            .replace("{date}", &self.date.format("%Y-%m-%d").to_string())
+            .replace("{build}", build)
+            .replace("{branch}", branch)
+    }
+
+    /// Fills in a naming template, replacing whatever can't be part of a directory name.
+    /// `None` if nothing is left.
+    pub fn templated_name(&self, template: &str) -> Option<String> {
+        let name: String = self
+            .fill_template(template)
+            .chars()
+            .map(|c| match c {
+                '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+                c if c.is_control() => '-',
+                c => c,
+            })
+            .collect();
+        let name = name.trim().trim_matches('.');
+
+        (!name.is_empty()).then(|| name.to_string())
+    }
+
     pub fn get_formatted_date_time(&self) -> String {
-        let mut formatter = timeago::Formatter::new();
-        formatter.num_items(2);
-        formatter.min_unit(Minutes);
-        let duration = Utc::now().naive_utc().signed_duration_since(self.date);
-        format!(
-            "{} ({})",
-            self.date.format("%B %d, %Y - %T"),
-            // TODO: Properly get date-time based on timezone.
-            // It worked miraculously for me all this time, but now `Utc::now()` gives me
-            // a time one hour behind the date scraped from blender.org so this is inaccurate.
-            // I should switch from NaiveDateTime to DateTime so I can properly calculate time.
-            formatter.convert(duration.to_std().unwrap_or_default())
-        )
+        self.date.format("%B %d, %Y - %T").to_string()
+    }
+
+    pub fn get_relative_date(&self) -> String {
+        // TODO: Properly get date-time based on timezone.
+        // It worked miraculously for me all this time, but now `Utc::now()` gives me
+        // a time one hour behind the date scraped from blender.org so this is inaccurate.
+        // I should switch from NaiveDateTime to DateTime so I can properly calculate time.
+        format_relative_date(&DateTime::from_utc(self.date, Utc))
+    }
+
+    /// Adds up the size of every file in the package's directory.
+    pub fn disk_usage(&self) -> Option<u64> {
+        match &self.local_path {
+            Some(local_path) => dir::get_size(local_path).ok(),
+            None => dir::get_size(get_setting().packages_dir.join(&self.name)).ok(),
+        }
     }
 
     pub fn remove(&self) {
+        if self.local_path.is_some() {
+            log::warn!("Not removing {}, since it's a local build.", self.name);
+            return;
+        }
         let path = get_setting().packages_dir.join(&self.name);
         let _ = remove_dir_all(path);
         println!("Removed: {}", self.name);
     }
 
+    /// Gives LTS packages saved without their series the one matching their version.
+    pub fn fill_lts_series(&mut self) {
+        if let Build::Lts(series) = &mut self.build {
+            if series.is_empty() {
+                *series = Build::lts_series(&self.version);
+            }
+        }
+    }
+
+    /// Reads the package info file of an installed package, or the settings of a local build.
+    pub fn load_info(name: &str) -> Option<Package> {
+        let path = get_setting()
+            .packages_dir
+            .join(name)
+            .join("package_info.ron");
+        match read_to_string(path) {
+            Ok(package_info) => from_str(&package_info).ok(),
+            Err(_) => get_setting()
+                .local_builds
+                .iter()
+                .find(|local_build| local_build.name == name)
+                .cloned(),
+        }
+    }
+
+    /// Writes the package info file of an installed package, keeping its notes on disk. Local
+    /// builds are saved in the settings instead, leaving their directory alone.
+    pub fn save_info(&self) {
+        if self.local_path.is_some() {
+            SettingsService::update(|settings| {
+                if let Some(local_build) = settings
+                    .local_builds
+                    .iter_mut()
+                    .find(|local_build| local_build.name == self.name)
+                {
+                    *local_build = self.clone();
+                }
+            });
+            return;
+        }
+
+        let path = get_setting()
+            .packages_dir
+            .join(&self.name)
+            .join("package_info.ron");
+        match to_string_pretty(self, PrettyConfig::new()) {
+            Ok(package_info) => {
+                if let Err(e) = write(&path, package_info) {
+                    log::error!("Couldn't save the package info of {}: {}", self.name, e);
+                }
+            }
+            Err(e) => log::error!("Couldn't save the package info of {}: {}", self.name, e),
+        }
+    }
+
+    /// Writes down the size of every file in the freshly installed package, so that
+    /// `check_integrity` can tell later on whether any of them went missing or changed.
+    pub fn save_manifest(&self) -> Result<(), String> {
+        let package_path = get_setting().packages_dir.join(&self.name);
+        let manifest = list_files(&package_path)?;
+        let manifest =
+            to_string_pretty(&manifest, PrettyConfig::new()).map_err(|e| e.to_string())?;
+        write(package_path.join(MANIFEST_FILE), manifest).map_err(|e| e.to_string())
+    }
+
+    /// Checks that the Blender executable is still there and, if a manifest was saved when
+    /// installing, that no file went missing or changed size since. This walks the whole
+    /// package, so it's best run off the GUI thread.
+    pub fn check_integrity(&self) -> IntegrityStatus {
+        let executable = blender_executable(&self.name);
+        match metadata(&executable) {
+            Ok(executable_metadata) => {
+                #[cfg(target_os = "linux")]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+
+                    if executable_metadata.permissions().mode() & 0o111 == 0 {
+                        return IntegrityStatus::Corrupted(vec![format!(
+                            "{} is not executable",
+                            executable.display()
+                        )]);
+                    }
+                }
+                #[cfg(not(target_os = "linux"))]
+                let _ = executable_metadata;
+            }
+            Err(_) => return IntegrityStatus::ExecutableMissing,
+        }
+
+        let package_path = get_setting().packages_dir.join(&self.name);
+        let manifest: BTreeMap<String, u64> = match read_to_string(package_path.join(MANIFEST_FILE))
+        {
+            Ok(manifest) => match from_str(&manifest) {
+                Ok(manifest) => manifest,
+                Err(e) => {
+                    return IntegrityStatus::Corrupted(vec![format!(
+                        "{} couldn't be read: {}",
+                        MANIFEST_FILE, e
+                    )])
+                }
+            },
+            // Packages installed before manifests were saved can only be checked for
+            // their executable.
+            Err(_) => return IntegrityStatus::Ok,
+        };
+        let files = match list_files(&package_path) {
+            Ok(files) => files,
+            Err(e) => return IntegrityStatus::Corrupted(vec![e]),
+        };
+
+        let problems: Vec<String> = manifest
+            .into_iter()
+            .filter_map(|(file, size)| match files.get(&file) {
+                None => Some(format!("{} is missing", file)),
+                Some(current_size) if *current_size != size => {
+                    Some(format!("{} changed size", file))
+                }
+                Some(_) => None,
+            })
+            .collect();
+
+        if problems.is_empty() {
+            IntegrityStatus::Ok
+        } else {
+            IntegrityStatus::Corrupted(problems)
+        }
+    }
+
+    /// Runs Blender in the background just to print its version, checking that it starts at
+    /// all and is the version it's supposed to be. Gives back what it printed, or why it
+    /// failed. Blocks until Blender exits, for up to `TEST_LAUNCH_TIMEOUT`.
+    pub fn test_launch(&self) -> Result<String, String> {
+        let mut child = Command::new(blender_executable(&self.name))
+            .args(["--background", "--version"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("couldn't start Blender: {}", e))?;
+
+        let deadline = Instant::now() + TEST_LAUNCH_TIMEOUT;
+        let status = loop {
+            match child.try_wait().map_err(|e| e.to_string())? {
+                Some(status) => break status,
+                None if Instant::now() < deadline => sleep(Duration::from_millis(100)),
+                None => {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err(format!(
+                        "Blender didn't exit within {} s",
+                        TEST_LAUNCH_TIMEOUT.as_secs()
+                    ));
+                }
+            }
+        };
+        let output = child.wait_with_output().map_err(|e| e.to_string())?;
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("Blender exited with {}: {}", status, stderr.trim()));
+        }
+
+        let printed_version = printed_blender_version(&stdout)
+            .ok_or_else(|| format!("Blender didn't print its version: {}", stdout))?;
+        let numbers = |version: &str| {
+            parse_semver(version).map(|version| (version.major, version.minor, version.patch))
+        };
+        match (numbers(printed_version), numbers(&self.version.to_string())) {
+            (Some(printed), Some(expected)) if printed != expected => Err(format!(
+                "Blender says it's {} rather than {}",
+                printed_version, self.version
+            )),
+            // Packages installed by hand may not know their version.
+            _ => Ok(stdout),
+        }
+    }
+
+    /// Makes a package out of a locally compiled build, asking its Blender for the version,
+    /// date and commit it was built from. Blocks until Blender exits.
+    pub fn from_local_build(dir: PathBuf) -> Result<Package, String> {
+        let executable = executable_in(&dir);
+        if !executable.exists() {
+            return Err(format!("{} has no Blender executable", dir.display()));
+        }
+        let output = Command::new(&executable)
+            .arg("--version")
+            .output()
+            .map_err(|e| format!("couldn't run {}: {}", executable.display(), e))?;
+        let output = String::from_utf8_lossy(&output.stdout);
+        let version = printed_blender_version(&output)
+            .and_then(Versioning::new)
+            .ok_or_else(|| format!("{} didn't print its version", executable.display()))?;
+
+        // The lines after the version read like "build date: 2023-08-01".
+        let detail = |key: &str| {
+            output.lines().find_map(|line| {
+                let (line_key, value) = line.trim().split_once(':')?;
+                (line_key.trim() == key).then(|| value.trim().to_string())
+            })
+        };
+        let date = detail("build date")
+            .and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok())
+            .map(|date| {
+                let time = detail("build time")
+                    .and_then(|time| NaiveTime::parse_from_str(&time, "%H:%M:%S").ok())
+                    .unwrap_or_else(|| NaiveTime::from_hms(0, 0, 0));
+                NaiveDateTime::new(date, time)
+            })
+            .unwrap_or_else(|| Utc::now().naive_utc());
+        let commit = detail("build hash").unwrap_or_default();
+
+        // Builds usually end up in a `bin` directory, which says nothing about them.
+        let dir_name = match dir.file_name().and_then(|name| name.to_str()) {
+            Some("bin") => dir.parent().and_then(|parent| parent.file_name()),
+            _ => dir.file_name(),
+        }
+        .and_then(|name| name.to_str())
+        .unwrap_or("build");
+
+        Ok(Package {
+            name: format!("local-{}-{}", dir_name, version),
+            release_notes_url: release_notes_url(&version),
+            version,
+            build: Build::StableArchive,
+            date,
+            commit,
+            os: if cfg!(target_os = "linux") {
+                Os::Linux
+            } else if cfg!(target_os = "windows") {
+                Os::Windows
+            } else {
+                Os::MacOs
+            },
+            local_path: Some(dir),
+            state: PackageState::Installed,
+            status: PackageStatus::Old,
+            ..Package::default()
+        })
+    }
+
+    /// Makes a package out of the URL of an archive published somewhere else, with the name and
+    /// version given for it. An empty name takes the name of the archive.
+    pub fn from_url(url: &str, name: &str, version: &str) -> Result<Package, String> {
+        let parsed = Url::parse(url).map_err(|e| format!("'{}' isn't a valid URL: {}", url, e))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(format!("'{}' isn't an HTTP or HTTPS URL", url));
+        }
+        // The archive's name is taken from the URL, so it can't depend on anything past the path.
+        if parsed.query().is_some() || parsed.fragment().is_some() {
+            return Err(format!(
+                "'{}' has a query string or fragment, link to the archive itself instead",
+                url
+            ));
+        }
+        let file_name = parsed
+            .path_segments()
+            .and_then(|mut segments| segments.next_back())
+            .unwrap_or_default();
+        if !ARCHIVE_EXTENSIONS
+            .iter()
+            .any(|extension| file_name.ends_with(extension))
+        {
+            return Err(format!(
+                "'{}' doesn't point to an archive this system can extract ({})",
+                url,
+                ARCHIVE_EXTENSIONS.join(", ")
+            ));
+        }
+
+        let name = match name.trim() {
+            "" => get_file_stem(file_name),
+            name => name,
+        };
+        if name.starts_with('.') || name.contains(['/', '\\', ':', '*', '?', '"', '<', '>', '|']) {
+            return Err(format!("'{}' can't be used as a directory name", name));
+        }
+        let version = Versioning::new(version.trim())
+            .ok_or_else(|| format!("'{}' isn't a valid version", version.trim()))?;
+
+        Ok(Package {
+            name: name.to_string(),
+            release_notes_url: release_notes_url(&version),
+            version,
+            build: Build::StableArchive,
+            date: Utc::now().naive_utc(),
+            url: url.to_string(),
+            os: if cfg!(target_os = "linux") {
+                Os::Linux
+            } else if cfg!(target_os = "windows") {
+                Os::Windows
+            } else {
+                Os::MacOs
+            },
+            status: PackageStatus::Old,
+            ..Package::default()
+        })
+    }
+
     pub fn take(&mut self) -> Self {
         mem::take(self)
     }
 }
 
+/// How long a test launch can take before Blender is assumed to be stuck.
+const TEST_LAUNCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+const MANIFEST_FILE: &str = "package_manifest.ron";
+
+/// Relative path and size of every file in a package, leaving out the files written by the
+/// launcher itself since those change along with the notes and arguments.
+fn list_files(package_path: &Path) -> Result<BTreeMap<String, u64>, String> {
+    let content = dir::get_dir_content(package_path).map_err(|e| e.to_string())?;
+    Ok(content
+        .files
+        .into_iter()
+        .filter_map(|file| {
+            let path = Path::new(&file);
+            let relative_path = path.strip_prefix(package_path).ok()?;
+            if relative_path == Path::new("package_info.ron")
+                || relative_path == Path::new(MANIFEST_FILE)
+            {
+                return None;
+            }
+            Some((
+                relative_path.to_string_lossy().into_owned(),
+                metadata(path).ok()?.len(),
+            ))
+        })
+        .collect())
+}
+
+/// What's kept of a package in an exported package list, enough to find it again among the
+/// fetched packages on another machine.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PackageListEntry {
+    pub name: String,
+    pub version: Versioning,
+    pub build: Build,
+    pub url: String,
+}
+
+impl From<&Package> for PackageListEntry {
+    fn from(package: &Package) -> Self {
+        PackageListEntry {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            build: package.build.clone(),
+            url: package.url.clone(),
+        }
+    }
+}
+
+/// Writes the given packages to a JSON file meant to be imported on another machine.
+pub fn export_package_list(packages: &[Package], path: &Path) -> Result<(), String> {
+    let entries: Vec<PackageListEntry> = packages.iter().map(PackageListEntry::from).collect();
+    let list = serde_json::to_string_pretty(&entries).map_err(|e| e.to_string())?;
+    write(path, list).map_err(|e| e.to_string())
+}
+
+pub fn import_package_list(path: &Path) -> Result<Vec<PackageListEntry>, String> {
+    serde_json::from_str(&read_to_string(path).map_err(|e| e.to_string())?)
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntegrityStatus {
+    Ok,
+    ExecutableMissing,
+    /// What was found wrong, one problem per entry.
+    Corrupted(Vec<String>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BuildHealth {
+    Green,
+    Yellow,
+    Red,
+    Unknown,
+}
+
+/// Builds the link to the release notes on the Blender developer documentation, which are
+/// written per minor version like `3.2`.
+pub fn release_notes_url(version: &Versioning) -> Option<Url> {
+    let version = version.to_string();
+    let mut numbers = version.split(|c: char| !c.is_ascii_digit());
+    let major: u32 = numbers.next()?.parse().ok()?;
+    let minor: u32 = numbers.next()?.parse().ok()?;
+
+    Url::parse(&format!(
+        "https://developer.blender.org/docs/release_notes/{}.{}/",
+        major, minor
+    ))
+    .ok()
+}
+
 impl Default for Package {
     fn default() -> Self {
         Package {
@@ -68,6 +622,25 @@ impl Default for Package {
             url: String::default(),
             os: Os::Linux,
             changelog: Vec::default(),
+            release_notes_url: None,
+            notes: None,
+            note_draft: None,
+            tags: Vec::new(),
+            tags_draft: None,
+            tag_input: String::new(),
+            launch_args: Vec::new(),
+            args_draft: None,
+            env_vars: Vec::new(),
+            env_draft: None,
+            local_path: None,
+            last_opened: None,
+            open_count: 0,
+            size: None,
+            integrity: None,
+            test_launch: None,
+            build_health: None,
+            download_size: None,
+            is_new_since_last_session: false,
             state: PackageState::default(),
             status: PackageStatus::default(),
             index: 0,
@@ -90,7 +663,7 @@ impl Ord for Package {
                 .build
                 .cmp(&other.build)
                 .then(self.date.cmp(&other.date).reverse()),
-            Build::StableLatest | Build::StableArchive | Build::Lts => {
+            Build::StableLatest | Build::StableArchive | Build::Lts(_) => {
                 Ord::cmp(&self.version, &other.version).reverse()
             }
         }
@@ -112,8 +685,8 @@ impl PartialEq for Package {
             | Build::ExperimentalArchive(_)
             | Build::PatchLatest(_)
             | Build::PatchArchive(_) => self.build == other.build && self.date == other.date,
-            Build::StableLatest | Build::StableArchive | Build::Lts => {
-                self.name == other.name && self.version == other.version
+            Build::StableLatest | Build::StableArchive | Build::Lts(_) => {
+                self.archive_name() == other.archive_name() && self.version == other.version
             }
         }
     }
@@ -126,7 +699,36 @@ impl PartialOrd for Package {
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(from = "BuildRepr")]
 pub enum Build {
+    DailyLatest(String),
+    DailyArchive(String),
+    ExperimentalLatest(String),
+    ExperimentalArchive(String),
+    PatchLatest(String),
+    PatchArchive(String),
+    StableLatest,
+    StableArchive,
+    /// The release series, like `3.6`.
+    #[serde(rename = "LtsSeries")]
+    Lts(String),
+}
+
+impl Build {
+    pub fn lts_series(version: &Versioning) -> String {
+        format!(
+            "{}.{}",
+            version.nth(0).unwrap_or_default(),
+            version.nth(1).unwrap_or_default()
+        )
+    }
+}
+
+/// What `Build` is read from, so package info saved back when LTS packages had no series
+/// can still be read. Their series is filled in by `Package::fill_lts_series`.
+#[derive(Deserialize)]
+#[serde(rename = "Build")]
+enum BuildRepr {
     DailyLatest(String),
     DailyArchive(String),
     ExperimentalLatest(String),
@@ -136,6 +738,40 @@ pub enum Build {
     StableLatest,
     StableArchive,
     Lts,
+    LtsSeries(String),
+}
+
+impl From<BuildRepr> for Build {
+    fn from(build: BuildRepr) -> Self {
+        match build {
+            BuildRepr::DailyLatest(name) => Build::DailyLatest(name),
+            BuildRepr::DailyArchive(name) => Build::DailyArchive(name),
+            BuildRepr::ExperimentalLatest(name) => Build::ExperimentalLatest(name),
+            BuildRepr::ExperimentalArchive(name) => Build::ExperimentalArchive(name),
+            BuildRepr::PatchLatest(name) => Build::PatchLatest(name),
+            BuildRepr::PatchArchive(name) => Build::PatchArchive(name),
+            BuildRepr::StableLatest => Build::StableLatest,
+            BuildRepr::StableArchive => Build::StableArchive,
+            BuildRepr::Lts => Build::Lts(String::new()),
+            BuildRepr::LtsSeries(series) => Build::Lts(series),
+        }
+    }
+}
+
+impl std::fmt::Display for Build {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Build::DailyLatest(name) => write!(f, "Daily (latest): {}", name),
+            Build::DailyArchive(name) => write!(f, "Daily (archive): {}", name),
+            Build::ExperimentalLatest(name) => write!(f, "Experimental (latest): {}", name),
+            Build::ExperimentalArchive(name) => write!(f, "Experimental (archive): {}", name),
+            Build::PatchLatest(name) => write!(f, "Patch (latest): {}", name),
+            Build::PatchArchive(name) => write!(f, "Patch (archive): {}", name),
+            Build::StableLatest => write!(f, "Stable (latest)"),
+            Build::StableArchive => write!(f, "Stable (archive)"),
+            Build::Lts(series) => write!(f, "Long-term Support: {}", series),
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
@@ -154,10 +790,21 @@ pub struct Change {
 #[derive(Clone, Debug)]
 pub enum PackageState {
     Fetched,
-    Downloading { progress: f32 },
-    Extracting { progress: f32 },
+    Queued,
+    Downloading {
+        progress: f32,
+        downloaded: u64,
+        bytes_per_sec: f64,
+        eta_secs: Option<u64>,
+    },
+    Verifying,
+    Extracting {
+        progress: f32,
+    },
     Installed,
-    Errored { message: String },
+    Errored {
+        message: String,
+    },
 }
 
 impl Default for PackageState {
@@ -171,6 +818,8 @@ pub enum PackageStatus {
     Update,
     New,
     Old,
+    /// Found in the packages directory without having been installed by Ablavema.
+    External,
 }
 
 impl Default for PackageStatus {
@@ -241,7 +890,7 @@ impl BuildType {
             } => match build {
                 Build::StableLatest => *latest = true,
                 Build::StableArchive => *archive = true,
-                Build::Lts => *lts = true,
+                Build::Lts(_) => *lts = true,
                 _ => (),
             },
             BuildType::None => match build {
@@ -301,7 +950,7 @@ impl BuildType {
                         lts: false,
                     };
                 }
-                Build::Lts => {
+                Build::Lts(_) => {
                     *self = BuildType::Stable {
                         latest: false,
                         archive: false,
@@ -388,3 +1037,67 @@ impl std::fmt::Display for BuildType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notes_for(version: &str) -> String {
+        release_notes_url(&Versioning::new(version).unwrap())
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn release_notes_url_of_lts() {
+        assert_eq!(
+            notes_for("2.93.18"),
+            "https://developer.blender.org/docs/release_notes/2.93/"
+        );
+        assert_eq!(
+            notes_for("3.6.5"),
+            "https://developer.blender.org/docs/release_notes/3.6/"
+        );
+    }
+
+    #[test]
+    fn release_notes_url_of_stable() {
+        assert_eq!(
+            notes_for("3.2.0"),
+            "https://developer.blender.org/docs/release_notes/3.2/"
+        );
+        assert_eq!(
+            notes_for("4.10.1"),
+            "https://developer.blender.org/docs/release_notes/4.10/"
+        );
+    }
+
+    #[test]
+    fn release_notes_url_of_prereleases() {
+        assert_eq!(
+            notes_for("3.3.0-alpha"),
+            "https://developer.blender.org/docs/release_notes/3.3/"
+        );
+        assert_eq!(
+            notes_for("3.2.0-beta+v32.abcdef123456"),
+            "https://developer.blender.org/docs/release_notes/3.2/"
+        );
+        assert_eq!(
+            notes_for("4.0.0-alpha+main.0123456789ab"),
+            "https://developer.blender.org/docs/release_notes/4.0/"
+        );
+    }
+
+    #[test]
+    fn from_url_rejects_query_strings() {
+        let archive = format!("https://example.com/blender-3.2.0{}", ARCHIVE_EXTENSIONS[0]);
+        assert!(Package::from_url(&archive, "", "3.2.0").is_ok());
+        assert!(Package::from_url(&format!("{}?download=1", archive), "", "3.2.0").is_err());
+        assert!(Package::from_url(&format!("{}#top", archive), "", "3.2.0").is_err());
+    }
+
+    #[test]
+    fn release_notes_url_without_minor_version() {
+        assert_eq!(release_notes_url(&Versioning::new("3").unwrap()), None);
+    }
+}
@@ -1,8 +1,11 @@
+pub mod buildbot;
+pub mod commits;
 pub mod daily_archive;
 pub mod daily_latest;
 pub mod experimental_archive;
 pub mod experimental_latest;
 pub mod installed;
+pub mod launcher;
 pub mod lts;
 pub mod patch_archive;
 pub mod patch_latest;
@@ -15,9 +18,9 @@ use self::{
     stable_archive::StableArchive, stable_latest::StableLatest,
 };
 use crate::{
-    helpers::{get_document, get_file_stem, ReturnOption},
-    package::{Build, Os, Package, PackageState, PackageStatus},
-    settings::{get_setting, init_settings, save_settings, set_setting, CAN_CONNECT},
+    helpers::{get_document, get_file_stem, http_client, ReturnOption},
+    package::{release_notes_url, Build, Os, Package, PackageState, PackageStatus},
+    settings::{get_setting, init_settings, SettingsService, CAN_CONNECT, INSTALLING},
 };
 use async_trait::async_trait;
 use chrono::{Datelike, NaiveDateTime, Utc};
@@ -49,6 +52,10 @@ pub struct Releases {
     pub stable_archive: StableArchive,
     pub lts: Lts,
     pub installed: Installed,
+    /// Packages found in the packages directory that weren't installed by Ablavema.
+    pub external: Vec<Package>,
+    /// Packages added by URL, which are only known from the settings.
+    pub custom: Vec<Package>,
 }
 
 impl Releases {
@@ -58,6 +65,7 @@ impl Releases {
         let mut releases = Releases::default();
         releases.load_all().await;
         releases.sync();
+        releases.scan_external();
         releases
     }
 
@@ -73,6 +81,9 @@ impl Releases {
         self.stable_latest.load();
         self.stable_archive.load();
         self.lts.load();
+        for package in self.lts.iter_mut() {
+            package.fill_lts_series();
+        }
     }
 
     /// Refreshes the state and status of all packages.
@@ -82,22 +93,35 @@ impl Releases {
         // the latest list. If this is implemented I should change how BuildType works, since they
         // wouldn't be able to be loaded at the same time anyway.
         self.installed.fetch();
+        // Finding new external packages means running their Blender, so that's left to
+        // `scan_external`, but the ones removed or installed over are dropped right away.
+        let packages_dir = get_setting().packages_dir.clone();
+        let installed = &self.installed;
+        self.external.retain(|package| {
+            packages_dir.join(&package.name).is_dir()
+                && !installed
+                    .iter()
+                    .any(|installed| installed.name == package.name)
+        });
+        self.refresh_custom();
+
+        let channel = get_setting().update_channel;
 
         self.daily_latest.refresh_state(&self.installed);
         self.daily_latest
-            .refresh_status(get_setting().update_daily_latest);
+            .refresh_status(get_setting().update_daily_latest && channel.allows_daily());
 
         self.daily_archive.refresh_state(&self.installed);
 
         self.experimental_latest.refresh_state(&self.installed);
         self.experimental_latest
-            .refresh_status(get_setting().update_experimental_latest);
+            .refresh_status(get_setting().update_experimental_latest && channel.allows_branched());
 
         self.experimental_archive.refresh_state(&self.installed);
 
         self.patch_latest.refresh_state(&self.installed);
         self.patch_latest
-            .refresh_status(get_setting().update_patch_latest);
+            .refresh_status(get_setting().update_patch_latest && channel.allows_branched());
 
         self.patch_archive.refresh_state(&self.installed);
 
@@ -108,7 +132,44 @@ impl Releases {
         self.stable_archive.refresh_state(&self.installed);
 
         self.lts.refresh_state(&self.installed);
-        self.lts.refresh_status(get_setting().update_lts);
+        self.lts
+            .refresh_status(get_setting().update_lts && channel.allows_lts());
+    }
+
+    /// Looks for packages copied into the packages directory by hand. This runs their Blender to
+    /// ask for their version, so the launcher does it off its own thread instead.
+    pub fn scan_external(&mut self) {
+        // Packages being extracted don't have their info file yet, so they'd be taken for
+        // external ones.
+        if !INSTALLING.load(Ordering::Relaxed) {
+            self.external = self.installed.scan_unknown(&self.external);
+        }
+    }
+
+    /// Lists the packages added by URL, taking the installed copy of those that are installed.
+    fn refresh_custom(&mut self) {
+        let previous = mem::take(&mut self.custom);
+        let installed = &self.installed;
+        self.custom = get_setting()
+            .custom_packages
+            .iter()
+            .map(
+                |package| match installed.iter().find(|installed| *installed == package) {
+                    Some(installed) => {
+                        let mut package = installed.clone();
+                        package.state = PackageState::Installed;
+                        // Measuring the package again on every sync would be wasteful.
+                        package.size = previous
+                            .iter()
+                            .find(|previous| previous.name == package.name)
+                            .and_then(|previous| previous.size)
+                            .or_else(|| package.disk_usage());
+                        package
+                    }
+                    None => package.clone(),
+                },
+            )
+            .collect();
     }
 
     /// Check for new packages. This returns a tuple where the first item is a boolean
@@ -129,8 +190,7 @@ impl Releases {
         StableLatest,
         Lts,
     ) {
-        set_setting().last_update_time = SystemTime::now();
-        save_settings();
+        SettingsService::update(|settings| settings.last_update_time = SystemTime::now());
 
         let (
             mut daily_latest,
@@ -140,15 +200,23 @@ impl Releases {
             mut lts,
         ) = packages;
 
+        let channel = get_setting().update_channel;
+
         let mut updated_daily_latest = false;
-        if get_setting().update_daily_latest && daily_latest.get_db_path().exists() {
+        if get_setting().update_daily_latest
+            && channel.allows_daily()
+            && daily_latest.get_db_path().exists()
+        {
             let (updated, fetched_daily_latest) = DailyLatest::check_updates(daily_latest).await;
             updated_daily_latest = updated;
             daily_latest = fetched_daily_latest;
         }
 
         let mut updated_experimental_latest = false;
-        if get_setting().update_experimental_latest && experimental_latest.get_db_path().exists() {
+        if get_setting().update_experimental_latest
+            && channel.allows_branched()
+            && experimental_latest.get_db_path().exists()
+        {
             let (updated, fetched_experimental_latest) =
                 ExperimentalLatest::check_updates(experimental_latest).await;
             updated_experimental_latest = updated;
@@ -156,7 +224,10 @@ impl Releases {
         }
 
         let mut updated_patch_latest = false;
-        if get_setting().update_patch_latest && patch_latest.get_db_path().exists() {
+        if get_setting().update_patch_latest
+            && channel.allows_branched()
+            && patch_latest.get_db_path().exists()
+        {
             let (updated, fetched_patch_latest) = PatchLatest::check_updates(patch_latest).await;
             updated_patch_latest = updated;
             patch_latest = fetched_patch_latest;
@@ -170,7 +241,7 @@ impl Releases {
         }
 
         let mut updated_lts = false;
-        if get_setting().update_lts && lts.get_db_path().exists() {
+        if get_setting().update_lts && channel.allows_lts() && lts.get_db_path().exists() {
             let (updated, fetched_lts) = Lts::check_updates(lts).await;
             updated_lts = updated;
             lts = fetched_lts;
@@ -228,33 +299,29 @@ impl Releases {
         self.lts = packages.5;
     }
 
-    /// Returns the amount of updates for each build type if there are any.
+    /// Returns the amount of updates for each build type if there are any. Build types left
+    /// out by the update channel have none.
     pub fn count_updates(&self) -> UpdateCount {
-        let daily_count = self
-            .daily_latest
-            .iter()
-            .filter(|package| package.status == PackageStatus::Update)
-            .count();
-        let experimental_count = self
-            .experimental_latest
-            .iter()
-            .filter(|package| package.status == PackageStatus::Update)
-            .count();
-        let patch_count = self
-            .patch_latest
-            .iter()
-            .filter(|package| package.status == PackageStatus::Update)
-            .count();
+        let channel = get_setting().update_channel;
+        let count = |packages: &[Package], allowed: bool| {
+            if allowed {
+                packages
+                    .iter()
+                    .filter(|package| package.status == PackageStatus::Update)
+                    .count()
+            } else {
+                0
+            }
+        };
+        let daily_count = count(&self.daily_latest, channel.allows_daily());
+        let experimental_count = count(&self.experimental_latest, channel.allows_branched());
+        let patch_count = count(&self.patch_latest, channel.allows_branched());
         let stable_count = self
             .stable_latest
             .iter()
             .filter(|package| package.status == PackageStatus::Update)
             .count();
-        let lts_count = self
-            .lts
-            .iter()
-            .filter(|package| package.status == PackageStatus::Update)
-            .count();
+        let lts_count = count(&self.lts, channel.allows_lts());
         let all_count = daily_count + experimental_count + patch_count + stable_count + lts_count;
 
         UpdateCount {
@@ -281,6 +348,13 @@ impl Releases {
             .chain(self.stable_latest.iter())
             .chain(self.stable_archive.iter())
             .chain(self.lts.iter())
+            .chain(self.external.iter())
+            .chain(self.custom.iter())
+            .chain(
+                self.installed
+                    .iter()
+                    .filter(|package| package.local_path.is_some()),
+            )
         {
             match packages
                 .iter_mut()
@@ -292,6 +366,10 @@ impl Releases {
                 None => {
                     let mut new_package = package.clone();
                     new_package.index = index;
+                    // Packages saved before release notes were linked don't have it yet.
+                    if new_package.release_notes_url.is_none() {
+                        new_package.release_notes_url = release_notes_url(&new_package.version);
+                    }
                     new_package.build_type.update(&package.build);
                     packages.push(new_package);
                     index += 1;
@@ -364,9 +442,31 @@ pub trait ReleaseType:
         for package in self.iter_mut() {
             if matches!(package.state, PackageState::Installed { .. }) {
                 package.state = PackageState::default();
+                package.name = package.archive_name().to_string();
+                package.notes = None;
+                package.tags = Vec::new();
+                package.launch_args = Vec::new();
+                package.env_vars = Vec::new();
+                package.last_opened = None;
+                package.open_count = 0;
+                package.size = None;
             }
-            if installed.contains(package) {
+            if let Some(installed_package) = installed
+                .iter()
+                .find(|installed_package| *installed_package == package)
+            {
                 package.state = PackageState::Installed;
+                // Named by the naming template, if there was one when it got installed.
+                package.name = installed_package.name.clone();
+                package.notes = installed_package.notes.clone();
+                package.tags = installed_package.tags.clone();
+                package.launch_args = installed_package.launch_args.clone();
+                package.env_vars = installed_package.env_vars.clone();
+                package.last_opened = installed_package.last_opened;
+                package.open_count = installed_package.open_count;
+                if package.size.is_none() {
+                    package.size = package.disk_usage();
+                }
             }
         }
     }
@@ -406,13 +506,11 @@ pub trait ReleaseType:
                         installed_packages.push(package.clone());
                         break;
                     }
-                    Build::Lts => {
-                        if !installed_packages.iter().any(|installed_package| {
-                            installed_package.version.nth(0).unwrap()
-                                == package.version.nth(0).unwrap()
-                                && installed_package.version.nth(1).unwrap()
-                                    == package.version.nth(1).unwrap()
-                        }) {
+                    Build::Lts(_) => {
+                        if !installed_packages
+                            .iter()
+                            .any(|installed_package| installed_package.build == package.build)
+                        {
                             installed_packages.push(package.clone());
                         }
                     }
@@ -448,12 +546,11 @@ pub trait ReleaseType:
                         }
                     }
                 }
-                Build::Lts => {
-                    if let Some(package) = self.iter_mut().find(|package| {
-                        installed_package.version.nth(0).unwrap() == package.version.nth(0).unwrap()
-                            && installed_package.version.nth(1).unwrap()
-                                == package.version.nth(1).unwrap()
-                    }) {
+                Build::Lts(_) => {
+                    if let Some(package) = self
+                        .iter_mut()
+                        .find(|package| installed_package.build == package.build)
+                    {
                         if package.date > installed_package.date {
                             package.status = PackageStatus::Update;
                         }
@@ -487,10 +584,9 @@ pub trait ReleaseType:
             let mut handles = Vec::new();
             for (index, url) in checkables {
                 let handle = tokio::task::spawn(async move {
-                    if reqwest::get(&url).await.unwrap().status().is_client_error() {
-                        Some(index)
-                    } else {
-                        None
+                    match http_client().get(&url).send().await {
+                        Ok(response) if response.status().is_client_error() => Some(index),
+                        _ => None,
                     }
                 });
                 handles.push(handle);
@@ -522,7 +618,7 @@ pub trait ReleaseType:
         if let Ok(db) = read_to_string(self.get_db_path()) {
             match from_str(&db) {
                 Ok(self_db) => *self = self_db,
-                Err(e) => eprintln!(
+                Err(e) => log::error!(
                     "Error reading database file `{}` with error:\n{}",
                     self.get_db_path().file_name().unwrap().to_str().unwrap(),
                     e
@@ -563,8 +659,11 @@ impl BuilderBuild {
             BuilderBuild::PatchLatest => "https://builder.blender.org/download/patch/",
             BuilderBuild::PatchArchive => "https://builder.blender.org/download/patch/archive/",
         };
-        let document = get_document(url).await;
         let mut packages = Vec::new();
+        let document = match get_document(url).await {
+            Some(document) => document,
+            None => return packages,
+        };
 
         let (platform, os) = {
             if cfg!(target_os = "linux") {
@@ -634,6 +733,7 @@ impl BuilderBuild {
             let date = NaiveDateTime::parse_from_str(&date_string, "%B %d, %T-%Y").unwrap();
 
             let package = Package {
+                release_notes_url: release_notes_url(&version),
                 version,
                 name,
                 build,
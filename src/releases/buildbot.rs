@@ -0,0 +1,93 @@
+use crate::{helpers::http_client, package::BuildHealth};
+use serde::Deserialize;
+
+/// Buildbot's result codes, see `buildbot.process.results`.
+const SUCCESS: u8 = 0;
+const FAILURE: u8 = 2;
+const EXCEPTION: u8 = 4;
+
+#[derive(Deserialize)]
+struct Changes {
+    changes: Vec<Change>,
+}
+
+#[derive(Deserialize)]
+struct Change {
+    changeid: u64,
+}
+
+#[derive(Deserialize)]
+struct Builds {
+    builds: Vec<Build>,
+}
+
+#[derive(Deserialize)]
+struct Build {
+    /// Missing while the build is still running.
+    results: Option<u8>,
+}
+
+/// Sums up the results of every build the buildbot ran for the given commit: green when they
+/// all passed, red when any failed and yellow for anything in between, like warnings or
+/// cancelled builds.
+pub async fn fetch_build_health(api_url: &str, commit: &str) -> BuildHealth {
+    if commit.is_empty() {
+        return BuildHealth::Unknown;
+    }
+
+    match fetch_results(api_url.trim_end_matches('/'), commit).await {
+        Ok(results) if results.is_empty() => BuildHealth::Unknown,
+        Ok(results) => {
+            if results
+                .iter()
+                .any(|result| *result == FAILURE || *result == EXCEPTION)
+            {
+                BuildHealth::Red
+            } else if results.iter().all(|result| *result == SUCCESS) {
+                BuildHealth::Green
+            } else {
+                BuildHealth::Yellow
+            }
+        }
+        Err(e) => {
+            log::warn!("Couldn't get the build health of commit {}: {}", commit, e);
+            BuildHealth::Unknown
+        }
+    }
+}
+
+async fn fetch_results(api_url: &str, commit: &str) -> Result<Vec<u8>, reqwest::Error> {
+    // The packages only know the short hash, so the full one has to be looked up.
+    let changes: Changes = http_client()
+        .get(format!("{}/changes", api_url))
+        .query(&[
+            ("revision__contains", commit),
+            ("field", "changeid"),
+            ("limit", "1"),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    let change = match changes.changes.first() {
+        Some(change) => change,
+        None => return Ok(Vec::new()),
+    };
+
+    let builds: Builds = http_client()
+        .get(format!("{}/changes/{}/builds", api_url, change.changeid))
+        .query(&[("field", "results")])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    Ok(builds
+        .builds
+        .into_iter()
+        .filter_map(|build| build.results)
+        .collect())
+}
@@ -0,0 +1,42 @@
+use crate::{helpers::http_client, package::Change};
+use select::{
+    document::Document,
+    predicate::{Class, Name, Predicate},
+};
+use url::Url;
+
+const REPOSITORY_URL: &str = "https://projects.blender.org/blender/blender";
+
+/// The commits that went in between two builds, newest first, as listed on the repository's
+/// compare page.
+pub async fn fetch_commits(old_commit: String, new_commit: String) -> Result<Vec<Change>, String> {
+    if old_commit.is_empty() || new_commit.is_empty() {
+        return Err(String::from("the commits of the packages aren't known"));
+    }
+
+    let url = format!("{}/compare/{}...{}", REPOSITORY_URL, old_commit, new_commit);
+    let response = http_client()
+        .get(&url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|e| e.to_string())?;
+    let bytes = response.bytes().await.map_err(|e| e.to_string())?;
+    let document = Document::from_read(&bytes[..]).map_err(|e| e.to_string())?;
+
+    let origin = Url::parse(REPOSITORY_URL).unwrap();
+    Ok(document
+        .find(Name("tr"))
+        .filter_map(|row| {
+            let link = row
+                .find(Name("td").and(Class("sha")).descendant(Name("a")))
+                .next()?
+                .attr("href")?;
+            let text = row.find(Class("message-wrapper")).next()?.text();
+            Some(Change {
+                text: text.trim().to_string(),
+                url: origin.join(link).ok()?.to_string(),
+            })
+        })
+        .collect())
+}
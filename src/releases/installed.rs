@@ -1,12 +1,19 @@
 use crate::{
-    package::{Build, Package},
-    settings::{get_setting, save_settings, set_setting},
+    helpers::{blender_executable, printed_blender_version},
+    package::{release_notes_url, Build, Os, Package, PackageState, PackageStatus},
+    settings::{get_setting, SettingsService},
 };
+use chrono::{DateTime, Duration, Utc};
 use derive_deref::{Deref, DerefMut};
 use ron::from_str;
-use std::fs::{create_dir_all, read_dir, read_to_string, remove_dir_all};
+use std::{
+    collections::{HashMap, HashSet},
+    fs::{create_dir_all, read_dir, read_to_string, remove_dir_all},
+    process::Command,
+};
+use versions::Versioning;
 
-#[derive(Debug, Default, Deref, DerefMut)]
+#[derive(Clone, Debug, Default, Deref, DerefMut)]
 pub struct Installed(Vec<Package>);
 
 impl Installed {
@@ -20,10 +27,13 @@ impl Installed {
 
             if package_info.exists() {
                 if let Ok(package_string) = read_to_string(&package_info) {
-                    match from_str(&package_string) {
-                        Ok(package) => self.push(package),
+                    match from_str::<Package>(&package_string) {
+                        Ok(mut package) => {
+                            package.fill_lts_series();
+                            self.push(package)
+                        }
                         Err(e) => {
-                            eprintln!(
+                            log::error!(
                                 "Error reading package info file: {}.\nRemoving installed package.",
                                 e
                             );
@@ -34,11 +44,91 @@ impl Installed {
             }
         }
 
+        // Local builds live outside the packages directory, so they're kept in the settings.
+        for local_build in get_setting().local_builds.iter() {
+            let mut local_build = local_build.clone();
+            local_build.state = PackageState::Installed;
+            self.push(local_build);
+        }
+
         self.sort_by_key(|x| x.date);
         self.reverse();
     }
 
-    pub fn update_default(&self) {
+    /// Looks for packages copied into the packages directory by hand, which have no package
+    /// info file. Each one's Blender is asked for its version, and what it can't tell is filled
+    /// in as a stable archive build. Packages in `known` were already found and are kept as
+    /// they are, so Blender only runs once for each.
+    pub fn scan_unknown(&self, known: &[Package]) -> Vec<Package> {
+        let entries = match read_dir(&get_setting().packages_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry.path().is_dir() && !entry.path().join("package_info.ron").exists()
+            })
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                if name.starts_with('.') || self.iter().any(|package| package.name == name) {
+                    return None;
+                }
+                if let Some(package) = known.iter().find(|package| package.name == name) {
+                    return Some(package.clone());
+                }
+
+                let executable = blender_executable(&name);
+                if !executable.exists() {
+                    return None;
+                }
+
+                let version = Command::new(&executable)
+                    .arg("--version")
+                    .output()
+                    .ok()
+                    .and_then(|output| {
+                        printed_blender_version(&String::from_utf8_lossy(&output.stdout))
+                            .and_then(Versioning::new)
+                    })
+                    .unwrap_or_default();
+                log::info!("Found {} installed by hand, version {}.", name, version);
+
+                let date = entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .map(|modified| DateTime::<Utc>::from(modified).naive_utc())
+                    .unwrap_or_else(|_| Utc::now().naive_utc());
+
+                let os = if cfg!(target_os = "linux") {
+                    Os::Linux
+                } else if cfg!(target_os = "windows") {
+                    Os::Windows
+                } else {
+                    Os::MacOs
+                };
+
+                let mut package = Package {
+                    release_notes_url: release_notes_url(&version),
+                    version,
+                    name,
+                    build: Build::StableArchive,
+                    date,
+                    os,
+                    state: PackageState::Installed,
+                    status: PackageStatus::External,
+                    ..Package::default()
+                };
+                package.size = package.disk_usage();
+                Some(package)
+            })
+            .collect()
+    }
+
+    /// Switches the default package to a newer one of the same build and version when
+    /// `use_latest_as_default` is set. Returns the old and new default if it did.
+    pub fn update_default(&self) -> Option<(Package, Package)> {
         if get_setting().use_latest_as_default && get_setting().default_package.is_some() {
             let default_package = get_setting().default_package.clone().unwrap();
             // TODO: Fix build comparison.
@@ -46,22 +136,95 @@ impl Installed {
             // and installed with another Build due to BuildType. I could save the BuildType as
             // well and compare that, but it could get out of sync so I'm not sure what to do.
             if let Some(new_default) = self.iter().find(|package| {
-                package.build == default_package.build
+                package.local_path.is_none()
+                    && package.build == default_package.build
                     && package.version.nth(0).unwrap() == default_package.version.nth(0).unwrap()
                     && package.version.nth(1).unwrap() == default_package.version.nth(1).unwrap()
                     && package.version.nth(2).unwrap() >= default_package.version.nth(2).unwrap()
             }) {
                 if new_default.date > default_package.date {
-                    set_setting().default_package = Some(new_default.clone());
-                    save_settings();
+                    SettingsService::update(|settings| {
+                        settings.default_package = Some(new_default.clone())
+                    });
 
                     println!(
                             "Installed an update for the default package, switched from:\n{} | {}\nTo:\n{} | {}",
                             default_package.name, default_package.date, new_default.name, new_default.date
                         );
+                    return Some((default_package, new_default.clone()));
                 }
             }
         }
+        None
+    }
+
+    /// Removes the oldest installed packages of each build past the amount set to be kept.
+    /// Daily, experimental and patch builds are counted per branch, and LTS per release series.
+    /// The default package is counted but never removed.
+    /// Removes the installed packages past the number to keep of their build, except for the
+    /// default package and the ones in `keep`.
+    pub fn remove_old_packages(&self, keep: &HashSet<String>) {
+        let mut kept: HashMap<String, usize> = HashMap::new();
+
+        // Already sorted from newest to oldest when fetched. Local builds aren't counted.
+        for package in self.iter().filter(|package| package.local_path.is_none()) {
+            let (group, keep_count) = match &package.build {
+                Build::DailyLatest(name) | Build::DailyArchive(name) => {
+                    (format!("daily {}", name), get_setting().keep_count_daily)
+                }
+                Build::ExperimentalLatest(name) | Build::ExperimentalArchive(name) => (
+                    format!("experimental {}", name),
+                    get_setting().keep_count_experimental,
+                ),
+                Build::PatchLatest(name) | Build::PatchArchive(name) => {
+                    (format!("patch {}", name), get_setting().keep_count_patch)
+                }
+                // Archived and custom packages are dated when they're added, so they'd push the
+                // latest stable release out if counted along with it.
+                Build::StableLatest => (
+                    String::from("stable latest"),
+                    get_setting().keep_count_stable,
+                ),
+                Build::StableArchive => (
+                    String::from("stable archive"),
+                    get_setting().keep_count_stable,
+                ),
+                Build::Lts(series) => (format!("lts {}", series), get_setting().keep_count_lts),
+            };
+
+            if let Some(keep_count) = keep_count {
+                let count = kept.entry(group).or_default();
+                *count += 1;
+                if *count > keep_count
+                    && !get_setting().is_any_default_package(package)
+                    && !keep.contains(&package.name)
+                {
+                    package.remove();
+                }
+            }
+        }
+    }
+
+    /// Removes the installed packages built more than `days` days ago, except for the default
+    /// package, favourites and the ones in `keep`.
+    pub fn cleanup_by_age(&self, days: u64, keep: &HashSet<String>) {
+        let oldest = Utc::now().naive_utc() - Duration::days(days as i64);
+
+        for package in self.iter() {
+            if package.date < oldest
+                && package.local_path.is_none()
+                && !get_setting().is_any_default_package(package)
+                && !keep.contains(&package.name)
+                && !get_setting().favourite_packages.contains(&package.name)
+            {
+                log::info!(
+                    "Removing {}, built more than {} days ago.",
+                    package.name,
+                    days
+                );
+                package.remove();
+            }
+        }
     }
 
     pub fn remove_all(&mut self) {
@@ -135,7 +298,7 @@ impl Installed {
 
     pub fn remove_lts(&mut self) {
         for package in self.iter() {
-            if package.build == Build::Lts {
+            if matches!(package.build, Build::Lts(_)) {
                 package.remove();
             }
         }
@@ -0,0 +1,178 @@
+use crate::{
+    helpers::{download_client, http_client},
+    settings::get_setting,
+};
+use clap::crate_version;
+use self_update::{Extract, Move};
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::{
+    env::{args, current_exe},
+    fs::{remove_file, write},
+    path::PathBuf,
+    process::{exit, Command},
+};
+
+const LATEST_RELEASE_URL: &str =
+    "https://api.github.com/repos/masscollaborationlabs/Ablavema/releases/latest";
+
+#[derive(Clone, Debug)]
+pub struct LauncherRelease {
+    pub version: Version,
+    pub url: String,
+    archive_name: String,
+    archive_url: String,
+    checksum_url: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubRelease {
+    tag_name: String,
+    html_url: String,
+    assets: Vec<GitHubAsset>,
+}
+
+#[derive(Deserialize)]
+struct GitHubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+pub struct LauncherUpdate;
+
+impl LauncherUpdate {
+    /// Returns the latest release if it's newer than the running launcher and has a build for
+    /// this platform.
+    pub async fn check() -> Option<LauncherRelease> {
+        let release: GitHubRelease = http_client()
+            .get(LATEST_RELEASE_URL)
+            .header(reqwest::header::USER_AGENT, "Ablavema")
+            .send()
+            .await
+            .ok()?
+            .json()
+            .await
+            .ok()?;
+
+        let version = Version::parse(release.tag_name.trim_start_matches('v')).ok()?;
+        if version <= Version::parse(crate_version!()).unwrap() {
+            return None;
+        }
+
+        let archive = release.assets.iter().find(|asset| {
+            asset.name.contains(self_update::get_target()) && !asset.name.ends_with(".sha256")
+        })?;
+        // Releases publish a single `ablavema-<version>.sha256` listing every archive, as
+        // written by `sha256sum`.
+        let checksum = release
+            .assets
+            .iter()
+            .find(|asset| asset.name == format!("{}.sha256", archive.name))
+            .or_else(|| {
+                release.assets.iter().find(|asset| {
+                    asset.name == format!("ablavema-{}.sha256", release.tag_name)
+                        || asset.name == format!("ablavema-{}.sha256", version)
+                })
+            })?;
+
+        Some(LauncherRelease {
+            version,
+            url: release.html_url,
+            archive_name: archive.name.clone(),
+            archive_url: archive.browser_download_url.clone(),
+            checksum_url: checksum.browser_download_url.clone(),
+        })
+    }
+
+    /// Downloads the release, verifies it against its published SHA256 and replaces the
+    /// running executable with the one inside the archive.
+    pub async fn install(release: LauncherRelease) -> Result<(), String> {
+        let archive = download_client()
+            .get(&release.archive_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("couldn't download the update: {}", e))?
+            .bytes()
+            .await
+            .map_err(|e| format!("couldn't download the update: {}", e))?;
+        let checksum = http_client()
+            .get(&release.checksum_url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("couldn't download the checksum: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("couldn't download the checksum: {}", e))?;
+
+        let expected = archive_checksum(&checksum, &release.archive_name)
+            .ok_or_else(|| format!("the checksum file doesn't list {}", release.archive_name))?;
+        let actual = Sha256::digest(&archive)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+        if !expected.eq_ignore_ascii_case(&actual) {
+            return Err(String::from(
+                "checksum mismatch, the update was not applied",
+            ));
+        }
+
+        let archive_path = get_setting().cache_dir.join(&release.archive_name);
+        write(&archive_path, &archive).map_err(|e| e.to_string())?;
+
+        let bin_archive_path = PathBuf::from(format!(
+            "ablavema-{}-{}/{}",
+            release.version,
+            self_update::get_target(),
+            if cfg!(target_os = "windows") {
+                "ablavema.exe"
+            } else {
+                "ablavema"
+            }
+        ));
+        let result = Extract::from_source(&archive_path)
+            .extract_file(&get_setting().cache_dir, &bin_archive_path)
+            .map_err(|e| format!("couldn't extract the update: {}", e));
+        let _ = remove_file(&archive_path);
+        result?;
+
+        let bin_path = get_setting().cache_dir.join(&bin_archive_path);
+        let current_exe = current_exe().map_err(|e| e.to_string())?;
+        let temp_path = current_exe.with_extension("old");
+        Move::from_source(&bin_path)
+            .replace_using_temp(&temp_path)
+            .to_dest(&current_exe)
+            .map_err(|e| format!("couldn't replace the executable: {}", e))
+    }
+
+    /// Starts the freshly installed launcher with the same arguments and exits this one.
+    pub fn restart() -> ! {
+        match Command::new(current_exe().unwrap())
+            .args(args().skip(1))
+            .spawn()
+        {
+            Ok(_) => exit(0),
+            Err(e) => {
+                log::error!("Couldn't restart the launcher: {}", e);
+                exit(1)
+            }
+        }
+    }
+}
+
+/// Picks the checksum of an archive out of the output of `sha256sum`, which has a line like
+/// `<hash>  <file name>` for each file. A file with a single bare hash is taken as is.
+fn archive_checksum<'a>(checksums: &'a str, archive_name: &str) -> Option<&'a str> {
+    let mut lines = checksums.lines().filter(|line| !line.trim().is_empty());
+    let listed = checksums.lines().find_map(|line| {
+        let (hash, file_name) = line.trim().split_once(char::is_whitespace)?;
+        // Binary mode marks the file name with an asterisk.
+        (file_name.trim().trim_start_matches('*') == archive_name).then_some(hash)
+    });
+    listed.or_else(|| match (lines.next(), lines.next()) {
+        (Some(line), None) if !line.trim().contains(char::is_whitespace) => Some(line.trim()),
+        _ => None,
+    })
+}
@@ -1,6 +1,6 @@
 use crate::{
     helpers::{get_document, get_file_stem},
-    package::{Build, Change, Os, Package},
+    package::{release_notes_url, Build, Change, Os, Package},
     releases::{stable_archive::fetch_stable_archive_version, ReleaseType},
     settings::get_setting,
 };
@@ -56,7 +56,10 @@ impl ReleaseType for Lts {
         };
 
         for (lts_url, lts_ver, lts_ver_path) in lts_info {
-            let document = get_document(lts_url).await;
+            let document = match get_document(lts_url).await {
+                Some(document) => document,
+                None => continue,
+            };
 
             for rev in 0.. {
                 let lts_id = format!("lts-release-{}{}", lts_ver, rev);
@@ -139,9 +142,10 @@ impl ReleaseType for Lts {
                     };
 
                     let package = Package {
+                        release_notes_url: release_notes_url(&version),
                         version: version.clone(),
                         name: get_file_stem(archive_name).to_string(),
-                        build: Build::Lts,
+                        build: Build::Lts(Build::lts_series(&version)),
                         date,
                         url,
                         os,
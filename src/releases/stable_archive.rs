@@ -1,6 +1,6 @@
 use crate::{
     helpers::{get_document, get_file_stem},
-    package::{Build, Os, Package},
+    package::{release_notes_url, Build, Os, Package},
     releases::ReleaseType,
     settings::{get_setting, ARCHIVE_DATE_RE},
 };
@@ -23,7 +23,11 @@ impl ReleaseType for StableArchive {
 
         let versions = {
             let mut versions = Vec::new();
-            let document = get_document("https://ftp.nluug.nl/pub/graphics/blender/release/").await;
+            let document =
+                match get_document("https://ftp.nluug.nl/pub/graphics/blender/release/").await {
+                    Some(document) => document,
+                    None => return stable_archive,
+                };
 
             for node in document.find(Name("a")) {
                 let url_path = node.attr("href").unwrap();
@@ -64,7 +68,10 @@ pub async fn fetch_stable_archive_version(version: String) -> Vec<Package> {
         "https://ftp.nluug.nl/pub/graphics/blender/release/", version
     );
 
-    let document = get_document(url.as_str()).await;
+    let document = match get_document(url.as_str()).await {
+        Some(document) => document,
+        None => return packages,
+    };
 
     let version = version.strip_prefix("Blender").unwrap().replace('/', "");
 
@@ -188,6 +195,7 @@ pub async fn fetch_stable_archive_version(version: String) -> Vec<Package> {
         };
 
         let package = Package {
+            release_notes_url: release_notes_url(&version),
             version,
             name: get_file_stem(build).to_string(),
             build: Build::StableArchive,
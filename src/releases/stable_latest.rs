@@ -1,6 +1,6 @@
 use crate::{
     helpers::{get_document, get_file_stem},
-    package::{Build, Os, Package},
+    package::{release_notes_url, Build, Os, Package},
     releases::{stable_archive::fetch_stable_archive_version, ReleaseType},
     settings::get_setting,
 };
@@ -20,7 +20,10 @@ impl ReleaseType for StableLatest {
     async fn fetch() -> Self {
         let (mut package, version_path) = {
             let url = "https://www.blender.org/download/";
-            let document = get_document(url).await;
+            let document = match get_document(url).await {
+                Some(document) => document,
+                None => return Self::default(),
+            };
 
             let (os, targ_os) = {
                 if cfg!(target_os = "linux") {
@@ -77,6 +80,7 @@ impl ReleaseType for StableLatest {
             };
 
             let package = Package {
+                release_notes_url: release_notes_url(&version),
                 version,
                 name: get_file_stem(&url).to_string(),
                 build: Build::StableLatest,
@@ -11,20 +11,19 @@ use std::{
 pub struct SelfUpdater;
 
 impl SelfUpdater {
-    pub fn fetch() -> Option<Vec<Release>> {
+    pub fn fetch() -> Result<Option<Vec<Release>>, String> {
         let releases = ReleaseList::configure()
             .repo_owner("AlexChaplinBraz")
             .repo_name("Ablavema")
             .with_target(self_update::get_target())
             .build()
-            .unwrap()
-            .fetch()
-            .unwrap();
+            .and_then(|release_list| release_list.fetch())
+            .map_err(|e| format!("Failed to fetch Ablavema releases: {}", e))?;
 
         if releases.is_empty() {
-            None
+            Ok(None)
         } else {
-            Some(releases)
+            Ok(Some(releases))
         }
     }
 
@@ -43,16 +42,16 @@ impl SelfUpdater {
         }
     }
 
-    pub fn change(releases: Vec<Release>, version: String) {
+    pub fn change(releases: Vec<Release>, version: String) -> Result<(), String> {
         let asset = releases
             .iter()
             .find(|release| release.version == version)
-            .unwrap()
-            .asset_for(self_update::get_target())
-            .unwrap();
+            .and_then(|release| release.asset_for(self_update::get_target()))
+            .ok_or_else(|| format!("No Ablavema {} release found for this platform.", version))?;
 
         let archive_path = get_setting().cache_dir.join(asset.name);
-        let archive = File::create(&archive_path).unwrap();
+        let archive = File::create(&archive_path)
+            .map_err(|e| format!("{}: {}", archive_path.display(), e))?;
 
         self_update::Download::from_url(&asset.download_url)
             .set_header(
@@ -60,7 +59,7 @@ impl SelfUpdater {
                 "application/octet-stream".parse().unwrap(),
             )
             .download_to(&archive)
-            .unwrap();
+            .map_err(|e| format!("Failed to download Ablavema {}: {}", version, e))?;
 
         let bin_archive_path = PathBuf::from(if cfg!(target_os = "linux") {
             format!(
@@ -82,12 +81,13 @@ impl SelfUpdater {
 
         self_update::Extract::from_source(&archive_path)
             .extract_file(&get_setting().cache_dir, &bin_archive_path)
-            .unwrap();
+            .map_err(|e| format!("Failed to extract Ablavema {}: {}", version, e))?;
 
         let bin_path = get_setting().cache_dir.join(bin_archive_path);
-        let temp_path = current_exe().unwrap().parent().unwrap().join("temp");
+        let exe_path = current_exe().map_err(|e| e.to_string())?;
+        let temp_path = exe_path.parent().unwrap().join("temp");
 
-        move_file(bin_path, &temp_path, &CopyOptions::new()).unwrap();
-        rename(temp_path, current_exe().unwrap()).unwrap();
+        move_file(bin_path, &temp_path, &CopyOptions::new()).map_err(|e| e.to_string())?;
+        rename(temp_path, exe_path).map_err(|e| e.to_string())
     }
 }
@@ -1,13 +1,14 @@
 use crate::{
     gui::{
-        filters::Filters,
+        filters::{Filters, PackageGroup},
         sort_by::SortBy,
         style::Theme,
         tabs::{recent_files::RecentFiles, Tab},
     },
-    package::Package,
+    package::{Build, Package},
 };
 use derive_deref::{Deref, DerefMut};
+#[cfg(feature = "gui")]
 use device_query::Keycode;
 use directories_next::ProjectDirs;
 use lazy_static::{initialize, lazy_static};
@@ -17,15 +18,17 @@ use ron::{
     ser::{to_string_pretty, PrettyConfig},
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{
+    collections::{HashMap, HashSet},
     env::current_exe,
     env::var,
-    fs::{create_dir_all, read_to_string, File},
+    fs::{create_dir_all, read_to_string, rename, write, File},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
-        atomic::{AtomicBool, Ordering},
-        RwLock, RwLockReadGuard, RwLockWriteGuard,
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, PoisonError, RwLock,
     },
     time::{Duration, SystemTime},
 };
@@ -34,37 +37,106 @@ pub fn init_settings() {
     initialize(&SETTINGS);
 }
 
-pub fn save_settings() {
-    SETTINGS.read().unwrap().save()
+/// Shorthand for `SettingsService::get`, used all over for reading a setting or two.
+pub fn get_setting() -> Arc<Settings> {
+    SettingsService::get()
 }
 
-pub fn get_setting() -> RwLockReadGuard<'static, Settings> {
-    SETTINGS.read().unwrap()
-}
+/// The only owner of the settings. Reading hands out a snapshot that stays the same however
+/// long it's kept, and every change goes through a closure applied while nothing else can
+/// change them, so there's no lock guard to hold on to by mistake. A panic while changing them
+/// is shrugged off, keeping what was written until then instead of having every later access
+/// panic as well.
+pub struct SettingsService;
+
+impl SettingsService {
+    pub fn get() -> Arc<Settings> {
+        SETTINGS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Applies the change and saves the settings to the config file.
+    pub fn update<T>(f: impl FnOnce(&mut Settings) -> T) -> T {
+        let mut settings = SETTINGS.write().unwrap_or_else(PoisonError::into_inner);
+        // Only copied when a snapshot of the old settings is still around.
+        let result = f(Arc::make_mut(&mut settings));
+        settings.save();
+        result
+    }
+
+    /// Applies the change without saving it, for changes coming in bursts like the window
+    /// being resized. They're saved along with the next `update`, or with `save`.
+    pub fn update_unsaved<T>(f: impl FnOnce(&mut Settings) -> T) -> T {
+        let mut settings = SETTINGS.write().unwrap_or_else(PoisonError::into_inner);
+        f(Arc::make_mut(&mut settings))
+    }
 
-pub fn set_setting() -> RwLockWriteGuard<'static, Settings> {
-    SETTINGS.write().unwrap()
+    pub fn save() {
+        Self::get().save();
+    }
 }
 
 const CONFIG_NAME: &str = "config.ron";
+/// Either of these next to the executable turns on portable mode, the second one being what
+/// Blender itself looks for.
+const PORTABLE_MARKERS: [&str; 2] = ["portable", "portable.txt"];
+/// Settings left out when importing, since they belong to the machine they were exported from.
+const MACHINE_SETTINGS: [&str; 5] = [
+    "databases_dir",
+    "packages_dir",
+    "cache_dir",
+    "local_builds",
+    "custom_packages",
+];
 pub const CONFIG_FILE_ENV: &str = "ABLAVEMA_CONFIG_FILE";
 pub static PORTABLE: AtomicBool = AtomicBool::new(false);
 pub static CAN_CONNECT: AtomicBool = AtomicBool::new(true);
 pub static LAUNCH_GUI: AtomicBool = AtomicBool::new(false);
 pub static FETCHING: AtomicBool = AtomicBool::new(false);
 pub static INSTALLING: AtomicBool = AtomicBool::new(false);
+/// Seconds to wait before fetching again, as asked by the last `429 Too Many Requests`
+/// response, or 0. Picked up and reset by the GUI once the fetch is done.
+pub static RATE_LIMITED_SECS: AtomicU64 = AtomicU64::new(0);
+/// Which of `LOADING_STAGES` is being fetched while fetching everything.
+pub static LOADING_STAGE: AtomicUsize = AtomicUsize::new(0);
+pub const LOADING_STAGES: [&str; 9] = [
+    "daily builds",
+    "archived daily builds",
+    "experimental branches",
+    "archived experimental branches",
+    "patch builds",
+    "archived patch builds",
+    "stable releases",
+    "archived stable releases",
+    "long-term support releases",
+];
 // TODO: Consider making the text size user-adjustable.
 // Would need for all elements and sizes to scale properly.
-// Another requirement is for the window to remember its size and position.
 pub const TEXT_SIZE: u16 = 16;
+/// Also the placeholder of the template's input, since an empty one copies nothing.
+pub const DEFAULT_COPY_INFO_TEMPLATE: &str = "Blender {version} ({build}) built {date}";
 
 lazy_static! {
     pub static ref PROJECT_DIRS: ProjectDirs = ProjectDirs::from("", "", "Ablavema").unwrap();
     static ref PORTABLE_PATH: PathBuf = current_exe().unwrap().parent().unwrap().to_path_buf();
     static ref CONFIG_PATH: PathBuf = {
-        if PORTABLE_PATH.join("portable").exists() {
+        if PORTABLE_MARKERS
+            .iter()
+            .any(|marker| PORTABLE_PATH.join(marker).exists())
+        {
             PORTABLE.store(true, Ordering::Relaxed);
-            PORTABLE_PATH.join(CONFIG_NAME)
+            let config_path = PORTABLE_PATH.join("config");
+            create_dir_all(&config_path).unwrap();
+            // Portable installs used to keep their config right next to the executable.
+            let old_config_path = PORTABLE_PATH.join(CONFIG_NAME);
+            if old_config_path.exists() && !config_path.join(CONFIG_NAME).exists() {
+                if let Err(e) = rename(&old_config_path, config_path.join(CONFIG_NAME)) {
+                    eprintln!("Couldn't move the config file into the config directory: {}", e);
+                }
+            }
+            config_path.join(CONFIG_NAME)
         } else if let Ok(path) = var(CONFIG_FILE_ENV) {
             let config_path = PathBuf::from(path);
             create_dir_all(config_path.parent().unwrap()).unwrap();
@@ -75,20 +147,27 @@ lazy_static! {
             config_path.join(CONFIG_NAME)
         }
     };
-    static ref SETTINGS: RwLock<Settings> = RwLock::new(Settings::init());
+    pub static ref LOG_PATH: PathBuf = CONFIG_PATH.with_file_name("ablavema.log");
+    static ref SETTINGS: RwLock<Arc<Settings>> = RwLock::new(Arc::new(Settings::init()));
     pub static ref ARCHIVE_DATE_RE: Regex = Regex::new(r"\d{2}-\w{3}-\d{4}\s\d{2}:\d{2}").unwrap();
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(default)]
 pub struct Settings {
     pub recent_files: RecentFiles,
-    pub bookmarks: Bookmarks,
+    #[serde(alias = "bookmarks")]
+    pub favourite_packages: FavouritePackages,
     pub tab: Tab,
     pub default_package: Option<Package>,
     pub bypass_launcher: bool,
     pub modifier_key: ModifierKey,
     pub use_latest_as_default: bool,
+    /// Copies the Blender config of the old default package before `use_latest_as_default`
+    /// switches to a newer one.
+    pub backup_prefs_on_default_change: bool,
+    /// Hides packages whose version has a pre-release part, like `3.6.0-alpha`.
+    pub hide_pre_release: bool,
     pub check_updates_at_launch: bool,
     pub minutes_between_updates: u64,
     pub update_daily_latest: bool,
@@ -96,15 +175,74 @@ pub struct Settings {
     pub update_patch_latest: bool,
     pub update_stable_latest: bool,
     pub update_lts: bool,
+    /// Which build types are checked for updates at all, before the toggles above.
+    pub update_channel: UpdateChannel,
     pub databases_dir: PathBuf,
     pub packages_dir: PathBuf,
     pub cache_dir: PathBuf,
+    /// What installed packages are renamed to, with `{version}`, `{date}`, `{build}` and
+    /// `{branch}` filled in. `None` keeps the name of the archive.
+    pub naming_template: Option<String>,
+    /// What copying the info of a package puts in the clipboard, with the same tokens as
+    /// `naming_template`.
+    pub copy_info_template: String,
     pub last_update_time: SystemTime,
     pub filters: Filters,
+    pub package_groups: Vec<PackageGroup>,
+    /// Every tag given to a package so far, offered while tagging others.
+    pub known_tags: Vec<String>,
+    /// Locally compiled builds added by hand, listed along with the installed packages.
+    pub local_builds: Vec<Package>,
+    /// Packages added by the URL of their archive, listed along with the fetched ones.
+    pub custom_packages: Vec<Package>,
+    pub profiles: Vec<LaunchProfile>,
+    /// Name of the profile overriding how packages are launched, if any.
+    pub active_profile: Option<String>,
     pub sort_by: SortBy,
     pub theme: Theme,
+    /// Loaded once at launch, so changes apply after a restart.
+    pub font_family: FontFamily,
     pub self_updater: bool,
     pub check_self_updates_at_launch: bool,
+    pub window_width: u32,
+    pub window_height: u32,
+    pub window_x: Option<i32>,
+    pub window_y: Option<i32>,
+    /// Index of the monitor the window was last on, among the ones winit lists.
+    pub window_monitor: usize,
+    pub max_concurrent_downloads: usize,
+    pub resume_downloads: bool,
+    pub proxy_url: Option<String>,
+    pub proxy_bypass: Vec<String>,
+    /// Base URLs tried in order when a download can't connect to where the package is from.
+    pub mirror_urls: Vec<String>,
+    /// Base URL of the buildbot's REST API, asked for the build health of the packages shown.
+    /// Empty, the default, to not ask at all.
+    pub buildbot_api_url: String,
+    pub connect_timeout_secs: u64,
+    pub read_timeout_secs: u64,
+    pub keybindings: Keybindings,
+    pub auto_install_updates: bool,
+    pub auto_install_daily: bool,
+    pub auto_install_experimental: bool,
+    pub auto_install_patch: bool,
+    pub auto_install_stable: bool,
+    pub auto_install_lts: bool,
+    /// How many installed packages of each build to keep, removing the oldest ones past it.
+    /// `None` keeps all of them.
+    pub keep_count_daily: Option<usize>,
+    pub keep_count_experimental: Option<usize>,
+    pub keep_count_patch: Option<usize>,
+    pub keep_count_stable: Option<usize>,
+    pub keep_count_lts: Option<usize>,
+    /// Installed packages built more than this many days ago are removed after installing a
+    /// package. `None` keeps them no matter their age.
+    pub auto_cleanup_days: Option<u64>,
+    pub download_stats: DownloadStats,
+    /// Whether the setup wizard still has to be shown. Configs from before it existed read
+    /// as false, since those users are already set up.
+    #[serde(default)]
+    pub first_run: bool,
 }
 
 impl Settings {
@@ -113,13 +251,15 @@ impl Settings {
             Ok(text) => match from_str(&text) {
                 Ok(settings) => settings,
                 Err(e) => {
-                    eprintln!("Error reading config file: {}.\nUsing default settings.", e);
+                    log::error!("Error reading config file: {}.\nUsing default settings.", e);
                     Settings::default()
                 }
             },
             Err(_) => Settings::default(),
         };
 
+        settings.validate();
+
         if PORTABLE.load(Ordering::Relaxed) {
             settings.databases_dir = PORTABLE_PATH.join("databases");
             settings.packages_dir = PORTABLE_PATH.join("packages");
@@ -133,6 +273,130 @@ impl Settings {
         settings
     }
 
+    /// Brings values edited by hand or from older versions back into their allowed ranges.
+    fn validate(&mut self) {
+        if let Some(default_package) = &mut self.default_package {
+            default_package.fill_lts_series();
+        }
+        self.max_concurrent_downloads = self.max_concurrent_downloads.clamp(1, 8);
+        self.connect_timeout_secs = self.connect_timeout_secs.clamp(1, 60);
+        self.read_timeout_secs = self.read_timeout_secs.clamp(5, 120);
+        for keep_count in [
+            &mut self.keep_count_daily,
+            &mut self.keep_count_experimental,
+            &mut self.keep_count_patch,
+            &mut self.keep_count_stable,
+            &mut self.keep_count_lts,
+        ] {
+            *keep_count = keep_count.map(|count| count.clamp(1, 10));
+        }
+        self.auto_cleanup_days = self.auto_cleanup_days.map(|days| days.clamp(1, 365));
+        if self.active_profile().is_none() {
+            self.active_profile = None;
+        }
+    }
+
+    pub fn active_profile(&self) -> Option<&LaunchProfile> {
+        let name = self.active_profile.as_ref()?;
+        self.profiles.iter().find(|profile| profile.name == *name)
+    }
+
+    /// Sets the active profile's default package, or the global one without a profile.
+    pub fn set_default_package(&mut self, package: Option<Package>) {
+        let name = self.active_profile.clone();
+        match self
+            .profiles
+            .iter_mut()
+            .find(|profile| Some(&profile.name) == name.as_ref())
+        {
+            Some(profile) => profile.default_package = package,
+            None => self.default_package = package,
+        }
+    }
+
+    /// The default package shown in the launcher, which is the active profile's own while one
+    /// is active.
+    pub fn active_default_package(&self) -> Option<&Package> {
+        match self.active_profile() {
+            Some(profile) => profile.default_package.as_ref(),
+            None => self.default_package.as_ref(),
+        }
+    }
+
+    /// Whether the package is the default of any profile, or the default without one.
+    pub fn is_any_default_package(&self, package: &Package) -> bool {
+        self.default_package.as_ref() == Some(package)
+            || self
+                .profiles
+                .iter()
+                .any(|profile| profile.default_package.as_ref() == Some(package))
+    }
+
+    /// The package opened when bypassing the launcher, which is the active profile's default
+    /// package if it has one.
+    pub fn launch_default_package(&self) -> Option<&Package> {
+        self.active_profile()
+            .and_then(|profile| profile.default_package.as_ref())
+            .or(self.default_package.as_ref())
+    }
+
+    /// Writes the settings to a JSON file meant to be imported on another machine.
+    pub fn export(&self, path: &Path) -> Result<(), String> {
+        let settings = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        write(path, settings).map_err(|e| e.to_string())
+    }
+
+    /// Reads a file written by `export` and merges it over these settings, returning the
+    /// result along with the keys that were left out. Keys unknown to this version, or whose
+    /// value doesn't fit, are skipped, as are the locations of the databases, packages and
+    /// cache, the local builds and the custom packages, since those belong to the machine they
+    /// were exported from.
+    pub fn import(&self, path: &Path) -> Result<(Settings, Vec<String>), String> {
+        let imported: Value =
+            serde_json::from_str(&read_to_string(path).map_err(|e| e.to_string())?)
+                .map_err(|e| e.to_string())?;
+        let imported = match imported {
+            Value::Object(imported) => imported,
+            _ => return Err(String::from("not a settings file")),
+        };
+
+        let mut merged = serde_json::to_value(self).map_err(|e| e.to_string())?;
+        let mut skipped = Vec::new();
+        for (key, value) in imported {
+            if MACHINE_SETTINGS.contains(&key.as_str()) || merged.get(&key).is_none() {
+                skipped.push(key);
+                continue;
+            }
+
+            let previous = merged[&key].clone();
+            merged[&key] = value;
+            if serde_json::from_value::<Settings>(merged.clone()).is_err() {
+                merged[&key] = previous;
+                skipped.push(key);
+            }
+        }
+
+        let mut settings: Settings = serde_json::from_value(merged).map_err(|e| e.to_string())?;
+        settings.validate();
+        Ok((settings, skipped))
+    }
+
+    /// Whether updates of this build get installed at launch without asking.
+    pub fn auto_installs(&self, build: &Build) -> bool {
+        self.auto_install_updates
+            && match build {
+                Build::DailyLatest(_) => self.auto_install_daily,
+                Build::ExperimentalLatest(_) => self.auto_install_experimental,
+                Build::PatchLatest(_) => self.auto_install_patch,
+                Build::StableLatest => self.auto_install_stable,
+                Build::Lts(_) => self.auto_install_lts,
+                Build::DailyArchive(_)
+                | Build::ExperimentalArchive(_)
+                | Build::PatchArchive(_)
+                | Build::StableArchive => false,
+            }
+    }
+
     fn save(&self) {
         let mut config_file = File::create(&*CONFIG_PATH).unwrap();
         let settings = to_string_pretty(&self, PrettyConfig::new()).unwrap();
@@ -146,12 +410,14 @@ impl Default for Settings {
 
         Self {
             recent_files: RecentFiles::default(),
-            bookmarks: Bookmarks::default(),
+            favourite_packages: FavouritePackages::default(),
             tab: Tab::default(),
             default_package: None,
             bypass_launcher: false,
             modifier_key: ModifierKey::Shift,
             use_latest_as_default: true,
+            backup_prefs_on_default_change: false,
+            hide_pre_release: false,
             check_updates_at_launch: true,
             minutes_between_updates,
             update_daily_latest: true,
@@ -159,21 +425,127 @@ impl Default for Settings {
             update_patch_latest: true,
             update_stable_latest: true,
             update_lts: true,
+            update_channel: UpdateChannel::default(),
             databases_dir: PROJECT_DIRS.config_dir().join("databases"),
             packages_dir: PROJECT_DIRS.data_local_dir().to_path_buf(),
             cache_dir: PROJECT_DIRS.cache_dir().to_path_buf(),
+            naming_template: None,
+            copy_info_template: String::from(DEFAULT_COPY_INFO_TEMPLATE),
             last_update_time: SystemTime::now()
                 .checked_sub(Duration::from_secs(minutes_between_updates * 60))
                 .unwrap_or_else(SystemTime::now),
             filters: Filters::default(),
+            package_groups: Vec::new(),
+            known_tags: Vec::new(),
+            local_builds: Vec::new(),
+            custom_packages: Vec::new(),
+            profiles: Vec::new(),
+            active_profile: None,
             sort_by: SortBy::default(),
             theme: Theme::default(),
+            font_family: FontFamily::default(),
             self_updater: false,
             check_self_updates_at_launch: false,
+            window_width: 680,
+            window_height: 620,
+            window_x: None,
+            window_y: None,
+            window_monitor: 0,
+            max_concurrent_downloads: 2,
+            resume_downloads: true,
+            proxy_url: None,
+            proxy_bypass: Vec::new(),
+            mirror_urls: Vec::new(),
+            buildbot_api_url: String::new(),
+            connect_timeout_secs: 10,
+            read_timeout_secs: 30,
+            keybindings: Keybindings::default(),
+            auto_install_updates: false,
+            auto_install_daily: true,
+            auto_install_experimental: true,
+            auto_install_patch: true,
+            auto_install_stable: true,
+            auto_install_lts: true,
+            keep_count_daily: None,
+            keep_count_experimental: None,
+            keep_count_patch: None,
+            keep_count_stable: None,
+            keep_count_lts: None,
+            auto_cleanup_days: None,
+            download_stats: DownloadStats::default(),
+            first_run: true,
         }
     }
 }
 
+/// Offered in the profile picker for launching without a profile.
+pub const NO_PROFILE: &str = "No profile";
+
+/// Launch settings for a project, applied on top of the global ones while active.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct LaunchProfile {
+    pub name: String,
+    pub default_package: Option<Package>,
+    /// Set after the package's own environment variables, so they win over them.
+    pub env_vars: Vec<(String, String)>,
+    /// Passed after the package's own launch arguments.
+    pub launch_args: Vec<String>,
+}
+
+impl LaunchProfile {
+    pub fn args_text(&self) -> String {
+        self.launch_args.join(" ")
+    }
+
+    /// The environment variables as `KEY=value` pairs separated by semicolons.
+    pub fn env_text(&self) -> String {
+        self.env_vars
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+
+    pub fn parse_args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    pub fn parse_env(text: &str) -> Vec<(String, String)> {
+        text.split(';')
+            .filter_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                let key = key.trim();
+                (!key.is_empty()).then(|| (key.to_string(), value.trim().to_string()))
+            })
+            .collect()
+    }
+}
+
+/// The font used for all of the launcher's text. Fonts that can't be found fall back to the
+/// system one, and that to the bundled one.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FontFamily {
+    #[default]
+    Bundled,
+    System,
+    Monospace,
+    /// A font family name, like `Noto Sans`.
+    Custom(String),
+}
+
+impl std::fmt::Display for FontFamily {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let printable = match self {
+            FontFamily::Bundled => "Bundled",
+            FontFamily::System => "System",
+            FontFamily::Monospace => "Monospace",
+            FontFamily::Custom(_) => "Custom",
+        };
+        write!(f, "{}", printable)
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum ModifierKey {
     Shift,
@@ -184,6 +556,7 @@ pub enum ModifierKey {
 impl ModifierKey {
     pub const ALL: [ModifierKey; 3] = [ModifierKey::Shift, ModifierKey::Control, ModifierKey::Alt];
 
+    #[cfg(feature = "gui")]
     pub fn get_keycode(&self) -> Keycode {
         match self {
             ModifierKey::Shift => Keycode::LShift,
@@ -204,35 +577,164 @@ impl std::fmt::Display for ModifierKey {
     }
 }
 
-#[derive(Debug, Default, Deref, DerefMut, Deserialize, Serialize)]
-pub struct Bookmarks(Vec<String>);
+/// How far from stable releases updates are looked for. Each channel includes the ones
+/// before it.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum UpdateChannel {
+    StableOnly,
+    StableAndLts,
+    IncludeBranched,
+    #[default]
+    IncludeDaily,
+}
+
+impl UpdateChannel {
+    pub const ALL: [UpdateChannel; 4] = [
+        UpdateChannel::StableOnly,
+        UpdateChannel::StableAndLts,
+        UpdateChannel::IncludeBranched,
+        UpdateChannel::IncludeDaily,
+    ];
 
-impl Bookmarks {
-    pub fn update(&mut self, package_name: String) {
-        match self.iter().position(|bookmark| bookmark == &package_name) {
-            Some(index) => {
-                self.remove(index);
-            }
-            None => self.push(package_name),
+    pub fn allows_lts(&self) -> bool {
+        *self != UpdateChannel::StableOnly
+    }
+
+    /// Experimental and patch builds.
+    pub fn allows_branched(&self) -> bool {
+        matches!(
+            self,
+            UpdateChannel::IncludeBranched | UpdateChannel::IncludeDaily
+        )
+    }
+
+    pub fn allows_daily(&self) -> bool {
+        *self == UpdateChannel::IncludeDaily
+    }
+}
+
+impl std::fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let printable = match self {
+            UpdateChannel::StableOnly => "Stable",
+            UpdateChannel::StableAndLts => "Stable and LTS",
+            UpdateChannel::IncludeBranched => "Branched",
+            UpdateChannel::IncludeDaily => "Daily",
+        };
+        write!(f, "{}", printable)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum KeyAction {
+    CheckForUpdates,
+    FocusSearch,
+    OpenSettings,
+    ClearSearch,
+}
+
+impl KeyAction {
+    pub const ALL: [KeyAction; 4] = [
+        KeyAction::CheckForUpdates,
+        KeyAction::FocusSearch,
+        KeyAction::OpenSettings,
+        KeyAction::ClearSearch,
+    ];
+}
+
+impl std::fmt::Display for KeyAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let printable = match self {
+            KeyAction::CheckForUpdates => "Check for updates",
+            KeyAction::FocusSearch => "Search packages",
+            KeyAction::OpenSettings => "Open settings",
+            KeyAction::ClearSearch => "Clear search",
+        };
+        write!(f, "{}", printable)
+    }
+}
+
+/// A key along with the modifiers held down with it. The key is stored by the name of its
+/// `iced` key code, like `F5` or `Comma`.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct KeyCombo {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub key: String,
+}
+
+impl KeyCombo {
+    fn new(ctrl: bool, key: &str) -> Self {
+        Self {
+            ctrl,
+            alt: false,
+            shift: false,
+            key: key.to_string(),
         }
     }
 
-    pub fn clean(&mut self, packages: &[Package]) {
-        let mut indexes = Vec::new();
-
-        for bookmark in self.iter() {
-            if !packages.iter().any(|package| &package.name == bookmark) {
-                if let Some(index) = self
-                    .iter()
-                    .position(|old_bookmark| old_bookmark == bookmark)
-                {
-                    indexes.push(index);
-                }
-            }
+    pub fn escape() -> Self {
+        Self::new(false, "Escape")
+    }
+}
+
+impl std::fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
         }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+#[derive(Clone, Debug, Deref, DerefMut, Deserialize, Serialize)]
+pub struct Keybindings(HashMap<KeyAction, KeyCombo>);
+
+impl Keybindings {
+    pub fn action_for(&self, combo: &KeyCombo) -> Option<KeyAction> {
+        self.iter()
+            .find(|(_, bound_combo)| *bound_combo == combo)
+            .map(|(action, _)| *action)
+    }
+}
 
-        for (removed, index) in indexes.iter().enumerate() {
-            self.remove(index - removed);
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self(HashMap::from([
+            (KeyAction::CheckForUpdates, KeyCombo::new(false, "F5")),
+            (KeyAction::FocusSearch, KeyCombo::new(true, "F")),
+            (KeyAction::OpenSettings, KeyCombo::new(true, "Comma")),
+            (KeyAction::ClearSearch, KeyCombo::escape()),
+        ]))
+    }
+}
+
+/// Running totals shown on the About tab.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DownloadStats {
+    pub total_bytes_downloaded: u64,
+    pub total_packages_installed: u64,
+    pub total_packages_removed: u64,
+}
+
+#[derive(Clone, Debug, Default, Deref, DerefMut, Deserialize, Serialize)]
+pub struct FavouritePackages(HashSet<String>);
+
+impl FavouritePackages {
+    pub fn toggle(&mut self, package_name: String) {
+        if !self.remove(&package_name) {
+            self.insert(package_name);
         }
     }
+
+    pub fn clean(&mut self, packages: &[Package]) {
+        self.retain(|favourite| packages.iter().any(|package| &package.name == favourite));
+    }
 }